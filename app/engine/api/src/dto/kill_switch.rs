@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct KillSwitchTripRequest {
+    /// Also close every position and cancel every open order.
+    #[serde(default)]
+    pub flatten: bool,
+}
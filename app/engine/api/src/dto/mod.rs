@@ -1,2 +1,4 @@
 #[cfg(not(feature = "production"))]
 pub mod dev;
+pub mod kill_switch;
+pub mod signals;
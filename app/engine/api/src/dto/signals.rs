@@ -0,0 +1,45 @@
+use perp_signals::TradingSignal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct SignalTestRequest {
+    /// Message text, as posted by the provider.
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FailuresQuery {
+    pub limit: Option<u32>,
+}
+
+/// What one parser read from the message. `signal` is null when the layout did not match.
+#[derive(Debug, Serialize)]
+pub struct SignalTestResult {
+    pub parser: String,
+    pub signal: Option<ParsedSignal>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParsedSignal {
+    pub symbol: String,
+    pub side: &'static str,
+    pub entry: f64,
+    pub targets: Vec<f64>,
+    pub stop_loss: f64,
+    pub timeframe: String,
+    pub leverage: Option<u32>,
+}
+
+impl From<TradingSignal> for ParsedSignal {
+    fn from(signal: TradingSignal) -> Self {
+        Self {
+            symbol: signal.symbol.to_string(),
+            side: if signal.is_long { "LONG" } else { "SHORT" },
+            entry: signal.entry,
+            targets: signal.targets,
+            stop_loss: signal.stop_loss,
+            timeframe: signal.timeframe,
+            leverage: signal.leverage,
+        }
+    }
+}
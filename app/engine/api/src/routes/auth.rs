@@ -0,0 +1,47 @@
+use app_state::AppState;
+use axum::{
+    Extension,
+    extract::Request,
+    http::{StatusCode, header::AUTHORIZATION},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+/// Lets a request through only with `Authorization: Bearer <API_TOKEN>`.
+///
+/// For routes that trade or change risk limits. They are refused altogether when no token is set.
+pub async fn require_token(
+    Extension(state): Extension<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(token) = state.api_token.as_deref() else {
+        return (
+            StatusCode::FORBIDDEN,
+            "Set API_TOKEN to enable this endpoint.",
+        )
+            .into_response();
+    };
+
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match bearer {
+        Some(bearer) if matches_token(bearer, token) => next.run(request).await,
+        _ => (StatusCode::UNAUTHORIZED, "Missing or invalid API token.").into_response(),
+    }
+}
+
+// Compares every byte, so the time taken does not tell how much of the token was right.
+fn matches_token(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
@@ -1,5 +1,6 @@
 use app_state::AppState;
 use axum::{Extension, Json, http::StatusCode, response::IntoResponse};
+use domain::clock::now_ms;
 use domain::types::{trade::TradeApproved, trade_intent::TradeIntent};
 use std::sync::Arc;
 
@@ -28,7 +29,7 @@ pub async fn dev_trade_approved(
         }
     };
 
-    let approved: TradeApproved = intent.into();
+    let approved = TradeApproved::new(intent, now_ms());
 
     state
         .bus
@@ -0,0 +1,47 @@
+use app_state::AppState;
+use axum::{Extension, Json, Router, http::StatusCode, response::IntoResponse, routing::post};
+use publisher::types::{KillSwitchCommand, PulsgramEvent};
+use std::sync::Arc;
+
+use crate::dto::kill_switch::KillSwitchTripRequest;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/arm", post(arm))
+        .route("/disarm", post(disarm))
+        .route("/trip", post(trip))
+        .route("/reset", post(reset))
+}
+
+async fn arm(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    send(&state, KillSwitchCommand::Arm)
+}
+
+async fn disarm(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    send(&state, KillSwitchCommand::Disarm)
+}
+
+async fn trip(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(payload): Json<KillSwitchTripRequest>,
+) -> impl IntoResponse {
+    send(
+        &state,
+        KillSwitchCommand::Trip {
+            flatten: payload.flatten,
+        },
+    )
+}
+
+async fn reset(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    send(&state, KillSwitchCommand::Reset)
+}
+
+// The risk manager owns the switch; the API only forwards the command.
+fn send(state: &AppState, command: KillSwitchCommand) -> (StatusCode, String) {
+    let accepted = format!("Kill switch: {:?}", command);
+
+    state.bus.publish(PulsgramEvent::KillSwitch(command));
+
+    (StatusCode::ACCEPTED, accepted)
+}
@@ -1,18 +1,22 @@
+mod auth;
 mod cors;
 #[cfg(not(feature = "production"))]
 mod dev;
+mod kill_switch;
 mod ping;
+mod signals;
 
 use axum::{
     Extension, Router,
     body::Body,
     http::{Request, StatusCode},
+    middleware,
     response::IntoResponse,
     routing::get,
 };
 use std::sync::Arc;
 
-use crate::routes::{cors::build_cors_layer, ping::ping};
+use crate::routes::{auth::require_token, cors::build_cors_layer, ping::ping};
 
 pub fn create(app_state: Arc<app_state::AppState>, prefix: &str) -> Router {
     Router::new()
@@ -27,7 +31,9 @@ fn routes() -> Router {
 }
 
 fn _routes() -> Router {
-    let router = Router::new().route("/ping", get(ping));
+    let router = Router::new()
+        .route("/ping", get(ping))
+        .merge(protected_routes());
 
     #[cfg(not(feature = "production"))]
     let router = router.nest("/dev", dev::routes());
@@ -35,6 +41,15 @@ fn _routes() -> Router {
     router
 }
 
+// Can flatten positions, lift the loss limits or inject trades, and the server binds every
+// interface in production.
+fn protected_routes() -> Router {
+    Router::new()
+        .nest("/kill-switch", kill_switch::routes())
+        .nest("/signals", signals::routes())
+        .route_layer(middleware::from_fn(require_token))
+}
+
 //TODO: Shared
 pub async fn fallback(req: Request<Body>) -> impl IntoResponse {
     let path = req.uri().path();
@@ -0,0 +1,105 @@
+use app_state::AppState;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query},
+    http::StatusCode,
+    routing::{get, post},
+};
+use domain::types::parse_failure::CapturedParseFailure;
+use providers::client::PersistenceClient;
+use providers::errors::ProvidersError;
+use publisher::types::PulsgramEvent;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::dto::signals::{FailuresQuery, ParsedSignal, SignalTestRequest, SignalTestResult};
+
+const DEFAULT_FAILURES_LIMIT: u32 = 100;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/test", post(test))
+        .route("/failures", get(failures))
+        .route("/failures/{id}/replay", post(replay))
+}
+
+/// Runs a pasted message through every parser, built-in and templates, without trading it.
+async fn test(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(payload): Json<SignalTestRequest>,
+) -> Json<Vec<SignalTestResult>> {
+    let results = state
+        .signal_parsers
+        .test(&payload.text)
+        .into_iter()
+        .map(|(parser, signal)| SignalTestResult {
+            parser: parser.to_string(),
+            signal: signal.map(Into::into),
+        })
+        .collect();
+
+    Json(results)
+}
+
+/// Messages that looked like signals but were not parsed, newest first.
+async fn failures(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(query): Query<FailuresQuery>,
+) -> Result<Json<Vec<CapturedParseFailure>>, (StatusCode, String)> {
+    let persistence = persistence(&state)?;
+
+    persistence
+        .open_failures(query.limit.unwrap_or(DEFAULT_FAILURES_LIMIT))
+        .await
+        .map(Json)
+        .map_err(persistence_error)
+}
+
+/// Sends a captured message back through its source's parser. When it parses now, it is
+/// marked resolved and goes through the pipeline like a new signal, validation included.
+async fn replay(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ParsedSignal>), (StatusCode, String)> {
+    let persistence = persistence(&state)?;
+
+    let captured = persistence.failure(id).await.map_err(persistence_error)?;
+    let failure = captured.failure;
+
+    let Some(parser) = state.signal_parsers.get(failure.chat_id) else {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Chat {} is no longer a signal source", failure.chat_id),
+        ));
+    };
+
+    let Some(signal) = parser.parse(&failure.text) else {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Still not parsed by the {} parser", parser.name()),
+        ));
+    };
+
+    // Resolved first: a signal failing validation is captured again, reopening it.
+    persistence.resolve(id).await.map_err(persistence_error)?;
+
+    state.bus.publish(PulsgramEvent::SignalReplay(failure));
+
+    Ok((StatusCode::ACCEPTED, Json(signal.into())))
+}
+
+fn persistence(state: &AppState) -> Result<&PersistenceClient, (StatusCode, String)> {
+    state.persistence.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Persistence is not configured, set [providers] url".to_string(),
+    ))
+}
+
+fn persistence_error(error: ProvidersError) -> (StatusCode, String) {
+    match error {
+        ProvidersError::Status { status: 404, .. } => {
+            (StatusCode::NOT_FOUND, "Parse failure not found".to_string())
+        }
+        error => (StatusCode::BAD_GATEWAY, error.to_string()),
+    }
+}
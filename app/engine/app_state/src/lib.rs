@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use perp_signals::parsers::ParserRegistry;
+use providers::client::PersistenceClient;
 use telegram::dialogs::DialogData;
 use telegram_types::Client;
 
@@ -8,5 +10,11 @@ pub struct AppState {
     pub client: Arc<Client>,
     pub client_dispatcher: Arc<Client>,
     pub bus: Arc<publisher::EventBus>,
+    /// Compiled at startup from the built-in layouts and the configured templates.
+    pub signal_parsers: Arc<ParserRegistry>,
+    /// Provider registry and parse-failure queue. None when `[providers] url` is not set.
+    pub persistence: Option<PersistenceClient>,
+    /// Bearer token for the kill switch and signal routes, which are refused without one.
+    pub api_token: Option<String>,
     pub reqwest_client: reqwest::Client, // We can use reqwest::Client directly without wrapping it in Arc, since it's designed to be cloned and shared across threads.
 }
@@ -4,4 +4,11 @@ pub const TESTNET_SPOT: &str = "https://testnet.binance.vision";
 pub const SPOT: &str = "https://api.binance.com";
 pub const FUTURES: &str = "https://fapi.binance.com";
 
+pub const TESTNET_FUTURES_WS: &str = "wss://fstream.binancefuture.com";
+pub const FUTURES_WS: &str = "wss://fstream.binance.com";
+
 pub const MAX_LEVERAGE: u32 = 125;
+
+// Allowed `callbackRate` range for TRAILING_STOP_MARKET orders, in percent.
+pub const MIN_CALLBACK_RATE: f64 = 0.1;
+pub const MAX_CALLBACK_RATE: f64 = 10.0;
@@ -3,9 +3,9 @@ use reqwest::Method;
 
 use crate::{
     client::BinanceClient,
-    endpoints::{EXCHANGE_INFO, TICKER_PRICE},
+    endpoints::{EXCHANGE_INFO, KLINES, TICKER_PRICE},
     errors::BinanceError,
-    response_types::{ExchangeInfoResponse, TickerPriceResponse},
+    response_types::{ExchangeInfoResponse, Kline, TickerPriceResponse},
     utils::build_query,
 };
 
@@ -31,4 +31,22 @@ impl BinanceClient {
 
         Ok(price)
     }
+
+    /// `interval` uses Binance notation (1m, 15m, 1h, 4h, 1d, ...). Oldest kline comes first.
+    pub async fn get_klines(
+        &self,
+        symbol: Symbol,
+        interval: &str,
+        limit: u16,
+    ) -> Result<Vec<Kline>, BinanceError> {
+        let query = build_query(&[
+            ("symbol", symbol.to_string()),
+            ("interval", interval.to_string()),
+            ("limit", limit.to_string()),
+        ]);
+
+        self.transport()
+            .api_key(Method::GET, KLINES, Some(query))
+            .await
+    }
 }
@@ -5,6 +5,7 @@ pub mod market;
 pub mod orders;
 
 pub const OPEN_ORDERS: &str = "fapi/v1/openOrders";
+pub const ALL_OPEN_ORDERS: &str = "fapi/v1/allOpenOrders";
 pub const COMMISSION_RATE: &str = "fapi/v1/commissionRate";
 pub const ACCOUNT_INFO: &str = "fapi/v3/account";
 pub const ORDER: &str = "fapi/v1/order";
@@ -14,3 +15,4 @@ pub const POSITION_RISK: &str = "fapi/v3/positionRisk";
 pub const LISTEN_KEY: &str = "fapi/v1/listenKey";
 pub const EXCHANGE_INFO: &str = "fapi/v1/exchangeInfo";
 pub const TICKER_PRICE: &str = "fapi/v1/ticker/price";
+pub const KLINES: &str = "fapi/v1/klines";
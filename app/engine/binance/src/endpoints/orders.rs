@@ -4,9 +4,9 @@ use reqwest::Method;
 use crate::{
     client::BinanceClient,
     constants::MAX_LEVERAGE,
-    endpoints::{LEVERAGE, OPEN_ORDERS, ORDER},
+    endpoints::{ALL_OPEN_ORDERS, LEVERAGE, OPEN_ORDERS, ORDER},
     errors::BinanceError,
-    response_types::{FuturesOrderResponse, SetLeverageResponse},
+    response_types::{CancelAllOrdersResponse, FuturesOrderResponse, SetLeverageResponse},
     utils::build_query,
 };
 
@@ -24,6 +24,19 @@ impl BinanceClient {
             .await
     }
 
+    pub async fn get_order(
+        &self,
+        symbol: Symbol,
+        order_id: i64,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        let query = build_query(&[
+            ("symbol", symbol.to_string()),
+            ("orderId", order_id.to_string()),
+        ]);
+
+        self.transport().signed(Method::GET, ORDER, query).await
+    }
+
     pub async fn cancel_order(
         &self,
         symbol: Symbol,
@@ -37,6 +50,17 @@ impl BinanceClient {
         self.transport().signed(Method::DELETE, ORDER, query).await
     }
 
+    pub async fn cancel_all_open_orders(
+        &self,
+        symbol: Symbol,
+    ) -> Result<CancelAllOrdersResponse, BinanceError> {
+        let query = build_query(&[("symbol", symbol.to_string())]);
+
+        self.transport()
+            .signed(Method::DELETE, ALL_OPEN_ORDERS, query)
+            .await
+    }
+
     pub async fn set_leverage(
         &self,
         symbol: Symbol,
@@ -92,4 +116,68 @@ impl BinanceClient {
 
         self.transport().signed(Method::POST, ORDER, query).await
     }
+
+    pub async fn place_trailing_stop_order_raw(
+        &self,
+        symbol: Symbol,
+        side: &OrderSide,
+        quantity: String,
+        callback_rate: String,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        let query = build_query(&[
+            ("symbol", symbol.to_string()),
+            ("side", side.to_string()),
+            ("type", "TRAILING_STOP_MARKET".to_string()),
+            ("quantity", quantity),
+            ("callbackRate", callback_rate),
+            ("reduceOnly", "true".to_string()),
+            ("workingType", "MARK_PRICE".to_string()),
+        ]);
+
+        self.transport().signed(Method::POST, ORDER, query).await
+    }
+
+    /// Reduce-only `STOP_MARKET` order for `quantity` once the mark price reaches `stop_price`.
+    /// Several can rest on the same side, one per trade.
+    pub async fn place_reduce_only_stop_market_order_raw(
+        &self,
+        symbol: Symbol,
+        side: &OrderSide,
+        quantity: String,
+        stop_price: String,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        let query = build_query(&[
+            ("symbol", symbol.to_string()),
+            ("side", side.to_string()),
+            ("type", "STOP_MARKET".to_string()),
+            ("quantity", quantity),
+            ("stopPrice", stop_price),
+            ("reduceOnly", "true".to_string()),
+            ("workingType", "MARK_PRICE".to_string()),
+        ]);
+
+        self.transport().signed(Method::POST, ORDER, query).await
+    }
+
+    /// Reduce-only `TAKE_PROFIT_MARKET` order for `quantity` once the mark price reaches
+    /// `stop_price`.
+    pub async fn place_take_profit_market_order_raw(
+        &self,
+        symbol: Symbol,
+        side: &OrderSide,
+        quantity: String,
+        stop_price: String,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        let query = build_query(&[
+            ("symbol", symbol.to_string()),
+            ("side", side.to_string()),
+            ("type", "TAKE_PROFIT_MARKET".to_string()),
+            ("quantity", quantity),
+            ("stopPrice", stop_price),
+            ("reduceOnly", "true".to_string()),
+            ("workingType", "MARK_PRICE".to_string()),
+        ]);
+
+        self.transport().signed(Method::POST, ORDER, query).await
+    }
 }
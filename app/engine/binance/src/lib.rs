@@ -7,6 +7,7 @@ pub mod constants;
 pub mod endpoints;
 pub mod errors;
 pub mod filters;
+pub mod network;
 pub mod services;
 pub mod utils;
 
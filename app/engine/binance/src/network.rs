@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+use crate::constants::{FUTURES, FUTURES_WS, TESTNET_FUTURES, TESTNET_FUTURES_WS};
+
+/// Futures environment an account lives on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Network {
+    Testnet,
+    Mainnet,
+}
+
+impl Network {
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Network::Testnet => TESTNET_FUTURES,
+            Network::Mainnet => FUTURES,
+        }
+    }
+
+    pub fn stream_url(&self) -> &'static str {
+        match self {
+            Network::Testnet => TESTNET_FUTURES_WS,
+            Network::Mainnet => FUTURES_WS,
+        }
+    }
+}
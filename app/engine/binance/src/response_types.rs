@@ -126,6 +126,13 @@ pub struct FuturesOrderResponse {
     pub good_till_date: Option<i64>,
 }
 
+// {"code": 200, "msg": "The operation of cancel all open order is done."}
+#[derive(Debug, Deserialize)]
+pub struct CancelAllOrdersResponse {
+    pub code: i64,
+    pub msg: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SetLeverageResponse {
@@ -219,3 +226,23 @@ pub enum ExchangeFilter {
     #[serde(other)]
     Other,
 }
+
+//https://developers.binance.com/docs/derivatives/usds-margined-futures/market-data/rest-api/Kline-Candlestick-Data
+// Each kline is returned as a positional array, not an object:
+// [openTime, open, high, low, close, volume, closeTime, quoteVolume, trades,
+//  takerBuyBaseVolume, takerBuyQuoteVolume, ignore]
+#[derive(Debug, Deserialize)]
+pub struct Kline {
+    pub open_time: i64,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    pub close_time: i64,
+    pub quote_volume: String,
+    pub trades: u64,
+    pub taker_buy_base_volume: String,
+    pub taker_buy_quote_volume: String,
+    pub _ignore: String,
+}
@@ -2,6 +2,7 @@ use domain::types::{order_side::OrderSide, symbol::Symbol};
 
 use crate::{
     client::BinanceClient,
+    constants::{MAX_CALLBACK_RATE, MIN_CALLBACK_RATE},
     errors::BinanceError,
     filters::quantize::{
         align_up, format_with_step, validate_notional, validate_price, validate_qty,
@@ -28,7 +29,34 @@ impl BinanceClient {
         self.place_market_order(symbol, side, qty).await
     }
 
-    pub async fn close_percentage(&self, symbol: Symbol, percent: f64) -> Result<(), BinanceError> {
+    /// Same sizing as `place_minimum_market_order`, but as a GTC limit order at `price`.
+    pub async fn place_minimum_limit_order(
+        &self,
+        symbol: Symbol,
+        side: &OrderSide,
+        price: f64,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        let filters = self.filters(symbol)?;
+
+        let aligned_price = validate_price(filters, price)?;
+
+        let min_notional_qty = filters.min_notional / aligned_price;
+
+        let raw = filters.min_qty.max(min_notional_qty);
+
+        let qty = align_up(raw, filters.step_size);
+
+        self.place_limit_order(symbol, side, qty, aligned_price)
+            .await
+    }
+
+    /// Closes `percent` of the position at market. Returns the closing order, or `None` when
+    /// there was no position.
+    pub async fn close_percentage(
+        &self,
+        symbol: Symbol,
+        percent: f64,
+    ) -> Result<Option<FuturesOrderResponse>, BinanceError> {
         if percent <= 0.0 || percent > 100.0 {
             return Err(BinanceError::InvalidInput(
                 "percent must be between 0 and 100".into(),
@@ -45,7 +73,7 @@ impl BinanceClient {
         let amt: f64 = pos.position_amt.parse().unwrap_or(0.0);
 
         if amt == 0.0 {
-            return Ok(());
+            return Ok(None);
         }
 
         let raw = amt.abs() * percent / 100.0;
@@ -60,11 +88,15 @@ impl BinanceClient {
             OrderSide::Buy
         };
 
-        self.place_market_order(symbol, &side, raw).await?;
-
-        Ok(())
+        self.place_market_order(symbol, &side, raw).await.map(Some)
     }
-    pub async fn close_full_position(&self, symbol: Symbol) -> Result<(), BinanceError> {
+
+    /// Closes the whole position at market. Returns the closing order, or `None` when there was
+    /// no position.
+    pub async fn close_full_position(
+        &self,
+        symbol: Symbol,
+    ) -> Result<Option<FuturesOrderResponse>, BinanceError> {
         let positions = self.get_position_risk(Some(symbol)).await?;
 
         let pos = positions
@@ -78,7 +110,7 @@ impl BinanceClient {
             .map_err(|_| BinanceError::InvalidInput("Invalid position amount".into()))?;
 
         if amt == 0.0 {
-            return Ok(());
+            return Ok(None);
         }
 
         let side = if amt > 0.0 {
@@ -87,9 +119,9 @@ impl BinanceClient {
             OrderSide::Buy
         };
 
-        self.place_market_order(symbol, &side, amt.abs()).await?;
-
-        Ok(())
+        self.place_market_order(symbol, &side, amt.abs())
+            .await
+            .map(Some)
     }
 
     pub async fn place_market_order(
@@ -127,4 +159,70 @@ impl BinanceClient {
         self.place_limit_order_raw(symbol, side, quantity_str, price_str)
             .await
     }
+
+    /// Places a reduce-only stop loss for `quantity`. `side` is the closing side.
+    pub async fn place_reduce_only_stop_order(
+        &self,
+        symbol: Symbol,
+        side: &OrderSide,
+        quantity: f64,
+        stop_price: f64,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        let filters = self.filters(symbol)?;
+        let aligned_qty = validate_qty(filters, quantity)?;
+        let aligned_price = validate_price(filters, stop_price)?;
+
+        let quantity_str = format_with_step(aligned_qty, filters.step_size);
+        let price_str = format_with_step(aligned_price, filters.tick_size);
+
+        self.place_reduce_only_stop_market_order_raw(symbol, side, quantity_str, price_str)
+            .await
+    }
+
+    /// Places a reduce-only take profit for `quantity`. `side` is the closing side.
+    pub async fn place_take_profit_order(
+        &self,
+        symbol: Symbol,
+        side: &OrderSide,
+        quantity: f64,
+        stop_price: f64,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        let filters = self.filters(symbol)?;
+        let aligned_qty = validate_qty(filters, quantity)?;
+        let aligned_price = validate_price(filters, stop_price)?;
+
+        let quantity_str = format_with_step(aligned_qty, filters.step_size);
+        let price_str = format_with_step(aligned_price, filters.tick_size);
+
+        self.place_take_profit_market_order_raw(symbol, side, quantity_str, price_str)
+            .await
+    }
+
+    /// Places a reduce-only `TRAILING_STOP_MARKET` order trailing the mark price.
+    /// `callback_rate` is in percent, e.g. 1.0 trails 1% behind the best price.
+    pub async fn place_trailing_stop_order(
+        &self,
+        symbol: Symbol,
+        side: &OrderSide,
+        quantity: f64,
+        callback_rate: f64,
+    ) -> Result<FuturesOrderResponse, BinanceError> {
+        if !(MIN_CALLBACK_RATE..=MAX_CALLBACK_RATE).contains(&callback_rate) {
+            return Err(BinanceError::InvalidInput(format!(
+                "Invalid callback rate {}. Allowed range: {}-{}",
+                callback_rate, MIN_CALLBACK_RATE, MAX_CALLBACK_RATE
+            )));
+        }
+
+        let filters = self.filters(symbol)?;
+        let aligned_qty = validate_qty(filters, quantity)?;
+
+        let quantity_str = format_with_step(aligned_qty, filters.step_size);
+
+        // Binance accepts callbackRate with at most one decimal.
+        let callback_rate_str = format!("{:.1}", callback_rate);
+
+        self.place_trailing_stop_order_raw(symbol, side, quantity_str, callback_rate_str)
+            .await
+    }
 }
@@ -4,6 +4,7 @@ mod tests {
     use domain::types::symbol::Symbol;
     use serial_test::serial;
 
+    use crate::response_types::Kline;
     use crate::tests::test_support::test_support::test_client;
     use crate::utils::build_query;
     use crate::{constants, errors::BinanceError};
@@ -197,7 +198,7 @@ mod tests {
                 .await
                 .expect("failed to change position mode");
 
-            assert_eq!(result.dual_side_position, false);
+            assert!(!result.dual_side_position);
         }
 
         // Verify final state
@@ -206,7 +207,7 @@ mod tests {
             .await
             .expect("failed to fetch position mode");
 
-        assert_eq!(current.dual_side_position, false);
+        assert!(!current.dual_side_position);
     }
     #[tokio::test]
     #[ignore]
@@ -260,4 +261,42 @@ mod tests {
 
         assert!(!info.symbols.is_empty());
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_klines() {
+        let client = test_client(constants::TESTNET_FUTURES);
+
+        let klines = client
+            .get_klines(Symbol::BTC, "15m", 20)
+            .await
+            .expect("failed to fetch klines");
+
+        assert_eq!(klines.len(), 20);
+        assert!(klines[0].open_time < klines[19].open_time);
+    }
+
+    #[test]
+    fn test_kline_deserializes_from_array() {
+        let raw = r#"[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]"#;
+
+        let kline: Kline = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(kline.open_time, 1499040000000);
+        assert_eq!(kline.high, "0.80000000");
+        assert_eq!(kline.trades, 308);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial(binance)]
+    async fn test_trailing_stop_rejects_invalid_callback_rate() {
+        let client = test_client(constants::TESTNET_FUTURES);
+
+        let result = client
+            .place_trailing_stop_order(Symbol::BTC, &OrderSide::Sell, 0.001, 15.0)
+            .await;
+
+        assert!(matches!(result, Err(BinanceError::InvalidInput(_))));
+    }
 }
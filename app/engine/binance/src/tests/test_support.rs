@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 pub mod test_support {
     use std::collections::HashMap;
     use std::sync::OnceLock;
@@ -0,0 +1,194 @@
+use uuid::Uuid;
+
+use crate::types::{order_side::OrderSide, symbol::Symbol, trade::TradeRejectionReason};
+
+/// How an approved trade was filled, published by the executor for every entry fill.
+/// Timestamps are Unix milliseconds. Slippage is signed: positive means a worse fill.
+#[derive(Debug, Clone)]
+pub struct ExecutionReport {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub source: Option<i64>,
+    pub signal_time_ms: i64,
+    pub approved_at_ms: i64,
+    pub sent_at_ms: i64,
+    /// Transaction time reported by the exchange.
+    pub filled_at_ms: i64,
+    /// Entry price written in the signal.
+    pub signal_entry: f64,
+    /// Last traded price when the signal arrived, if the symbol is streamed.
+    pub signal_market_price: Option<f64>,
+    pub stop_loss: f64,
+    pub fill_price: f64,
+}
+
+impl ExecutionReport {
+    /// Signal posted to risk approval.
+    pub fn approval_latency_ms(&self) -> i64 {
+        self.approved_at_ms - self.signal_time_ms
+    }
+
+    /// Risk approval to the entry order leaving Pulsgram.
+    pub fn send_latency_ms(&self) -> i64 {
+        self.sent_at_ms - self.approved_at_ms
+    }
+
+    /// Order sent to filled on the exchange. Includes resting time for limit entries.
+    pub fn fill_latency_ms(&self) -> i64 {
+        self.filled_at_ms - self.sent_at_ms
+    }
+
+    pub fn total_latency_ms(&self) -> i64 {
+        self.filled_at_ms - self.signal_time_ms
+    }
+
+    /// Fill against the signal's entry, in basis points.
+    pub fn slippage_bps(&self) -> f64 {
+        self.adverse_move(self.signal_entry) / self.signal_entry * 10_000.0
+    }
+
+    /// Fill against the signal's entry, in units of the signal's risk (entry to stop).
+    pub fn slippage_r(&self) -> f64 {
+        let risk = (self.signal_entry - self.stop_loss).abs();
+
+        if risk == 0.0 {
+            return 0.0;
+        }
+
+        self.adverse_move(self.signal_entry) / risk
+    }
+
+    /// Fill against the market when the signal arrived, in basis points.
+    /// Unlike `slippage_bps`, this leaves out how far the signal itself was from the market.
+    pub fn market_slippage_bps(&self) -> Option<f64> {
+        self.signal_market_price
+            .map(|price| self.adverse_move(price) / price * 10_000.0)
+    }
+
+    fn adverse_move(&self, reference: f64) -> f64 {
+        if self.side.is_long() {
+            self.fill_price - reference
+        } else {
+            reference - self.fill_price
+        }
+    }
+}
+
+/// Running totals of execution quality for one source or symbol.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExecutionStats {
+    pub fills: u32,
+    total_latency_ms: i64,
+    slippage_bps: f64,
+    slippage_r: f64,
+}
+
+impl ExecutionStats {
+    pub fn add(&mut self, report: &ExecutionReport) {
+        self.fills += 1;
+        self.total_latency_ms += report.total_latency_ms();
+        self.slippage_bps += report.slippage_bps();
+        self.slippage_r += report.slippage_r();
+    }
+
+    pub fn avg_latency_ms(&self) -> i64 {
+        self.total_latency_ms / i64::from(self.fills.max(1))
+    }
+
+    pub fn avg_slippage_bps(&self) -> f64 {
+        self.slippage_bps / f64::from(self.fills.max(1))
+    }
+
+    pub fn avg_slippage_r(&self) -> f64 {
+        self.slippage_r / f64::from(self.fills.max(1))
+    }
+}
+
+/// Execution quality so far of a fill's source and symbol, published by the executor after
+/// every `ExecutionReport`.
+#[derive(Debug, Clone)]
+pub struct ExecutionQuality {
+    pub source: Option<i64>,
+    pub by_source: ExecutionStats,
+    pub symbol: Symbol,
+    pub by_symbol: ExecutionStats,
+}
+
+/// Result of an approved trade on one of the accounts it was fanned out to.
+#[derive(Debug, Clone)]
+pub struct AccountExecution {
+    pub account: String,
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub outcome: AccountOutcome,
+}
+
+#[derive(Debug, Clone)]
+pub enum AccountOutcome {
+    Filled {
+        price: f64,
+        quantity: f64,
+    },
+    /// Entry order resting on the book.
+    Resting {
+        order_id: i64,
+    },
+    /// Entry order ended without a fill.
+    Cancelled {
+        reason: String,
+    },
+    Rejected(TradeRejectionReason),
+    Failed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(side: OrderSide, fill_price: f64) -> ExecutionReport {
+        ExecutionReport {
+            intent_id: Uuid::new_v4(),
+            symbol: Symbol::BTC,
+            side,
+            source: None,
+            signal_time_ms: 1_000,
+            approved_at_ms: 1_050,
+            sent_at_ms: 1_200,
+            filled_at_ms: 1_450,
+            signal_entry: 100.0,
+            signal_market_price: Some(100.5),
+            stop_loss: if side.is_long() { 95.0 } else { 105.0 },
+            fill_price,
+        }
+    }
+
+    #[test]
+    fn test_latency_per_stage() {
+        let report = report(OrderSide::Buy, 100.0);
+
+        assert_eq!(report.approval_latency_ms(), 50);
+        assert_eq!(report.send_latency_ms(), 150);
+        assert_eq!(report.fill_latency_ms(), 250);
+        assert_eq!(report.total_latency_ms(), 450);
+    }
+
+    #[test]
+    fn test_worse_fill_is_positive_slippage_on_both_sides() {
+        let long = report(OrderSide::Buy, 101.0);
+        let short = report(OrderSide::Sell, 99.0);
+
+        for report in [long, short] {
+            assert!((report.slippage_bps() - 100.0).abs() < 1e-9);
+            assert!((report.slippage_r() - 0.2).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_market_slippage_uses_price_at_signal() {
+        let report = report(OrderSide::Buy, 100.0);
+
+        // Filled below the market at signal time: a price improvement.
+        assert!(report.market_slippage_bps().unwrap() < 0.0);
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// A tracked KOL following an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KolFollow {
+    pub follower: String,
+    pub followee: String,
+    pub followed_at_ms: i64,
+    /// The followed account's profile as shown in the relay message.
+    pub profile_info: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KolFollower {
+    pub follower: String,
+    pub followed_at_ms: i64,
+}
+
+/// Which tracked KOLs follow an account, earliest first. Handles are lower-cased.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowTarget {
+    pub followee: String,
+    pub first_followed_at_ms: Option<i64>,
+    pub followers: Vec<KolFollower>,
+}
@@ -0,0 +1,476 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::types::{
+    order_side::OrderSide,
+    symbol::{QUOTE_ASSET, Symbol},
+    trade::ExitReason,
+};
+
+/// Quantities and fractions of a position are accumulated as f64, so "nothing left" is checked
+/// with this tolerance.
+pub const EPSILON: f64 = 1e-9;
+
+/// Whether an accumulated quantity or fraction of a position is down to nothing.
+pub fn is_nothing_left(amount: f64) -> bool {
+    amount <= EPSILON
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeState {
+    Intent,
+    Approved,
+    EntryPending,
+    Open,
+    PartiallyClosed,
+    Closed,
+    Cancelled,
+    Failed,
+}
+
+impl TradeState {
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TradeState::Closed | TradeState::Cancelled | TradeState::Failed
+        )
+    }
+
+    /// Entry was sent to the exchange and the trade is not finished yet.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            TradeState::EntryPending | TradeState::Open | TradeState::PartiallyClosed
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LifecycleEvent {
+    Approve,
+    EntrySubmitted {
+        order_id: i64,
+    },
+    EntryFilled {
+        price: f64,
+        quantity: f64,
+    },
+    /// Reduces the position without closing it, e.g. a take-profit level.
+    PartialExit {
+        price: f64,
+        quantity: f64,
+    },
+    /// Closes whatever is left of the position.
+    Exit {
+        price: f64,
+        reason: ExitReason,
+    },
+    /// Commission charged by the exchange for one of the trade's fills, in `asset`.
+    Commission {
+        amount: f64,
+        asset: String,
+    },
+    Cancel {
+        reason: String,
+    },
+    Fail {
+        reason: String,
+    },
+}
+
+/// Lifecycle event as published on the bus, addressed to a single trade.
+#[derive(Debug, Clone)]
+pub struct TradeTransition {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub event: LifecycleEvent,
+}
+
+/// One applied transition. `realized_r` and `fees` are running totals for the trade, `fees` in
+/// the quote asset.
+#[derive(Debug, Clone)]
+pub struct LifecycleStep {
+    pub from: TradeState,
+    pub to: TradeState,
+    pub event: LifecycleEvent,
+    pub realized_r: f64,
+    pub fees: f64,
+    pub timestamp_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionError {
+    Invalid {
+        state: TradeState,
+        event: LifecycleEvent,
+    },
+    InvalidQuantity(f64),
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransitionError::Invalid { state, event } => {
+                write!(f, "{:?} is not allowed in state {:?}", event, state)
+            }
+            TransitionError::InvalidQuantity(quantity) => {
+                write!(f, "Invalid quantity: {}", quantity)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+/// A trade from the moment it is parsed until it is closed, cancelled or failed.
+/// Every change goes through `apply`, which rejects transitions that cannot happen.
+#[derive(Debug, Clone)]
+pub struct Trade {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub stop_loss: f64,
+    pub entry_order_id: Option<i64>,
+    pub entry_price: Option<f64>,
+    pub quantity: f64,
+    pub remaining_quantity: f64,
+    pub realized_pnl: f64,
+    /// Commissions charged in the quote asset, netted in `realized_r`.
+    pub fees: f64,
+    /// Commissions charged in other assets (e.g. BNB), by asset. They are not converted, so
+    /// `realized_r` leaves them out.
+    pub other_fees: HashMap<String, f64>,
+    pub history: Vec<LifecycleStep>,
+    state: TradeState,
+}
+
+impl Trade {
+    pub fn new(intent_id: Uuid, symbol: Symbol, side: OrderSide, stop_loss: f64) -> Self {
+        Self {
+            intent_id,
+            symbol,
+            side,
+            stop_loss,
+            entry_order_id: None,
+            entry_price: None,
+            quantity: 0.0,
+            remaining_quantity: 0.0,
+            realized_pnl: 0.0,
+            fees: 0.0,
+            other_fees: HashMap::new(),
+            history: Vec::new(),
+            state: TradeState::Intent,
+        }
+    }
+
+    pub fn state(&self) -> TradeState {
+        self.state
+    }
+
+    /// PnL net of fees, in units of the initial risk (entry to stop loss).
+    pub fn realized_r(&self) -> f64 {
+        let Some(entry) = self.entry_price else {
+            return 0.0;
+        };
+
+        let risk = (entry - self.stop_loss).abs() * self.quantity;
+
+        if is_nothing_left(risk) {
+            return 0.0;
+        }
+
+        (self.realized_pnl - self.fees) / risk
+    }
+
+    pub fn last_timestamp_ms(&self) -> Option<i64> {
+        self.history.last().map(|step| step.timestamp_ms)
+    }
+
+    pub fn apply(
+        &mut self,
+        event: LifecycleEvent,
+        timestamp_ms: i64,
+    ) -> Result<&LifecycleStep, TransitionError> {
+        use LifecycleEvent as E;
+        use TradeState as S;
+
+        let from = self.state;
+
+        let to = match (&event, from) {
+            (E::Approve, S::Intent) => S::Approved,
+
+            (E::EntrySubmitted { order_id }, S::Approved) => {
+                self.entry_order_id = Some(*order_id);
+                S::EntryPending
+            }
+
+            // Market entries can be reported filled without a separate submission step.
+            (E::EntryFilled { price, quantity }, S::Approved | S::EntryPending) => {
+                if *quantity <= 0.0 {
+                    return Err(TransitionError::InvalidQuantity(*quantity));
+                }
+
+                self.entry_price = Some(*price);
+                self.quantity = *quantity;
+                self.remaining_quantity = *quantity;
+                S::Open
+            }
+
+            (E::PartialExit { price, quantity }, S::Open | S::PartiallyClosed) => {
+                if *quantity <= 0.0 || *quantity > self.remaining_quantity + EPSILON {
+                    return Err(TransitionError::InvalidQuantity(*quantity));
+                }
+
+                self.close(*price, quantity.min(self.remaining_quantity));
+                S::PartiallyClosed
+            }
+
+            (E::Exit { price, .. }, S::Open | S::PartiallyClosed) => {
+                self.close(*price, self.remaining_quantity);
+                S::Closed
+            }
+
+            // Commissions are reported separately and may arrive after the closing fill.
+            (
+                E::Commission { amount, asset },
+                S::EntryPending | S::Open | S::PartiallyClosed | S::Closed,
+            ) => {
+                if asset == QUOTE_ASSET {
+                    self.fees += amount;
+                } else {
+                    *self.other_fees.entry(asset.clone()).or_default() += amount;
+                }
+                from
+            }
+
+            (E::Cancel { .. }, S::Intent | S::Approved | S::EntryPending) => S::Cancelled,
+
+            (E::Fail { .. }, state) if !state.is_terminal() => S::Failed,
+
+            _ => return Err(TransitionError::Invalid { state: from, event }),
+        };
+
+        self.state = to;
+
+        let realized_r = self.realized_r();
+
+        self.history.push(LifecycleStep {
+            from,
+            to,
+            event,
+            realized_r,
+            fees: self.fees,
+            timestamp_ms,
+        });
+
+        Ok(self.history.last().expect("step was just pushed"))
+    }
+
+    fn close(&mut self, price: f64, quantity: f64) {
+        let Some(entry) = self.entry_price else {
+            return;
+        };
+
+        let direction = if self.side.is_long() { 1.0 } else { -1.0 };
+
+        self.realized_pnl += (price - entry) * quantity * direction;
+        self.remaining_quantity = (self.remaining_quantity - quantity).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_trade() -> Trade {
+        let mut trade = Trade::new(Uuid::new_v4(), Symbol::BTC, OrderSide::Buy, 90.0);
+
+        trade.apply(LifecycleEvent::Approve, 1).unwrap();
+        trade
+            .apply(LifecycleEvent::EntrySubmitted { order_id: 7 }, 2)
+            .unwrap();
+        trade
+            .apply(
+                LifecycleEvent::EntryFilled {
+                    price: 100.0,
+                    quantity: 2.0,
+                },
+                3,
+            )
+            .unwrap();
+
+        trade
+    }
+
+    #[test]
+    fn test_full_lifecycle_records_r_fees_and_timestamps() {
+        let mut trade = open_trade();
+
+        trade
+            .apply(
+                LifecycleEvent::PartialExit {
+                    price: 110.0,
+                    quantity: 1.0,
+                },
+                4,
+            )
+            .unwrap();
+        assert_eq!(trade.state(), TradeState::PartiallyClosed);
+
+        trade
+            .apply(
+                LifecycleEvent::Commission {
+                    amount: 2.0,
+                    asset: "USDT".into(),
+                },
+                5,
+            )
+            .unwrap();
+        trade
+            .apply(
+                LifecycleEvent::Commission {
+                    amount: 0.01,
+                    asset: "BNB".into(),
+                },
+                5,
+            )
+            .unwrap();
+
+        let step = trade
+            .apply(
+                LifecycleEvent::Exit {
+                    price: 120.0,
+                    reason: ExitReason::FinalTarget,
+                },
+                6,
+            )
+            .unwrap();
+
+        // PnL 10 + 20, minus 2 in fees, over a risk of 10 x 2. BNB fees are kept apart.
+        assert_eq!(step.to, TradeState::Closed);
+        assert_eq!(step.fees, 2.0);
+        assert!((step.realized_r - 1.4).abs() < 1e-9);
+        assert_eq!(step.timestamp_ms, 6);
+        assert_eq!(trade.history.len(), 7);
+        assert_eq!(trade.other_fees["BNB"], 0.01);
+        assert_eq!(trade.entry_order_id, Some(7));
+    }
+
+    #[test]
+    fn test_exit_before_entry_is_rejected() {
+        let mut trade = Trade::new(Uuid::new_v4(), Symbol::ETH, OrderSide::Sell, 110.0);
+        trade.apply(LifecycleEvent::Approve, 1).unwrap();
+
+        let result = trade.apply(
+            LifecycleEvent::Exit {
+                price: 100.0,
+                reason: ExitReason::StopLoss,
+            },
+            2,
+        );
+
+        assert!(matches!(
+            result,
+            Err(TransitionError::Invalid {
+                state: TradeState::Approved,
+                ..
+            })
+        ));
+        assert_eq!(trade.state(), TradeState::Approved);
+        assert_eq!(trade.history.len(), 1);
+    }
+
+    #[test]
+    fn test_terminal_states_accept_nothing_but_late_commissions() {
+        let mut trade = open_trade();
+        trade
+            .apply(
+                LifecycleEvent::Exit {
+                    price: 95.0,
+                    reason: ExitReason::StopLoss,
+                },
+                4,
+            )
+            .unwrap();
+
+        assert!(
+            trade
+                .apply(
+                    LifecycleEvent::Commission {
+                        amount: 0.1,
+                        asset: "USDT".into()
+                    },
+                    5
+                )
+                .is_ok()
+        );
+        assert!(
+            trade
+                .apply(
+                    LifecycleEvent::Fail {
+                        reason: "late".into()
+                    },
+                    6
+                )
+                .is_err()
+        );
+        assert!(
+            trade
+                .apply(
+                    LifecycleEvent::Cancel {
+                        reason: "late".into()
+                    },
+                    6
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_partial_exit_cannot_exceed_remaining_quantity() {
+        let mut trade = open_trade();
+
+        let result = trade.apply(
+            LifecycleEvent::PartialExit {
+                price: 110.0,
+                quantity: 3.0,
+            },
+            4,
+        );
+
+        assert_eq!(result.unwrap_err(), TransitionError::InvalidQuantity(3.0));
+        assert_eq!(trade.state(), TradeState::Open);
+    }
+
+    #[test]
+    fn test_cancel_only_before_entry_fill() {
+        let mut trade = open_trade();
+
+        assert!(
+            trade
+                .apply(
+                    LifecycleEvent::Cancel {
+                        reason: "expired".into()
+                    },
+                    4
+                )
+                .is_err()
+        );
+
+        let mut pending = Trade::new(Uuid::new_v4(), Symbol::SOL, OrderSide::Buy, 9.0);
+        pending.apply(LifecycleEvent::Approve, 1).unwrap();
+
+        let step = pending
+            .apply(
+                LifecycleEvent::Cancel {
+                    reason: "expired".into(),
+                },
+                2,
+            )
+            .unwrap();
+
+        assert_eq!(step.to, TradeState::Cancelled);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::types::{order_side::OrderSide, symbol::Symbol};
+
+/// Last traded price for a symbol, as received from the market data stream.
+#[derive(Debug, Clone, Copy)]
+pub struct PriceTick {
+    pub symbol: Symbol,
+    pub price: f64,
+}
+
+/// Order execution report from the Binance user data stream (ORDER_TRADE_UPDATE).
+#[derive(Debug, Clone)]
+pub struct OrderUpdate {
+    pub symbol: Symbol,
+    pub order_id: i64,
+    pub side: OrderSide,
+    /// NEW, TRADE, CANCELED, EXPIRED, ...
+    pub execution_type: String,
+    /// NEW, PARTIALLY_FILLED, FILLED, CANCELED, ...
+    pub status: String,
+    pub last_filled_quantity: f64,
+    pub last_filled_price: f64,
+    pub commission: f64,
+    pub commission_asset: String,
+    pub realized_profit: f64,
+    pub reduce_only: bool,
+    pub event_time_ms: i64,
+}
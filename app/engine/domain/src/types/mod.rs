@@ -1,4 +1,12 @@
+pub mod execution;
+pub mod kol_follow;
+pub mod lifecycle;
+pub mod market;
 pub mod order_side;
+pub mod parse_failure;
+pub mod provider;
+pub mod signal_update;
 pub mod symbol;
+pub mod timeframe;
 pub mod trade;
 pub mod trade_intent;
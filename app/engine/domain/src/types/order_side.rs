@@ -2,12 +2,26 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum OrderSide {
     Buy,  // LONG
     Sell, // SHORT
 }
+impl OrderSide {
+    pub fn is_long(&self) -> bool {
+        matches!(self, OrderSide::Buy)
+    }
+
+    /// Side of the order that reduces a position opened with `self`.
+    pub fn opposite(&self) -> OrderSide {
+        match self {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        }
+    }
+}
+
 impl From<bool> for OrderSide {
     fn from(is_long: bool) -> Self {
         if is_long {
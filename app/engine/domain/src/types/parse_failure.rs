@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A message from a signal source that looked like a signal but was not turned into one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalParseFailure {
+    pub chat_id: i64,
+    pub message_id: i32,
+    pub text: String,
+    /// The missing field, or the validation error.
+    pub reason: String,
+    pub posted_at_ms: i64,
+}
+
+/// A failure stored for review by the persistence service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedParseFailure {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub failure: SignalParseFailure,
+    /// Set once the message was replayed successfully.
+    pub resolved: bool,
+}
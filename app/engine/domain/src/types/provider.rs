@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// A signal channel or KOL, with its settings and rolling stats from the persistence service.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Provider {
+    pub name: String,
+    pub chat_id: i64,
+    /// Name of the signal parser its posts are read with.
+    pub parser: String,
+    /// Disabled providers are not read at all.
+    pub enabled: bool,
+    /// Relative trust, for comparing providers that post the same setup.
+    pub weight: f64,
+    /// Scales the risk taken on each of its trades.
+    pub risk_multiplier: f64,
+    #[serde(default)]
+    pub stats: ProviderStats,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProviderStats {
+    pub signals_per_day: f64,
+    /// Share of signal-looking messages that could not be parsed.
+    pub parse_failure_rate: f64,
+    pub closed_trades: u32,
+    pub win_rate: f64,
+    /// Average realised PnL per closed trade, in USDT.
+    pub expectancy: f64,
+}
+
+/// Something a provider did, counted in its stats.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProviderActivity {
+    Signal,
+    /// A message that looks like a signal but could not be turned into one.
+    ParseFailure,
+    /// A trade from one of its signals closed with this realised PnL, in USDT.
+    Closed {
+        pnl: f64,
+    },
+}
@@ -0,0 +1,39 @@
+use uuid::Uuid;
+
+use crate::types::symbol::Symbol;
+
+/// A provider's follow-up on a signal it posted, linked to the signal's intent through the
+/// message it edited or replied to.
+#[derive(Debug, Clone)]
+pub struct SignalUpdate {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub kind: SignalUpdateKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignalUpdateKind {
+    /// The signal message was edited to new levels.
+    AmendSignal {
+        entry: f64,
+        targets: Vec<f64>,
+        stop_loss: f64,
+    },
+    MoveStop {
+        to: StopLevel,
+    },
+    /// Close `percent` of what is left of the position.
+    ClosePartial {
+        percent: f64,
+    },
+    CloseAll,
+    /// The provider withdrew the signal: a resting entry is cancelled, an open trade closed.
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopLevel {
+    /// Break-even.
+    Entry,
+    Price(f64),
+}
@@ -2,6 +2,9 @@ use serde::Deserialize;
 use std::convert::TryFrom;
 use std::{fmt, str::FromStr};
 
+/// Every symbol is quoted in USDT.
+pub const QUOTE_ASSET: &str = "USDT";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum Symbol {
     BTC,
@@ -0,0 +1,95 @@
+use core::fmt;
+use std::{str::FromStr, time::Duration};
+
+// Exchanges have no candles longer than a month; the count is written by signal providers.
+const MAX_TIMEFRAME_SECS: u64 = 31 * 24 * 60 * 60;
+
+/// Candle timeframe as written in signals: a count followed by m, h, d or w (15m, 4h, 1d).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeframe(Duration);
+
+impl Timeframe {
+    pub fn duration(&self) -> Duration {
+        self.0
+    }
+
+    /// Duration of `count` candles of this timeframe.
+    pub fn candles(&self, count: u32) -> Duration {
+        self.0 * count
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TimeframeError(pub String);
+
+impl fmt::Display for TimeframeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid timeframe: {}", self.0)
+    }
+}
+
+impl FromStr for Timeframe {
+    type Err = TimeframeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TimeframeError(s.to_string());
+
+        let unit_start = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let (count, unit) = s.split_at(unit_start);
+
+        let count: u64 = count.parse().map_err(|_| invalid())?;
+
+        let unit_secs = match unit {
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            "w" => 7 * 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+
+        let secs = count
+            .checked_mul(unit_secs)
+            .filter(|secs| (1..=MAX_TIMEFRAME_SECS).contains(secs))
+            .ok_or_else(invalid)?;
+
+        Ok(Timeframe(Duration::from_secs(secs)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_signal_timeframes() {
+        assert_eq!(
+            "15m".parse::<Timeframe>().unwrap().duration(),
+            Duration::from_secs(900)
+        );
+        assert_eq!(
+            "4h".parse::<Timeframe>().unwrap().candles(3),
+            Duration::from_secs(12 * 3600)
+        );
+        assert_eq!(
+            "1w".parse::<Timeframe>().unwrap().duration(),
+            Duration::from_secs(7 * 86400)
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_timeframes() {
+        for raw in [
+            "",
+            "h",
+            "15",
+            "0m",
+            "1y",
+            "1h30m",
+            "5w",
+            "9999999999999w",
+            "99999999999999999999m",
+        ] {
+            assert!(raw.parse::<Timeframe>().is_err(), "{raw} should be invalid");
+        }
+    }
+}
@@ -18,10 +18,16 @@ pub struct TradeApproved {
     pub targets: Vec<f64>,
     pub timeframe: String,
     pub stop_loss: f64,
+    pub source: Option<i64>,
+    pub signal_time_ms: i64,
+    pub approved_at_ms: i64,
+    /// Scales the position size, set by the risk manager from the provider's record.
+    pub risk_multiplier: f64,
 }
 
-impl From<TradeIntent> for TradeApproved {
-    fn from(intent: TradeIntent) -> Self {
+impl TradeApproved {
+    /// Approves the intent at `approved_at_ms`, at full risk.
+    pub fn new(intent: TradeIntent, approved_at_ms: i64) -> Self {
         Self {
             intent_id: intent.intent_id,
             symbol: intent.symbol,
@@ -30,14 +36,103 @@ impl From<TradeIntent> for TradeApproved {
             targets: intent.targets,
             timeframe: intent.timeframe,
             stop_loss: intent.stop_loss,
+            source: intent.source,
+            signal_time_ms: intent.signal_time_ms,
+            approved_at_ms,
+            risk_multiplier: 1.0,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TradeRejectionReason {
     RiskRejected,
     InvalidSignal,
     InsufficientBalance,
+    /// Price already reached TP1 before the entry was placed.
+    MissedFirstTarget,
+    /// Price already reached the stop loss before the entry was placed.
+    PastStopLoss,
+    /// Same symbol, side, entry and stop loss as a recent signal.
+    Duplicate,
+    /// Conflicts with a trade already open on the symbol.
+    Conflict,
+    /// New entries are halted by the kill switch.
+    KillSwitch,
+    /// The signal's provider is disabled, by hand or by its stats.
+    ProviderDisabled(String),
     Other(String),
 }
+
+/// Emitted by the executor once an entry order is filled.
+/// Carries everything the trade manager needs to manage the position.
+#[derive(Debug, Clone)]
+pub struct TradeOpened {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub entry_price: f64,
+    pub quantity: f64,
+    pub targets: Vec<f64>,
+    pub timeframe: String,
+    pub stop_loss: f64,
+    pub source: Option<i64>,
+}
+
+/// Lifecycle step of an open trade, published by the trade manager.
+#[derive(Debug, Clone)]
+pub struct TradeUpdate {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub kind: TradeUpdateKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum TradeUpdateKind {
+    /// `level` is 1-based (TP1, TP2, ...). `closed_fraction` is relative to the original size.
+    TargetHit {
+        level: usize,
+        price: f64,
+        closed_fraction: f64,
+        pnl: f64,
+    },
+    StopMoved {
+        from: f64,
+        to: f64,
+    },
+    /// Part of the position closed outside of the target ladder. `closed_fraction` is
+    /// relative to the original size.
+    PartialClose {
+        price: f64,
+        closed_fraction: f64,
+        pnl: f64,
+    },
+    Closed {
+        price: f64,
+        pnl: f64,
+        reason: ExitReason,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    FinalTarget,
+    StopLoss,
+    /// Closed to make room for a conflicting signal.
+    Conflict,
+    /// Flattened by the kill switch.
+    KillSwitch,
+    /// Went stale: neither the next target nor the stop was reached in time.
+    TimeExit,
+    /// Closed or cancelled by the signal's provider.
+    Provider,
+}
+
+/// Asks the trade manager to close a managed trade at market. An entry still resting on the book
+/// is cancelled by the trade executor instead.
+#[derive(Debug, Clone)]
+pub struct CloseTrade {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub reason: ExitReason,
+}
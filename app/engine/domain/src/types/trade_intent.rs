@@ -1,7 +1,9 @@
 use core::fmt;
 
+use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::clock::now_ms;
 use crate::types::{order_side::OrderSide, symbol::Symbol};
 
 #[derive(Debug, Clone)]
@@ -13,6 +15,10 @@ pub struct TradeIntent {
     pub targets: Vec<f64>,
     pub timeframe: String,
     pub stop_loss: f64,
+    /// Telegram peer the signal was posted in, used for per-source settings.
+    pub source: Option<i64>,
+    /// When the signal was posted, in Unix milliseconds.
+    pub signal_time_ms: i64,
 }
 
 #[derive(Debug)]
@@ -22,6 +28,37 @@ pub enum TradeIntentError {
     MissingTargets,
     MissingTimeframe,
     MissingStopLoss,
+    /// Zero, negative or not a number.
+    InvalidPrice(f64),
+    StopOnWrongSide {
+        side: OrderSide,
+        entry: f64,
+        stop_loss: f64,
+    },
+    /// `level` counts from 1, as in TP1.
+    TargetOnWrongSide {
+        side: OrderSide,
+        level: usize,
+        target: f64,
+        entry: f64,
+    },
+    /// Target `level` is not further from the entry than the one before it.
+    TargetsOutOfOrder {
+        level: usize,
+    },
+    EntryFarFromMarket {
+        entry: f64,
+        mark_price: f64,
+        distance_percent: f64,
+    },
+    RewardToRiskTooLow {
+        ratio: f64,
+        min: f64,
+    },
+    RewardToRiskTooHigh {
+        ratio: f64,
+        max: f64,
+    },
 }
 
 impl fmt::Display for TradeIntentError {
@@ -32,6 +69,69 @@ impl fmt::Display for TradeIntentError {
             TradeIntentError::MissingTargets => write!(f, "Targets are missing"),
             TradeIntentError::MissingTimeframe => write!(f, "Timeframe is missing"),
             TradeIntentError::MissingStopLoss => write!(f, "Stop loss is missing"),
+            TradeIntentError::InvalidPrice(price) => write!(f, "Invalid price {}", price),
+            TradeIntentError::StopOnWrongSide {
+                side,
+                entry,
+                stop_loss,
+            } => write!(
+                f,
+                "Stop loss {} is on the wrong side of entry {} for a {}",
+                stop_loss, entry, side
+            ),
+            TradeIntentError::TargetOnWrongSide {
+                side,
+                level,
+                target,
+                entry,
+            } => write!(
+                f,
+                "TP{} {} is on the wrong side of entry {} for a {}",
+                level, target, entry, side
+            ),
+            TradeIntentError::TargetsOutOfOrder { level } => {
+                write!(f, "TP{} is not beyond TP{}", level, level - 1)
+            }
+            TradeIntentError::EntryFarFromMarket {
+                entry,
+                mark_price,
+                distance_percent,
+            } => write!(
+                f,
+                "Entry {} is {:.1}% away from the market price {}",
+                entry, distance_percent, mark_price
+            ),
+            TradeIntentError::RewardToRiskTooLow { ratio, min } => {
+                write!(f, "Reward to risk {:.2} is below {}", ratio, min)
+            }
+            TradeIntentError::RewardToRiskTooHigh { ratio, max } => {
+                write!(f, "Reward to risk {:.2} is above {}", ratio, max)
+            }
+        }
+    }
+}
+
+/// Bounds a signal has to respect before it is traded.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SanityLimits {
+    /// Largest distance between the entry and the market price, in percent of the market price.
+    pub max_entry_distance_percent: f64,
+    /// Reward to TP1 per unit of risk to the stop.
+    pub min_reward_to_risk: f64,
+    /// Anything higher usually means a stop glued to the entry or a typo in a level.
+    pub max_reward_to_risk: f64,
+    /// Older signals, e.g. replayed by the catch-up after a restart, are logged but not traded.
+    pub max_signal_age_secs: i64,
+}
+
+impl Default for SanityLimits {
+    fn default() -> Self {
+        Self {
+            max_entry_distance_percent: 10.0,
+            min_reward_to_risk: 0.3,
+            max_reward_to_risk: 20.0,
+            max_signal_age_secs: 5 * 60,
         }
     }
 }
@@ -43,6 +143,8 @@ pub struct TradeIntentBuilder {
     targets: Option<Vec<f64>>,
     timeframe: Option<String>,
     stop_loss: Option<f64>,
+    source: Option<i64>,
+    signal_time_ms: Option<i64>,
 }
 impl TradeIntent {
     pub fn builder(symbol: &Symbol) -> TradeIntentBuilder {
@@ -53,10 +155,97 @@ impl TradeIntent {
             targets: None,
             timeframe: None,
             stop_loss: None,
+            source: None,
+            signal_time_ms: None,
         }
     }
 }
 
+impl TradeIntent {
+    /// Checks the levels make sense for the side, and the entry against `mark_price` when
+    /// one is known. Returns the first failed check.
+    pub fn validate(
+        &self,
+        limits: &SanityLimits,
+        mark_price: Option<f64>,
+    ) -> Result<(), TradeIntentError> {
+        let prices = [self.entry, self.stop_loss]
+            .into_iter()
+            .chain(self.targets.iter().copied());
+
+        for price in prices {
+            if !price.is_finite() || price <= 0.0 {
+                return Err(TradeIntentError::InvalidPrice(price));
+            }
+        }
+
+        let Some(first_target) = self.targets.first() else {
+            return Err(TradeIntentError::MissingTargets);
+        };
+
+        // Distance in the direction of profit, positive when the price is beyond the entry.
+        let gain = |price: f64| match self.side {
+            OrderSide::Buy => price - self.entry,
+            OrderSide::Sell => self.entry - price,
+        };
+
+        if gain(self.stop_loss) >= 0.0 {
+            return Err(TradeIntentError::StopOnWrongSide {
+                side: self.side,
+                entry: self.entry,
+                stop_loss: self.stop_loss,
+            });
+        }
+
+        for (index, target) in self.targets.iter().enumerate() {
+            if gain(*target) <= 0.0 {
+                return Err(TradeIntentError::TargetOnWrongSide {
+                    side: self.side,
+                    level: index + 1,
+                    target: *target,
+                    entry: self.entry,
+                });
+            }
+        }
+
+        for (index, pair) in self.targets.windows(2).enumerate() {
+            if gain(pair[1]) <= gain(pair[0]) {
+                return Err(TradeIntentError::TargetsOutOfOrder { level: index + 2 });
+            }
+        }
+
+        if let Some(mark_price) = mark_price {
+            let distance_percent = (self.entry - mark_price).abs() / mark_price * 100.0;
+
+            if distance_percent > limits.max_entry_distance_percent {
+                return Err(TradeIntentError::EntryFarFromMarket {
+                    entry: self.entry,
+                    mark_price,
+                    distance_percent,
+                });
+            }
+        }
+
+        let ratio = gain(*first_target) / -gain(self.stop_loss);
+
+        if ratio < limits.min_reward_to_risk {
+            return Err(TradeIntentError::RewardToRiskTooLow {
+                ratio,
+                min: limits.min_reward_to_risk,
+            });
+        }
+
+        if ratio > limits.max_reward_to_risk {
+            return Err(TradeIntentError::RewardToRiskTooHigh {
+                ratio,
+                max: limits.max_reward_to_risk,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 impl TradeIntentBuilder {
     pub fn side(mut self, side: OrderSide) -> Self {
         self.side = Some(side);
@@ -83,6 +272,17 @@ impl TradeIntentBuilder {
         self
     }
 
+    pub fn source(mut self, source: i64) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Defaults to the time the intent is built.
+    pub fn signal_time_ms(mut self, signal_time_ms: i64) -> Self {
+        self.signal_time_ms = Some(signal_time_ms);
+        self
+    }
+
     pub fn build(self) -> Result<TradeIntent, TradeIntentError> {
         Ok(TradeIntent {
             intent_id: Uuid::new_v4(),
@@ -92,6 +292,108 @@ impl TradeIntentBuilder {
             targets: self.targets.ok_or(TradeIntentError::MissingTargets)?,
             timeframe: self.timeframe.ok_or(TradeIntentError::MissingTimeframe)?,
             stop_loss: self.stop_loss.ok_or(TradeIntentError::MissingStopLoss)?,
+            source: self.source,
+            signal_time_ms: self.signal_time_ms.unwrap_or_else(now_ms),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(side: OrderSide, entry: f64, stop_loss: f64, targets: &[f64]) -> TradeIntent {
+        TradeIntent::builder(&Symbol::BTC)
+            .side(side)
+            .entry(entry)
+            .stop_loss(stop_loss)
+            .targets(targets)
+            .timeframe("1h")
+            .build()
+            .unwrap()
+    }
+
+    fn validate(intent: &TradeIntent, mark_price: Option<f64>) -> Result<(), TradeIntentError> {
+        intent.validate(&SanityLimits::default(), mark_price)
+    }
+
+    #[test]
+    fn test_valid_long_and_short_pass() {
+        let long = intent(OrderSide::Buy, 100.0, 95.0, &[105.0, 110.0]);
+        let short = intent(OrderSide::Sell, 100.0, 105.0, &[95.0, 90.0]);
+
+        assert!(validate(&long, Some(101.0)).is_ok());
+        assert!(validate(&short, Some(99.0)).is_ok());
+    }
+
+    #[test]
+    fn test_long_with_stop_above_entry_is_rejected() {
+        let long = intent(OrderSide::Buy, 100.0, 105.0, &[110.0]);
+
+        assert!(matches!(
+            validate(&long, None),
+            Err(TradeIntentError::StopOnWrongSide { .. })
+        ));
+    }
+
+    #[test]
+    fn test_short_target_above_entry_is_rejected() {
+        let short = intent(OrderSide::Sell, 100.0, 105.0, &[95.0, 101.0]);
+
+        assert!(matches!(
+            validate(&short, None),
+            Err(TradeIntentError::TargetOnWrongSide { level: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_targets_out_of_order_are_rejected() {
+        let long = intent(OrderSide::Buy, 100.0, 95.0, &[110.0, 105.0]);
+        let short = intent(OrderSide::Sell, 100.0, 105.0, &[90.0, 90.0]);
+
+        assert!(matches!(
+            validate(&long, None),
+            Err(TradeIntentError::TargetsOutOfOrder { level: 2 })
+        ));
+        assert!(matches!(
+            validate(&short, None),
+            Err(TradeIntentError::TargetsOutOfOrder { level: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_entry_far_from_market_is_rejected() {
+        let long = intent(OrderSide::Buy, 150.0, 140.0, &[160.0]);
+
+        assert!(validate(&long, None).is_ok());
+        assert!(matches!(
+            validate(&long, Some(100.0)),
+            Err(TradeIntentError::EntryFarFromMarket { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reward_to_risk_bounds() {
+        let tiny_reward = intent(OrderSide::Buy, 100.0, 90.0, &[101.0]);
+        let glued_stop = intent(OrderSide::Buy, 100.0, 99.9, &[110.0]);
+
+        assert!(matches!(
+            validate(&tiny_reward, None),
+            Err(TradeIntentError::RewardToRiskTooLow { .. })
+        ));
+        assert!(matches!(
+            validate(&glued_stop, None),
+            Err(TradeIntentError::RewardToRiskTooHigh { .. })
+        ));
+    }
+
+    #[test]
+    fn test_non_positive_price_is_rejected() {
+        let long = intent(OrderSide::Buy, 100.0, 0.0, &[110.0]);
+
+        assert!(matches!(
+            validate(&long, None),
+            Err(TradeIntentError::InvalidPrice(_))
+        ));
+    }
+}
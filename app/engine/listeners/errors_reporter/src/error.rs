@@ -1,12 +1,18 @@
-use telegram_types::{Client, PeerRef};
+use telegram::outbox::Outbox;
+use telegram_types::PeerRef;
 
-pub async fn report_error(client: &Client, error_peer: PeerRef, source: &'static str, error: &str) {
+/// Queues the report; a report that cannot be delivered is only printed, never published again.
+pub fn report_error(outbox: &Outbox, error_peer: PeerRef, source: &'static str, error: &str) {
     let formatted = format!("Source: {}\n\nError: {}", source, error);
+    let delivery = outbox.send(error_peer, formatted);
+    let error = error.to_string();
 
-    if let Err(send_err) = client.send_message(error_peer, formatted).await {
-        println!(
-            "[ERROR_REPORTER_FAILURE]\nSource: {}\nOriginal: {}\nTelegram error: {:?}",
-            source, error, send_err
-        );
-    }
+    tokio::spawn(async move {
+        if let Err(send_err) = delivery.await {
+            println!(
+                "[ERROR_REPORTER_FAILURE]\nSource: {}\nOriginal: {}\nTelegram error: {:?}",
+                source, error, send_err
+            );
+        }
+    });
 }
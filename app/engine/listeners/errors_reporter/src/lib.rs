@@ -1,10 +1,10 @@
 mod error;
 use publisher::handle_recv_error;
 use std::sync::Arc;
-use telegram_types::Client;
+use telegram::outbox::Outbox;
 use telegram_types::PeerRef;
 
-pub async fn run(client: Arc<Client>, to_peer: PeerRef, bus: Arc<publisher::EventBus>) {
+pub async fn run(outbox: Outbox, to_peer: PeerRef, bus: Arc<publisher::EventBus>) {
     println!("Errors Reporter running...");
     let mut rx = bus.subscribe();
 
@@ -15,7 +15,7 @@ pub async fn run(client: Arc<Client>, to_peer: PeerRef, bus: Arc<publisher::Even
                     let error_message = error_event.message_text;
                     let error_source = error_event.source;
 
-                    error::report_error(&client, to_peer, error_source, &error_message).await;
+                    error::report_error(&outbox, to_peer, error_source, &error_message);
                 }
                 _ => continue,
             },
@@ -0,0 +1,78 @@
+use serde::Deserialize;
+
+/// Routing table of the forwarder; every rule is evaluated for every message.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ForwarderConfig {
+    pub rules: Vec<RuleConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleConfig {
+    /// Shown in errors and available to templates as `{rule}`.
+    pub name: String,
+    /// Chats to forward from.
+    pub sources: Vec<ChatRef>,
+    #[serde(default)]
+    pub filter: FilterConfig,
+    #[serde(default)]
+    pub transform: TransformConfig,
+    /// Bare chat IDs of the dispatcher's dialogs to send to.
+    pub destinations: Vec<i64>,
+}
+
+/// A chat or user, by bare ID or by username (with or without the `@`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ChatRef {
+    Id(i64),
+    Username(String),
+}
+
+impl ChatRef {
+    pub fn matches(&self, id: i64, username: Option<&str>) -> bool {
+        match self {
+            ChatRef::Id(expected) => *expected == id,
+            ChatRef::Username(expected) => username.is_some_and(|username| {
+                expected
+                    .trim_start_matches('@')
+                    .eq_ignore_ascii_case(username)
+            }),
+        }
+    }
+}
+
+/// Every set filter must match; an empty list or a missing regex lets everything through.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FilterConfig {
+    /// At least one must appear in the text, ignoring case.
+    pub keywords: Vec<String>,
+    pub regex: Option<String>,
+    pub senders: Vec<ChatRef>,
+    pub media: Vec<MediaKind>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaKind {
+    /// No media at all.
+    Text,
+    Photo,
+    Video,
+    Document,
+    Sticker,
+    /// Polls, locations, contacts, web pages and the like.
+    Other,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TransformConfig {
+    pub strip_emojis: bool,
+    /// Drops "null"s and blank lines, and trims blockquotes, like the KOL relays do.
+    pub postprocess_html: bool,
+    /// HTML placed around the message. `{rule}`, `{chat}` and `{sender}` are replaced.
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+}
@@ -0,0 +1,43 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum ForwarderError {
+    InvalidRegex {
+        rule: String,
+        error: regex::Error,
+    },
+    /// The dispatcher has no dialog with this chat.
+    UnknownDestination {
+        rule: String,
+        chat_id: i64,
+    },
+    NoDestinations {
+        rule: String,
+    },
+}
+
+impl Display for ForwarderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ForwarderError::InvalidRegex { rule, error } => {
+                write!(f, "Rule {}: invalid regex: {}", rule, error)
+            }
+            ForwarderError::UnknownDestination { rule, chat_id } => {
+                write!(f, "Rule {}: no dialog with destination {}", rule, chat_id)
+            }
+            ForwarderError::NoDestinations { rule } => {
+                write!(f, "Rule {}: no destinations", rule)
+            }
+        }
+    }
+}
+
+impl Error for ForwarderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ForwarderError::InvalidRegex { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -1,53 +1,39 @@
+pub mod config;
+pub mod errors;
+pub mod router;
+
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use publisher::handle_recv_error;
-use telegram_types::Client;
-use telegram_types::PeerRef;
-
-pub async fn run(
-    client: Arc<Client>,
-    from_peer: PeerRef,
-    to_peer: PeerRef,
-    bus: Arc<publisher::EventBus>,
-) {
+use publisher::types::{PulsgramEvent, TgDeletion};
+use telegram::copies::SourceMessage;
+use telegram::media::extract_photo_url_from_raw;
+use telegram::outbox::Outbox;
+use telegram_types::{InputMessage, Media, Message, PeerRef};
+
+use crate::config::MediaKind;
+use crate::router::{Incoming, Router};
+
+/// Forwards messages by the routing table; each matching rule sends its own copy.
+///
+/// Copies follow their source message when it is edited or deleted.
+pub async fn run(outbox: Outbox, router: Router, bus: Arc<publisher::EventBus>) {
+    println!("Forwarder running...");
     let mut rx = bus.subscribe();
 
     loop {
         match rx.recv().await {
-            Ok(event) => match event {
-                publisher::types::PulsgramEvent::Telegram(tg_event) => {
-                    let message = tg_event.message;
-                    let message_peer_id = message.peer_id();
-
-                    if message_peer_id != from_peer.id {
-                        continue;
-                    }
-
-                    let mut message_text = message.text().to_owned();
-
-                    if message_text.is_empty() {
-                        message_text = "<non-text message (sticker or img)>\n".to_string();
-                    }
-
-                    match client.send_message(to_peer, message_text).await {
-                        Ok(_) => {}
-                        Err(error) => {
-                            let msg = format!(
-                                "Failed to forward message.\nFrom: {}\nTo: {}\nError: {}",
-                                from_peer.id, to_peer.id, error
-                            );
-
-                            bus.publish(publisher::types::PulsgramEvent::Error(
-                                publisher::types::ErrorEvent {
-                                    message_text: msg,
-                                    source: "Forwarder::Err",
-                                },
-                            ));
-                        }
-                    }
-                }
-                _ => continue,
-            },
+            Ok(PulsgramEvent::Telegram(tg_event)) => {
+                forward(&outbox, &router, &tg_event.message, &bus);
+            }
+            Ok(PulsgramEvent::TelegramEdited(tg_event)) => {
+                edit_copies(&outbox, &router, &tg_event.message, &bus);
+            }
+            Ok(PulsgramEvent::TelegramDeleted(deletion)) => {
+                delete_copies(&outbox, &deletion, &bus);
+            }
+            Ok(_) => continue,
             Err(error) => {
                 if handle_recv_error("Forwarder RecvError", error, &bus) {
                     break;
@@ -56,3 +42,163 @@ pub async fn run(
         }
     }
 }
+
+fn forward(outbox: &Outbox, router: &Router, message: &Message, bus: &Arc<publisher::EventBus>) {
+    let incoming = incoming(message);
+    let source = SourceMessage::of(message);
+
+    for (rule, destination, input_message) in render(router, message, &incoming) {
+        outbox
+            .send_copy(source, destination, input_message)
+            .report_failure(
+                Arc::clone(bus),
+                "Forwarder::Err",
+                format!(
+                    "Failed to forward message.\nRule: {}\nFrom: {}\nTo: {}",
+                    rule, incoming.chat_id, destination.id
+                ),
+            );
+    }
+}
+
+/// Re-renders the edited message and edits the copy each destination got.
+///
+/// When several rules sent to the same destination, the first matching rule's rendering is used
+/// for all of its copies there.
+fn edit_copies(
+    outbox: &Outbox,
+    router: &Router,
+    message: &Message,
+    bus: &Arc<publisher::EventBus>,
+) {
+    let copies = outbox.copies(&SourceMessage::of(message));
+
+    if copies.is_empty() {
+        return;
+    }
+
+    let incoming = incoming(message);
+
+    let mut rendered = HashMap::new();
+
+    for (_, destination, input_message) in render(router, message, &incoming) {
+        rendered.entry(destination.id).or_insert(input_message);
+    }
+
+    for copy in copies {
+        // The edit no longer matches a rule sending there; the copy is left as it was.
+        let Some(input_message) = rendered.get(&copy.peer.id) else {
+            continue;
+        };
+
+        outbox
+            .edit(copy.peer, copy.message_id, input_message.clone())
+            .report_failure(
+                Arc::clone(bus),
+                "Forwarder::Edit",
+                format!(
+                    "Failed to edit forwarded message.\nFrom: {}\nTo: {}\nMessage: {}",
+                    incoming.chat_id, copy.peer.id, copy.message_id
+                ),
+            );
+    }
+}
+
+fn delete_copies(outbox: &Outbox, deletion: &TgDeletion, bus: &Arc<publisher::EventBus>) {
+    let mut by_peer: HashMap<PeerRef, Vec<i32>> = HashMap::new();
+
+    for source in SourceMessage::deleted(deletion) {
+        for copy in outbox.forget_copies(&source) {
+            by_peer.entry(copy.peer).or_default().push(copy.message_id);
+        }
+    }
+
+    for (peer, message_ids) in by_peer {
+        let context = format!(
+            "Failed to delete forwarded messages.\nTo: {}\nMessages: {:?}",
+            peer.id, message_ids
+        );
+
+        outbox.delete(peer, message_ids).report_failure(
+            Arc::clone(bus),
+            "Forwarder::Delete",
+            context,
+        );
+    }
+}
+
+fn incoming(message: &Message) -> Incoming<'_> {
+    let chat = message.peer();
+    let sender = message.sender();
+
+    Incoming {
+        chat_id: message.peer_id().bare_id(),
+        chat_username: chat.and_then(|chat| chat.username()),
+        chat_name: chat.and_then(|chat| chat.name()),
+        sender_id: message.sender_id().map(|id| id.bare_id()),
+        sender_username: sender.and_then(|sender| sender.username()),
+        sender_name: sender.and_then(|sender| sender.name()),
+        text: message.text(),
+        media: media_kind(message),
+    }
+}
+
+/// One message per destination of every matching rule, with the rule's name.
+fn render<'a>(
+    router: &'a Router,
+    message: &Message,
+    incoming: &'a Incoming,
+) -> Vec<(&'a str, PeerRef, InputMessage)> {
+    let mut routes = router.matching(incoming).peekable();
+
+    if routes.peek().is_none() {
+        return Vec::new();
+    }
+
+    let html = if incoming.text.is_empty() {
+        "<i>non-text message (sticker or img)</i>".to_string()
+    } else {
+        message.html_text()
+    };
+
+    let photo_url = extract_photo_url_from_raw(&message.raw);
+
+    let mut messages = Vec::new();
+
+    for route in routes {
+        let mut rendered = route.rule.render(&html, incoming);
+
+        if let Some(photo_url) = &photo_url {
+            rendered.insert_str(0, &format!("<a href=\"{}\">&#8205;</a>", photo_url));
+        }
+
+        for destination in &route.destinations {
+            let input_message = InputMessage::new()
+                .html(rendered.clone())
+                .link_preview(photo_url.is_some())
+                .invert_media(photo_url.is_some());
+
+            messages.push((route.rule.name.as_str(), *destination, input_message));
+        }
+    }
+
+    messages
+}
+
+fn media_kind(message: &Message) -> MediaKind {
+    match message.media() {
+        // Link previews are part of a text message.
+        None | Some(Media::WebPage(_)) => MediaKind::Text,
+        Some(Media::Photo(_)) => MediaKind::Photo,
+        Some(Media::Sticker(_)) => MediaKind::Sticker,
+        Some(Media::Document(document))
+            if document
+                .mime_type()
+                .is_some_and(|mime_type| mime_type.starts_with("video/")) =>
+        {
+            MediaKind::Video
+        }
+        Some(Media::Document(_)) => MediaKind::Document,
+        Some(_) => MediaKind::Other,
+    }
+}
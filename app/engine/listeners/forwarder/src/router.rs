@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use shared::{postprocess_html, remove_emojis};
+use telegram_types::PeerRef;
+
+use crate::config::{ChatRef, ForwarderConfig, MediaKind, RuleConfig, TransformConfig};
+use crate::errors::ForwarderError;
+
+/// The parts of a message the rules look at.
+#[derive(Debug, Clone)]
+pub struct Incoming<'a> {
+    pub chat_id: i64,
+    pub chat_username: Option<&'a str>,
+    pub chat_name: Option<&'a str>,
+    pub sender_id: Option<i64>,
+    pub sender_username: Option<&'a str>,
+    pub sender_name: Option<&'a str>,
+    pub text: &'a str,
+    pub media: MediaKind,
+}
+
+/// A rule with its regex compiled and keywords lower-cased.
+#[derive(Debug)]
+pub struct Rule {
+    pub name: String,
+    sources: Vec<ChatRef>,
+    keywords: Vec<String>,
+    regex: Option<Regex>,
+    senders: Vec<ChatRef>,
+    media: Vec<MediaKind>,
+    transform: TransformConfig,
+}
+
+impl Rule {
+    pub fn compile(config: &RuleConfig) -> Result<Self, ForwarderError> {
+        let regex = config
+            .filter
+            .regex
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|error| ForwarderError::InvalidRegex {
+                rule: config.name.clone(),
+                error,
+            })?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            sources: config.sources.clone(),
+            keywords: config
+                .filter
+                .keywords
+                .iter()
+                .map(|keyword| keyword.to_lowercase())
+                .collect(),
+            regex,
+            senders: config.filter.senders.clone(),
+            media: config.filter.media.clone(),
+            transform: config.transform.clone(),
+        })
+    }
+
+    pub fn matches(&self, incoming: &Incoming) -> bool {
+        if !self
+            .sources
+            .iter()
+            .any(|source| source.matches(incoming.chat_id, incoming.chat_username))
+        {
+            return false;
+        }
+
+        if !self.keywords.is_empty() {
+            let text = incoming.text.to_lowercase();
+
+            if !self.keywords.iter().any(|keyword| text.contains(keyword)) {
+                return false;
+            }
+        }
+
+        if let Some(regex) = &self.regex
+            && !regex.is_match(incoming.text)
+        {
+            return false;
+        }
+
+        if !self.senders.is_empty() {
+            let Some(sender_id) = incoming.sender_id else {
+                return false;
+            };
+
+            if !self
+                .senders
+                .iter()
+                .any(|sender| sender.matches(sender_id, incoming.sender_username))
+            {
+                return false;
+            }
+        }
+
+        self.media.is_empty() || self.media.contains(&incoming.media)
+    }
+
+    pub fn render(&self, html: &str, incoming: &Incoming) -> String {
+        let mut body = html.to_string();
+
+        if self.transform.strip_emojis {
+            body = remove_emojis(&body).into_owned();
+        }
+
+        if self.transform.postprocess_html {
+            body = postprocess_html(&body);
+        }
+
+        let prefix = self.transform.prefix.as_deref().unwrap_or_default();
+        let suffix = self.transform.suffix.as_deref().unwrap_or_default();
+
+        format!(
+            "{}{}{}",
+            self.fill(prefix, incoming),
+            body,
+            self.fill(suffix, incoming)
+        )
+    }
+
+    fn fill(&self, template: &str, incoming: &Incoming) -> String {
+        if template.is_empty() {
+            return String::new();
+        }
+
+        let chat = incoming
+            .chat_name
+            .or(incoming.chat_username)
+            .map_or_else(|| incoming.chat_id.to_string(), str::to_string);
+
+        let sender = incoming
+            .sender_username
+            .map(|username| format!("@{username}"))
+            .or_else(|| incoming.sender_name.map(str::to_string))
+            .unwrap_or_default();
+
+        template
+            .replace("{rule}", &escape_html(&self.name))
+            .replace("{chat}", &escape_html(&chat))
+            .replace("{sender}", &escape_html(&sender))
+    }
+}
+
+pub struct Route {
+    pub rule: Rule,
+    pub destinations: Vec<PeerRef>,
+}
+
+/// Every rule of the routing table, with its destinations resolved.
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    /// Destinations are looked up in the dispatcher's dialogs, keyed by bare chat ID.
+    pub fn new(
+        config: &ForwarderConfig,
+        peers: &HashMap<i64, PeerRef>,
+    ) -> Result<Self, ForwarderError> {
+        let mut routes = Vec::with_capacity(config.rules.len());
+
+        for rule_config in &config.rules {
+            if rule_config.destinations.is_empty() {
+                return Err(ForwarderError::NoDestinations {
+                    rule: rule_config.name.clone(),
+                });
+            }
+
+            let destinations = rule_config
+                .destinations
+                .iter()
+                .map(|chat_id| {
+                    peers
+                        .get(chat_id)
+                        .copied()
+                        .ok_or_else(|| ForwarderError::UnknownDestination {
+                            rule: rule_config.name.clone(),
+                            chat_id: *chat_id,
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            routes.push(Route {
+                rule: Rule::compile(rule_config)?,
+                destinations,
+            });
+        }
+
+        Ok(Self { routes })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub fn matching<'a>(&'a self, incoming: &'a Incoming) -> impl Iterator<Item = &'a Route> {
+        self.routes
+            .iter()
+            .filter(move |route| route.rule.matches(incoming))
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FilterConfig;
+
+    fn rule_config(filter: FilterConfig, transform: TransformConfig) -> RuleConfig {
+        RuleConfig {
+            name: "calls".into(),
+            sources: vec![ChatRef::Id(1), ChatRef::Username("@AlphaCalls".into())],
+            filter,
+            transform,
+            destinations: vec![9],
+        }
+    }
+
+    fn incoming(chat_id: i64, text: &str) -> Incoming<'_> {
+        Incoming {
+            chat_id,
+            chat_username: None,
+            chat_name: Some("Alpha <Calls>"),
+            sender_id: Some(42),
+            sender_username: Some("caller"),
+            sender_name: Some("Caller"),
+            text,
+            media: MediaKind::Text,
+        }
+    }
+
+    fn rule(filter: FilterConfig) -> Rule {
+        Rule::compile(&rule_config(filter, TransformConfig::default())).unwrap()
+    }
+
+    #[test]
+    fn test_sources_by_id_or_username() {
+        let rule = rule(FilterConfig::default());
+
+        assert!(rule.matches(&incoming(1, "hi")));
+        assert!(!rule.matches(&incoming(2, "hi")));
+
+        let by_username = Incoming {
+            chat_username: Some("alphacalls"),
+            ..incoming(3, "hi")
+        };
+        assert!(rule.matches(&by_username));
+    }
+
+    #[test]
+    fn test_keywords_and_regex_must_both_match() {
+        let rule = rule(FilterConfig {
+            keywords: vec!["LONG".into(), "short".into()],
+            regex: Some(r"\$[A-Z]+".into()),
+            ..FilterConfig::default()
+        });
+
+        assert!(rule.matches(&incoming(1, "Long $BTC")));
+        assert!(!rule.matches(&incoming(1, "long btc")));
+        assert!(!rule.matches(&incoming(1, "$BTC looks good")));
+    }
+
+    #[test]
+    fn test_sender_and_media_filters() {
+        let rule = rule(FilterConfig {
+            senders: vec![ChatRef::Username("caller".into())],
+            media: vec![MediaKind::Photo],
+            ..FilterConfig::default()
+        });
+
+        assert!(!rule.matches(&incoming(1, "chart")));
+
+        let photo = Incoming {
+            media: MediaKind::Photo,
+            ..incoming(1, "chart")
+        };
+        assert!(rule.matches(&photo));
+
+        let other_sender = Incoming {
+            sender_id: Some(7),
+            sender_username: Some("someone"),
+            ..photo
+        };
+        assert!(!rule.matches(&other_sender));
+    }
+
+    #[test]
+    fn test_invalid_regex_names_the_rule() {
+        let result = Rule::compile(&rule_config(
+            FilterConfig {
+                regex: Some("(".into()),
+                ..FilterConfig::default()
+            },
+            TransformConfig::default(),
+        ));
+
+        assert!(matches!(
+            result,
+            Err(ForwarderError::InvalidRegex { rule, .. }) if rule == "calls"
+        ));
+    }
+
+    #[test]
+    fn test_render_applies_transform_and_templates() {
+        let rule = Rule::compile(&rule_config(
+            FilterConfig::default(),
+            TransformConfig {
+                strip_emojis: true,
+                postprocess_html: true,
+                prefix: Some("<b>{chat}</b> ({sender})\n".into()),
+                suffix: Some("\n#{rule}".into()),
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(
+            rule.render("🚀 Long BTC\n\nnull", &incoming(1, "")),
+            "<b>Alpha &lt;Calls&gt;</b> (@caller)\n Long BTC\n\n#calls"
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KolConvergenceConfig {
+    /// Only KOLs acting on the same target within this many seconds count together.
+    pub window_secs: i64,
+    /// Distinct KOLs needed before an alert is posted.
+    pub min_kols: usize,
+}
+
+impl Default for KolConvergenceConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 6 * 60 * 60,
+            min_kols: 3,
+        }
+    }
+}
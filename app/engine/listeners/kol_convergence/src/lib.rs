@@ -0,0 +1,135 @@
+pub mod config;
+pub mod tracker;
+
+use crate::config::KolConvergenceConfig;
+use crate::tracker::{Convergence, ConvergenceTracker, Target};
+use publisher::types::{KolActivity, PulsgramEvent};
+use publisher::{EventBus, handle_recv_error};
+use std::sync::Arc;
+use telegram::outbox::Outbox;
+use telegram_types::{InputMessage, PeerRef};
+use twitter::entities::EntityKind;
+use twitter::regex::MessageType;
+
+/// Alerts when several KOLs follow the same account or mention the same cashtag within the window.
+pub async fn run(
+    bus: Arc<EventBus>,
+    outbox: Outbox,
+    destination: PeerRef,
+    config: KolConvergenceConfig,
+) {
+    println!("KOL Convergence running...");
+    let mut rx = bus.subscribe();
+
+    let mut tracker = ConvergenceTracker::new(config.window_secs, config.min_kols);
+
+    loop {
+        match rx.recv().await {
+            Ok(PulsgramEvent::KolActivity(activity)) => {
+                for convergence in observe(&mut tracker, &activity) {
+                    let input_message = InputMessage::new().html(alert_html(&convergence));
+
+                    outbox.send(destination, input_message).report_failure(
+                        Arc::clone(&bus),
+                        "KolConvergence::SendMessage",
+                        format!(
+                            "KOL convergence alert failed.\nDestination: {}",
+                            destination.id
+                        ),
+                    );
+                }
+            }
+            Ok(_) => continue,
+            Err(error) => {
+                if handle_recv_error("KOL Convergence RecvError", error, &bus) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn observe(tracker: &mut ConvergenceTracker, activity: &KolActivity) -> Vec<Convergence> {
+    let at = activity.message.date().timestamp();
+
+    let (kol, targets) = match &activity.kind {
+        // The profile text after a follow belongs to the followed account, not the KOL.
+        MessageType::Follow {
+            follower, followee, ..
+        } => (follower, vec![Target::Account(followee.to_lowercase())]),
+        MessageType::Tweet { user, .. }
+        | MessageType::Retweet { user, .. }
+        | MessageType::Reply { user, .. }
+        | MessageType::Quote { user, .. } => {
+            let mut cashtags = Vec::new();
+
+            for entity in &activity.entities {
+                if let EntityKind::Cashtag(ticker) = &entity.kind {
+                    let target = Target::Cashtag(ticker.clone());
+
+                    if !cashtags.contains(&target) {
+                        cashtags.push(target);
+                    }
+                }
+            }
+
+            (user, cashtags)
+        }
+        MessageType::ProfileUpdate { .. } | MessageType::Unknown => return Vec::new(),
+    };
+
+    targets
+        .into_iter()
+        .filter_map(|target| tracker.observe(kol, target, at))
+        .collect()
+}
+
+fn alert_html(convergence: &Convergence) -> String {
+    let first = convergence.kols.first().map_or(0, |(_, at)| *at);
+
+    let kols = convergence
+        .kols
+        .iter()
+        .map(|(kol, at)| format!("• {} (+{})", kol, format_duration(at - first)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "<b>{} KOLs {} within {}</b>\n\n{}",
+        convergence.kols.len(),
+        convergence.target,
+        format_duration(convergence.spread_secs),
+        kols
+    )
+}
+
+fn format_duration(secs: i64) -> String {
+    match secs {
+        secs if secs < 60 => format!("{secs}s"),
+        secs if secs < 60 * 60 => format!("{}m", secs / 60),
+        secs => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_lists_kols_and_spread() {
+        let convergence = Convergence {
+            target: Target::Cashtag("GHOST".into()),
+            kols: vec![
+                ("alice".into(), 1_000),
+                ("bob".into(), 1_300),
+                ("carol".into(), 4_720),
+            ],
+            spread_secs: 3_720,
+        };
+
+        assert_eq!(
+            alert_html(&convergence),
+            "<b>3 KOLs mentioned $GHOST within 1h 2m</b>\n\n• alice (+0s)\n• bob (+5m)\n• carol (+1h 2m)"
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// What several KOLs converge on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// An account they followed, by handle.
+    Account(String),
+    /// A cashtag they mentioned, upper-cased.
+    Cashtag(String),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Account(handle) => write!(f, "followed {handle}"),
+            Target::Cashtag(ticker) => write!(f, "mentioned ${ticker}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Convergence {
+    pub target: Target,
+    /// Each KOL with the time of its latest action, earliest first.
+    pub kols: Vec<(String, i64)>,
+    /// Seconds between the first and the last action.
+    pub spread_secs: i64,
+}
+
+#[derive(Debug, Default)]
+struct TargetState {
+    /// Keyed by lower-cased handle, with the handle as written and the time in unix seconds.
+    kols: HashMap<String, (String, i64)>,
+    alerted: bool,
+}
+
+/// Distinct KOLs per target over a sliding window.
+///
+/// A target is announced once when it reaches the threshold, and again only after enough of its
+/// KOLs have left the window for it to fall back below.
+#[derive(Debug)]
+pub struct ConvergenceTracker {
+    window_secs: i64,
+    min_kols: usize,
+    targets: HashMap<Target, TargetState>,
+}
+
+impl ConvergenceTracker {
+    pub fn new(window_secs: i64, min_kols: usize) -> Self {
+        Self {
+            window_secs,
+            min_kols: min_kols.max(2),
+            targets: HashMap::new(),
+        }
+    }
+
+    pub fn observe(&mut self, kol: &str, target: Target, at: i64) -> Option<Convergence> {
+        self.prune(at);
+
+        let state = self.targets.entry(target.clone()).or_default();
+
+        let entry = state
+            .kols
+            .entry(kol.to_lowercase())
+            .or_insert_with(|| (kol.to_string(), at));
+        entry.1 = entry.1.max(at);
+
+        if state.alerted || state.kols.len() < self.min_kols {
+            return None;
+        }
+
+        state.alerted = true;
+
+        let mut kols: Vec<(String, i64)> = state.kols.values().cloned().collect();
+        kols.sort_by_key(|(_, at)| *at);
+
+        let spread_secs = match (kols.first(), kols.last()) {
+            (Some((_, first)), Some((_, last))) => last - first,
+            _ => 0,
+        };
+
+        Some(Convergence {
+            target,
+            kols,
+            spread_secs,
+        })
+    }
+
+    fn prune(&mut self, now: i64) {
+        let oldest = now - self.window_secs;
+        let min_kols = self.min_kols;
+
+        self.targets.retain(|_, state| {
+            state.kols.retain(|_, (_, at)| *at >= oldest);
+
+            if state.kols.len() < min_kols {
+                state.alerted = false;
+            }
+
+            !state.kols.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cashtag(ticker: &str) -> Target {
+        Target::Cashtag(ticker.into())
+    }
+
+    #[test]
+    fn test_alerts_once_when_threshold_is_crossed() {
+        let mut tracker = ConvergenceTracker::new(3600, 3);
+
+        assert!(tracker.observe("alice", cashtag("GHOST"), 0).is_none());
+        assert!(tracker.observe("bob", cashtag("GHOST"), 600).is_none());
+
+        let convergence = tracker.observe("carol", cashtag("GHOST"), 900).unwrap();
+
+        assert_eq!(convergence.spread_secs, 900);
+        assert_eq!(
+            convergence
+                .kols
+                .iter()
+                .map(|(kol, _)| kol.as_str())
+                .collect::<Vec<_>>(),
+            vec!["alice", "bob", "carol"]
+        );
+
+        assert!(tracker.observe("dave", cashtag("GHOST"), 1000).is_none());
+    }
+
+    #[test]
+    fn test_same_kol_counts_once() {
+        let mut tracker = ConvergenceTracker::new(3600, 2);
+
+        assert!(tracker.observe("alice", cashtag("GHOST"), 0).is_none());
+        assert!(tracker.observe("Alice", cashtag("GHOST"), 60).is_none());
+        assert!(tracker.observe("bob", cashtag("GHOST"), 120).is_some());
+    }
+
+    #[test]
+    fn test_actions_outside_the_window_do_not_count() {
+        let mut tracker = ConvergenceTracker::new(3600, 2);
+
+        assert!(tracker.observe("alice", cashtag("GHOST"), 0).is_none());
+        assert!(tracker.observe("bob", cashtag("GHOST"), 4000).is_none());
+        assert!(tracker.observe("carol", cashtag("GHOST"), 4100).is_some());
+    }
+
+    #[test]
+    fn test_targets_are_tracked_separately() {
+        let mut tracker = ConvergenceTracker::new(3600, 2);
+
+        assert!(tracker.observe("alice", cashtag("GHOST"), 0).is_none());
+        assert!(
+            tracker
+                .observe("bob", Target::Account("ghost_dev".into()), 10)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_realerts_after_falling_below_threshold() {
+        let mut tracker = ConvergenceTracker::new(3600, 2);
+
+        tracker.observe("alice", cashtag("GHOST"), 0);
+        assert!(tracker.observe("bob", cashtag("GHOST"), 10).is_some());
+
+        assert!(tracker.observe("carol", cashtag("GHOST"), 5000).is_none());
+        assert!(tracker.observe("dave", cashtag("GHOST"), 5100).is_some());
+    }
+}
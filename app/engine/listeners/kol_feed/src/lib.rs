@@ -0,0 +1,45 @@
+use publisher::types::{KolActivity, PulsgramEvent};
+use publisher::{EventBus, handle_recv_error};
+use std::sync::Arc;
+use twitter::entities::extract_entities;
+use twitter::regex::{MessageType, parse_message_type};
+
+/// Classifies every message posted in the KOL relay chat and republishes it as `KolActivity`,
+/// with the tokens, addresses and links it mentions.
+/// Messages the relay format does not cover are dropped.
+pub async fn run(bus: Arc<EventBus>, relay_dialog_id: i64) {
+    println!("KOL Feed running...");
+    let mut rx = bus.subscribe();
+
+    loop {
+        match rx.recv().await {
+            Ok(PulsgramEvent::Telegram(event)) => {
+                let message = event.message;
+
+                if message.peer_id().bare_id() != relay_dialog_id {
+                    continue;
+                }
+
+                let kind = parse_message_type(message.text());
+
+                if kind == MessageType::Unknown {
+                    continue;
+                }
+
+                let entities = extract_entities(kind.body());
+
+                bus.publish(PulsgramEvent::KolActivity(KolActivity {
+                    message,
+                    kind,
+                    entities,
+                }));
+            }
+            Ok(_) => continue,
+            Err(error) => {
+                if handle_recv_error("KOL Feed RecvError", error, &bus) {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -1,16 +1,26 @@
-use publisher::types::{ErrorEvent, PulsgramEvent};
+pub mod score;
+
+use crate::score::FollowScore;
+use domain::types::kol_follow::KolFollow;
+use providers::client::PersistenceClient;
+use publisher::types::{ErrorEvent, KolActivity, PulsgramEvent};
 use publisher::{EventBus, handle_recv_error};
 use shared::{postprocess_html, remove_emojis};
 use std::sync::Arc;
 use telegram::media::extract_photo_url_from_raw;
+use telegram::outbox::Outbox;
+use telegram_types::Message;
 use telegram_types::PeerRef;
-use telegram_types::{Client, Message};
+use twitter::profile::profile_counts;
+use twitter::regex::MessageType;
 
+/// Forwards follows from the KOL relay. With the persistence service, each follow is also stored
+/// in the follow graph and the alert is scored against it.
 pub async fn run(
     bus: Arc<EventBus>,
-    target_dialog_id: i64,
     destination: PeerRef,
-    dispatcher: Arc<Client>,
+    outbox: Outbox,
+    persistence: Option<PersistenceClient>,
 ) {
     println!("KOL Follows running...");
     let mut rx = bus.subscribe();
@@ -18,16 +28,30 @@ pub async fn run(
     loop {
         match rx.recv().await {
             Ok(event) => match event {
-                publisher::types::PulsgramEvent::Telegram(event) => {
-                    let message = event.message;
-
-                    let peer_id = message.peer_id().bare_id();
-
-                    if peer_id != target_dialog_id {
-                        continue;
-                    }
-
-                    handle_follow(message, &dispatcher, destination, &bus).await;
+                PulsgramEvent::KolActivity(KolActivity {
+                    message,
+                    kind:
+                        MessageType::Follow {
+                            follower,
+                            followee,
+                            profile_info,
+                            ..
+                        },
+                    ..
+                }) => {
+                    let follow = KolFollow {
+                        follower,
+                        followee,
+                        followed_at_ms: message.date().timestamp_millis(),
+                        profile_info,
+                    };
+
+                    let score = match &persistence {
+                        Some(persistence) => score_follow(persistence, &follow, &bus).await,
+                        None => None,
+                    };
+
+                    handle_follow(message, score, &outbox, destination, &bus).await;
                 }
                 _ => continue,
             },
@@ -40,17 +64,43 @@ pub async fn run(
     }
 }
 
+async fn score_follow(
+    persistence: &PersistenceClient,
+    follow: &KolFollow,
+    bus: &EventBus,
+) -> Option<FollowScore> {
+    match persistence.record_follow(follow).await {
+        Ok(target) => Some(FollowScore::new(
+            &follow.follower,
+            &target,
+            profile_counts(&follow.profile_info),
+        )),
+        Err(error) => {
+            bus.publish(PulsgramEvent::Error(ErrorEvent {
+                message_text: format!(
+                    "Failed to record {} following {}: {}",
+                    follow.follower, follow.followee, error
+                ),
+                source: "KOL Follows::RecordFollow",
+            }));
+
+            None
+        }
+    }
+}
+
 pub async fn handle_follow(
     message: Box<Message>,
-    dispatcher: &Client,
+    score: Option<FollowScore>,
+    outbox: &Outbox,
     destination: PeerRef,
-    bus: &EventBus,
+    bus: &Arc<EventBus>,
 ) {
-    if !simple_is_followed_check(message.text()) {
-        return;
-    }
+    let mut html_content = postprocess_html(&remove_emojis(&message.html_text()));
 
-    let html_content = postprocess_html(&remove_emojis(&message.html_text()));
+    if let Some(score) = score {
+        html_content.insert_str(0, &score.header_html());
+    }
 
     let final_html = if let Some(photo_url) = extract_photo_url_from_raw(&message.raw) {
         format!("<a href=\"{}\">&#8205;</a>{}", photo_url, html_content)
@@ -69,17 +119,14 @@ pub async fn handle_follow(
             .link_preview(true)
             .invert_media(true);
 
-        if let Err(error) = dispatcher.send_message(destination, input_message).await {
-            let msg = format!(
-                "KOL Follows failed (test mode).\nDestination: {}\nError: {}",
-                destination.id, error
-            );
-
-            bus.publish(PulsgramEvent::Error(ErrorEvent {
-                message_text: msg,
-                source: "KOL Follows::SendMessage(Test)",
-            }));
-        }
+        outbox.send(destination, input_message).report_failure(
+            Arc::clone(bus),
+            "KOL Follows::SendMessage(Test)",
+            format!(
+                "KOL Follows failed (test mode).\nDestination: {}",
+                destination.id
+            ),
+        );
 
         return;
     }
@@ -90,22 +137,10 @@ pub async fn handle_follow(
             .link_preview(true)
             .invert_media(true);
 
-        if let Err(error) = dispatcher.send_message(destination, input_message).await {
-            let msg = format!(
-                "KOL Follows failed.\nDestination: {}\nError: {}",
-                destination.id, error
-            );
-
-            bus.publish(PulsgramEvent::Error(ErrorEvent {
-                message_text: msg,
-                source: "KOL Follows::SendMessage",
-            }));
-        }
+        outbox.send(destination, input_message).report_failure(
+            Arc::clone(bus),
+            "KOL Follows::SendMessage",
+            format!("KOL Follows failed.\nDestination: {}", destination.id),
+        );
     }
 }
-
-fn simple_is_followed_check(message_text: &str) -> bool {
-    let first_line = message_text.lines().next().unwrap_or("");
-
-    first_line.contains("followed")
-}
@@ -0,0 +1,114 @@
+use domain::types::kol_follow::FollowTarget;
+use twitter::profile::ProfileCounts;
+
+/// Points per tracked KOL that already followed the account, up to `MAX_KOL_POINTS`.
+const KOL_POINTS: f64 = 15.0;
+const MAX_KOL_POINTS: f64 = 60.0;
+/// Awarded in full to an account without followers, down to none at 100k.
+const MAX_NEWNESS_POINTS: f64 = 40.0;
+const ESTABLISHED_FOLLOWERS_LOG10: f64 = 5.0;
+
+/// How much a follow is worth looking at, from 0 to 100.
+///
+/// The relay does not show when an account was created, so its follower count stands in for how
+/// new it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowScore {
+    /// Tracked KOLs that followed the account before this one.
+    pub prior_kols: usize,
+    pub followers: Option<u64>,
+    pub score: u32,
+}
+
+impl FollowScore {
+    pub fn new(follower: &str, target: &FollowTarget, profile: Option<ProfileCounts>) -> Self {
+        let prior_kols = target
+            .followers
+            .iter()
+            .filter(|kol| !kol.follower.eq_ignore_ascii_case(follower))
+            .count();
+
+        let followers = profile.map(|profile| profile.followers);
+
+        let kol_points = (prior_kols as f64 * KOL_POINTS).min(MAX_KOL_POINTS);
+
+        let newness_points = followers.map_or(0.0, |followers| {
+            let established = (followers as f64 + 1.0).log10() / ESTABLISHED_FOLLOWERS_LOG10;
+
+            MAX_NEWNESS_POINTS * (1.0 - established).clamp(0.0, 1.0)
+        });
+
+        Self {
+            prior_kols,
+            followers,
+            score: (kol_points + newness_points).round() as u32,
+        }
+    }
+
+    pub fn header_html(&self) -> String {
+        let followers = match self.followers {
+            Some(followers) => format!(", {} followers", followers),
+            None => String::new(),
+        };
+
+        format!(
+            "<b>Score {}/100</b> ({} tracked KOLs already follow{})\n\n",
+            self.score, self.prior_kols, followers
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::kol_follow::KolFollower;
+
+    use super::*;
+
+    fn target(followers: &[&str]) -> FollowTarget {
+        FollowTarget {
+            followee: "yashaxbt".into(),
+            first_followed_at_ms: Some(0),
+            followers: followers
+                .iter()
+                .map(|follower| KolFollower {
+                    follower: follower.to_string(),
+                    followed_at_ms: 0,
+                })
+                .collect(),
+        }
+    }
+
+    fn profile(followers: u64) -> Option<ProfileCounts> {
+        Some(ProfileCounts {
+            following: 10,
+            followers,
+        })
+    }
+
+    #[test]
+    fn test_first_follow_of_an_established_account_scores_zero() {
+        let score = FollowScore::new("toly", &target(&["toly"]), profile(100_000));
+
+        assert_eq!(score.prior_kols, 0);
+        assert_eq!(score.score, 0);
+    }
+
+    #[test]
+    fn test_prior_kols_and_a_fresh_account_score_high() {
+        let score = FollowScore::new(
+            "Toly",
+            &target(&["pmarca", "blknoiz06", "toly"]),
+            profile(0),
+        );
+
+        assert_eq!(score.prior_kols, 2);
+        assert_eq!(score.score, 70);
+    }
+
+    #[test]
+    fn test_kol_points_are_capped() {
+        let score = FollowScore::new("z", &target(&["a", "b", "c", "d", "e", "f"]), None);
+
+        assert_eq!(score.score, 60);
+    }
+}
@@ -1,4 +1,12 @@
+pub mod user_stream;
+
+use std::sync::Arc;
+
+use domain::types::market::PriceTick;
+use domain::types::symbol::Symbol;
 use futures_util::StreamExt;
+use publisher::EventBus;
+use publisher::types::PulsgramEvent;
 use serde::Deserialize;
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::protocol::Message;
@@ -17,7 +25,7 @@ struct TickerData {
     last_price: String,
 }
 
-pub async fn run(symbols: Vec<domain::types::symbol::Symbol>) {
+pub async fn run(bus: Arc<EventBus>, symbols: Vec<Symbol>) {
     let streams = symbols
         .iter()
         .map(|s| format!("{}@ticker", s.to_string().to_lowercase()))
@@ -50,10 +58,16 @@ pub async fn run(symbols: Vec<domain::types::symbol::Symbol>) {
         match msg {
             Message::Text(txt) => match serde_json::from_str::<StreamWrapper>(&txt) {
                 Ok(parsed) => {
-                    if let Ok(symbol) = parsed.data.symbol.parse::<domain::types::symbol::Symbol>()
-                    {
-                        println!("{:?} => {}", symbol, parsed.data.last_price);
-                    }
+                    let Ok(symbol) = parsed.data.symbol.parse::<Symbol>() else {
+                        continue;
+                    };
+
+                    let Ok(price) = parsed.data.last_price.parse::<f64>() else {
+                        eprintln!("Invalid ticker price: {}", parsed.data.last_price);
+                        continue;
+                    };
+
+                    bus.publish(PulsgramEvent::PriceUpdate(PriceTick { symbol, price }));
                 }
                 Err(e) => eprintln!("JSON parse error: {}", e),
             },
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use domain::types::market::OrderUpdate;
+use domain::types::order_side::OrderSide;
+use domain::types::symbol::Symbol;
+use futures_util::StreamExt;
+use publisher::EventBus;
+use publisher::types::{ErrorEvent, PulsgramEvent};
+use serde::Deserialize;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+//https://developers.binance.com/docs/derivatives/usds-margined-futures/user-data-streams/Event-Order-Update
+// Only ORDER_TRADE_UPDATE is handled; every other user data event is skipped.
+#[derive(Debug, Deserialize)]
+struct UserEvent {
+    #[serde(rename = "e")]
+    event_type: String,
+
+    #[serde(rename = "E")]
+    event_time: i64,
+
+    #[serde(rename = "o")]
+    order: Option<OrderData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderData {
+    #[serde(rename = "s")]
+    symbol: String,
+
+    #[serde(rename = "i")]
+    order_id: i64,
+
+    #[serde(rename = "S")]
+    side: OrderSide,
+
+    #[serde(rename = "x")]
+    execution_type: String,
+
+    #[serde(rename = "X")]
+    status: String,
+
+    #[serde(rename = "l")]
+    last_filled_quantity: String,
+
+    #[serde(rename = "L")]
+    last_filled_price: String,
+
+    #[serde(rename = "n", default)]
+    commission: Option<String>,
+
+    #[serde(rename = "N", default)]
+    commission_asset: Option<String>,
+
+    #[serde(rename = "rp")]
+    realized_profit: String,
+
+    #[serde(rename = "R")]
+    reduce_only: bool,
+}
+
+/// Streams order updates for the account owning `listen_key`.
+/// `ws_base_url` is e.g. `wss://fstream.binance.com`. Keepalive is handled by listen_key_keepalive.
+pub async fn run(bus: Arc<EventBus>, ws_base_url: &'static str, listen_key: String) {
+    let url = format!("{}/ws/{}", ws_base_url, listen_key);
+
+    println!("User Stream connecting...");
+
+    let (ws_stream, _) = match connect_async(&url).await {
+        Ok(v) => v,
+        Err(e) => {
+            bus.publish(PulsgramEvent::Error(ErrorEvent {
+                source: "UserStream::Connect",
+                message_text: format!("User stream connect error: {}", e),
+            }));
+            return;
+        }
+    };
+
+    let (_write, mut read) = ws_stream.split();
+
+    while let Some(msg) = read.next().await {
+        let msg = match msg {
+            Ok(m) => m,
+            Err(e) => {
+                eprintln!("User stream read error: {}", e);
+                break;
+            }
+        };
+
+        match msg {
+            Message::Text(txt) => match serde_json::from_str::<UserEvent>(&txt) {
+                Ok(event) => {
+                    if let Some(update) = order_update(event) {
+                        bus.publish(PulsgramEvent::OrderUpdate(update));
+                    }
+                }
+                Err(e) => eprintln!("User stream JSON parse error: {}", e),
+            },
+            Message::Close(frame) => {
+                println!("User stream closed: {:?}", frame);
+                break;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn order_update(event: UserEvent) -> Option<OrderUpdate> {
+    if event.event_type != "ORDER_TRADE_UPDATE" {
+        return None;
+    }
+
+    let order = event.order?;
+
+    Some(OrderUpdate {
+        symbol: order.symbol.parse::<Symbol>().ok()?,
+        order_id: order.order_id,
+        side: order.side,
+        execution_type: order.execution_type,
+        status: order.status,
+        last_filled_quantity: order.last_filled_quantity.parse().ok()?,
+        last_filled_price: order.last_filled_price.parse().ok()?,
+        commission: order
+            .commission
+            .and_then(|commission| commission.parse().ok())
+            .unwrap_or(0.0),
+        commission_asset: order.commission_asset.unwrap_or_default(),
+        realized_profit: order.realized_profit.parse().ok()?,
+        reduce_only: order.reduce_only,
+        event_time_ms: event.event_time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_trade_update_is_parsed() {
+        let raw = r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{"s":"BTCUSDT","c":"TEST","S":"SELL","o":"MARKET","f":"GTC","q":"0.001","p":"0","ap":"9638","sp":"0","x":"TRADE","X":"FILLED","i":8886774,"l":"0.001","z":"0.001","L":"9638","N":"USDT","n":"0.0038","T":1568879465650,"t":1,"b":"0","a":"0","m":false,"R":true,"wt":"CONTRACT_PRICE","ot":"MARKET","ps":"BOTH","cp":false,"rp":"1.25","pP":false,"si":0,"ss":0,"V":"NONE","pm":"NONE","gtd":0}}"#;
+
+        let event: UserEvent = serde_json::from_str(raw).unwrap();
+        let update = order_update(event).unwrap();
+
+        assert_eq!(update.symbol, Symbol::BTC);
+        assert_eq!(update.order_id, 8886774);
+        assert_eq!(update.side, OrderSide::Sell);
+        assert_eq!(update.execution_type, "TRADE");
+        assert_eq!(update.commission, 0.0038);
+        assert_eq!(update.realized_profit, 1.25);
+        assert!(update.reduce_only);
+    }
+
+    #[test]
+    fn test_other_user_events_are_skipped() {
+        let raw = r#"{"e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,"a":{}}"#;
+
+        let event: UserEvent = serde_json::from_str(raw).unwrap();
+
+        assert!(order_update(event).is_none());
+    }
+}
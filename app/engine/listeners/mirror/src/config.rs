@@ -0,0 +1,51 @@
+use binance::network::Network;
+use serde::Deserialize;
+
+/// A sub-account copying the primary account's trades. Credentials are read from the environment.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FollowerConfig {
+    pub name: String,
+    /// Environment variables holding the API key and secret.
+    pub api_key_env: String,
+    pub api_secret_env: String,
+    #[serde(default = "default_network")]
+    pub network: Network,
+    /// Follower size relative to the master trade, e.g. 0.5 copies half the quantity.
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_network() -> Network {
+    Network::Testnet
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MirrorConfig {
+    pub followers: Vec<FollowerConfig>,
+    /// Difference between a follower's position and the mirrored size, in percent of the
+    /// mirrored size, above which the follower is reported as diverged.
+    pub divergence_tolerance_percent: f64,
+    /// Read as 1 when set to 0.
+    pub divergence_check_secs: u64,
+}
+
+impl Default for MirrorConfig {
+    fn default() -> Self {
+        Self {
+            followers: Vec::new(),
+            divergence_tolerance_percent: 5.0,
+            divergence_check_secs: 60,
+        }
+    }
+}
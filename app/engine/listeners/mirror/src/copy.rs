@@ -0,0 +1,97 @@
+use domain::types::{
+    lifecycle::{EPSILON, is_nothing_left},
+    order_side::OrderSide,
+    symbol::Symbol,
+};
+use uuid::Uuid;
+
+/// A master trade as copied onto one follower.
+#[derive(Debug, Clone)]
+pub struct MirroredTrade {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    /// Quantity the follower was filled for at entry.
+    pub quantity: f64,
+    /// Exchange-side stop loss protecting the follower's position.
+    pub stop_order_id: Option<i64>,
+    remaining_fraction: f64,
+}
+
+impl MirroredTrade {
+    pub fn new(intent_id: Uuid, symbol: Symbol, side: OrderSide, quantity: f64) -> Self {
+        Self {
+            intent_id,
+            symbol,
+            side,
+            quantity,
+            stop_order_id: None,
+            remaining_fraction: 1.0,
+        }
+    }
+
+    /// Records a partial close of `closed_fraction` of the original size, as reported by the
+    /// master, and returns the share of the *remaining* position to close, in percent.
+    pub fn take(&mut self, closed_fraction: f64) -> f64 {
+        if is_nothing_left(self.remaining_fraction) {
+            return 0.0;
+        }
+
+        let closed = closed_fraction.clamp(0.0, self.remaining_fraction);
+        let percent = closed / self.remaining_fraction * 100.0;
+
+        self.remaining_fraction -= closed;
+        percent
+    }
+
+    /// Signed position the follower should hold for this trade: positive long, negative short.
+    pub fn expected_position(&self) -> f64 {
+        let size = self.quantity * self.remaining_fraction;
+
+        if self.side.is_long() { size } else { -size }
+    }
+}
+
+/// Difference between the follower's `actual` position and the `expected` one, when it is larger
+/// than `tolerance_percent` of the expected size. A leftover position on a closed trade always
+/// counts as a divergence.
+pub fn divergence(expected: f64, actual: f64, tolerance_percent: f64) -> Option<f64> {
+    let difference = actual - expected;
+    let tolerance = expected.abs() * tolerance_percent / 100.0;
+
+    (difference.abs() > tolerance.max(EPSILON)).then_some(difference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partials_are_converted_to_share_of_remaining() {
+        let mut trade = MirroredTrade::new(Uuid::new_v4(), Symbol::BTC, OrderSide::Buy, 2.0);
+
+        assert!((trade.take(0.5) - 50.0).abs() < 1e-9);
+        // 0.3 of the original is 60% of the remaining half.
+        assert!((trade.take(0.3) - 60.0).abs() < 1e-9);
+        assert!((trade.expected_position() - 0.4).abs() < 1e-9);
+
+        assert!((trade.take(0.2) - 100.0).abs() < 1e-9);
+        assert_eq!(trade.take(0.1), 0.0);
+    }
+
+    #[test]
+    fn test_short_expected_position_is_negative() {
+        let trade = MirroredTrade::new(Uuid::new_v4(), Symbol::ETH, OrderSide::Sell, 3.0);
+
+        assert_eq!(trade.expected_position(), -3.0);
+    }
+
+    #[test]
+    fn test_divergence_outside_tolerance_only() {
+        assert_eq!(divergence(1.0, 1.04, 5.0), None);
+        assert!(divergence(1.0, 0.5, 5.0).is_some());
+        assert!(divergence(-1.0, 1.0, 5.0).is_some());
+        assert_eq!(divergence(0.0, 0.0, 5.0), None);
+        assert_eq!(divergence(0.0, 0.01, 5.0), Some(0.01));
+    }
+}
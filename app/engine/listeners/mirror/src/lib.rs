@@ -0,0 +1,366 @@
+pub mod config;
+mod copy;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use binance::client::BinanceClient;
+use domain::types::execution::{AccountExecution, AccountOutcome};
+use domain::types::symbol::Symbol;
+use domain::types::trade::{TradeOpened, TradeUpdate, TradeUpdateKind};
+use publisher::types::{ErrorEvent, PulsgramEvent};
+use publisher::{EventBus, handle_recv_error};
+use uuid::Uuid;
+
+use crate::config::{FollowerConfig, MirrorConfig};
+use crate::copy::{MirroredTrade, divergence};
+
+pub struct Follower {
+    pub config: FollowerConfig,
+    pub client: Arc<BinanceClient>,
+}
+
+struct MirrorState {
+    // Keyed by follower index and master intent.
+    trades: HashMap<(usize, Uuid), MirroredTrade>,
+    // Follower and symbol pairs already reported, so a divergence is reported once.
+    diverged: HashSet<(usize, Symbol)>,
+}
+
+/// Copies the trades managed on the primary account onto follower sub-accounts.
+///
+/// Entries follow `TradeOpened`; partial closes, stop moves and exits follow the trade
+/// manager's `TradeUpdate`s. Each follower also holds an exchange-side stop loss, so it stays
+/// protected if Pulsgram goes down.
+pub async fn run(bus: Arc<EventBus>, followers: Vec<Follower>, config: MirrorConfig) {
+    println!("Mirror running...");
+    let mut rx = bus.subscribe();
+
+    let mut state = MirrorState {
+        trades: HashMap::new(),
+        diverged: HashSet::new(),
+    };
+
+    // A zero period would panic.
+    let mut check = tokio::time::interval(Duration::from_secs(config.divergence_check_secs.max(1)));
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Ok(event) => match event {
+                    PulsgramEvent::TradeOpened(opened) => {
+                        for (index, follower) in followers.iter().enumerate() {
+                            if !follower.config.enabled {
+                                continue;
+                            }
+
+                            if let Some(trade) = open(&bus, follower, &opened).await {
+                                state.trades.insert((index, opened.intent_id), trade);
+                            }
+                        }
+                    }
+
+                    PulsgramEvent::TradeUpdate(update) => {
+                        for (index, follower) in followers.iter().enumerate() {
+                            let key = (index, update.intent_id);
+
+                            let Some(trade) = state.trades.get_mut(&key) else {
+                                continue;
+                            };
+
+                            let is_closed = follow(&bus, follower, trade, &update).await;
+
+                            if is_closed {
+                                state.trades.remove(&key);
+                            }
+                        }
+                    }
+
+                    _ => {}
+                },
+
+                Err(error) => {
+                    if handle_recv_error("Mirror RecvError", error, &bus) {
+                        break;
+                    }
+                }
+            },
+
+            _ = check.tick(), if !state.trades.is_empty() => {
+                check_divergence(&bus, &followers, &mut state, &config).await;
+            }
+        }
+    }
+}
+
+async fn open(bus: &EventBus, follower: &Follower, opened: &TradeOpened) -> Option<MirroredTrade> {
+    let client = &follower.client;
+    let quantity = opened.quantity * follower.config.scale;
+
+    let response = match client
+        .place_market_order(opened.symbol, &opened.side, quantity)
+        .await
+    {
+        Ok(response) => response,
+        Err(error) => {
+            publish_outcome(
+                bus,
+                follower,
+                opened,
+                AccountOutcome::Failed(error.to_string()),
+            );
+            report(
+                bus,
+                "Mirror::Open",
+                follower,
+                opened.intent_id,
+                format!("Failed to copy entry on {}: {}", opened.symbol, error),
+            );
+            return None;
+        }
+    };
+
+    let filled = response.executed_qty.parse().unwrap_or(quantity);
+    let price = response.avg_price.parse().unwrap_or(opened.entry_price);
+
+    publish_outcome(
+        bus,
+        follower,
+        opened,
+        AccountOutcome::Filled {
+            price,
+            quantity: filled,
+        },
+    );
+
+    let mut trade = MirroredTrade::new(opened.intent_id, opened.symbol, opened.side, filled);
+    place_stop(bus, follower, &mut trade, opened.stop_loss).await;
+
+    Some(trade)
+}
+
+/// Applies a master update to the follower. Returns true once the mirrored trade is closed.
+async fn follow(
+    bus: &EventBus,
+    follower: &Follower,
+    trade: &mut MirroredTrade,
+    update: &TradeUpdate,
+) -> bool {
+    let client = &follower.client;
+
+    match update.kind {
+        TradeUpdateKind::TargetHit {
+            closed_fraction, ..
+        }
+        | TradeUpdateKind::PartialClose {
+            closed_fraction, ..
+        } => {
+            let percent = trade.take(closed_fraction);
+
+            // Levels configured with a zero fraction only move the stop.
+            if percent > 0.0
+                && let Err(error) = client.close_percentage(trade.symbol, percent).await
+            {
+                report(
+                    bus,
+                    "Mirror::PartialClose",
+                    follower,
+                    trade.intent_id,
+                    format!("Failed to copy partial close ({:.1}%): {}", percent, error),
+                );
+            }
+
+            false
+        }
+
+        // The new stop goes on first, so the follower is never left without one. If it cannot
+        // be placed, the old stop stays.
+        TradeUpdateKind::StopMoved { to, .. } => {
+            let previous = trade.stop_order_id;
+
+            if place_stop(bus, follower, trade, to).await
+                && let Some(order_id) = previous
+            {
+                cancel_stop_order(bus, follower, trade, order_id).await;
+            }
+
+            false
+        }
+
+        TradeUpdateKind::Closed { reason, .. } => {
+            if let Err(error) = client.close_full_position(trade.symbol).await {
+                // Kept, so the divergence check keeps flagging the leftover position.
+                report(
+                    bus,
+                    "Mirror::Close",
+                    follower,
+                    trade.intent_id,
+                    format!("Failed to copy close ({:?}): {}", reason, error),
+                );
+                trade.take(1.0);
+                return false;
+            }
+
+            cancel_stop(bus, follower, trade).await;
+            true
+        }
+    }
+}
+
+/// Reduce-only and sized to what is left of the position, so it can rest next to the stop it
+/// replaces. Returns false when it was not placed.
+async fn place_stop(
+    bus: &EventBus,
+    follower: &Follower,
+    trade: &mut MirroredTrade,
+    stop: f64,
+) -> bool {
+    match follower
+        .client
+        .place_reduce_only_stop_order(
+            trade.symbol,
+            &trade.side.opposite(),
+            trade.expected_position().abs(),
+            stop,
+        )
+        .await
+    {
+        Ok(order) => {
+            trade.stop_order_id = Some(order.order_id);
+            true
+        }
+        Err(error) => {
+            report(
+                bus,
+                "Mirror::StopLoss",
+                follower,
+                trade.intent_id,
+                format!("Failed to place stop loss at {}: {}", stop, error),
+            );
+            false
+        }
+    }
+}
+
+async fn cancel_stop(bus: &EventBus, follower: &Follower, trade: &mut MirroredTrade) {
+    if let Some(order_id) = trade.stop_order_id.take() {
+        cancel_stop_order(bus, follower, trade, order_id).await;
+    }
+}
+
+async fn cancel_stop_order(
+    bus: &EventBus,
+    follower: &Follower,
+    trade: &MirroredTrade,
+    order_id: i64,
+) {
+    // Expected to fail when the stop itself closed the position.
+    if let Err(error) = follower.client.cancel_order(trade.symbol, order_id).await {
+        report(
+            bus,
+            "Mirror::StopLoss",
+            follower,
+            trade.intent_id,
+            format!("Failed to cancel stop loss order {}: {}", order_id, error),
+        );
+    }
+}
+
+/// Compares each follower's positions with what the mirrored trades add up to.
+async fn check_divergence(
+    bus: &EventBus,
+    followers: &[Follower],
+    state: &mut MirrorState,
+    config: &MirrorConfig,
+) {
+    for (index, follower) in followers.iter().enumerate() {
+        let mut expected: HashMap<Symbol, f64> = HashMap::new();
+
+        for (_, trade) in state.trades.iter().filter(|((i, _), _)| *i == index) {
+            *expected.entry(trade.symbol).or_default() += trade.expected_position();
+        }
+
+        if expected.is_empty() {
+            continue;
+        }
+
+        let positions = match follower.client.get_position_risk(None).await {
+            Ok(positions) => positions,
+            Err(error) => {
+                eprintln!(
+                    "[MIRROR] follower={} position check failed: {}",
+                    follower.config.name, error
+                );
+                continue;
+            }
+        };
+
+        for (symbol, expected) in expected {
+            let actual: f64 = positions
+                .iter()
+                .filter(|position| position.symbol == symbol.to_string())
+                .filter_map(|position| position.position_amt.parse::<f64>().ok())
+                .sum();
+
+            let key = (index, symbol);
+
+            let Some(difference) =
+                divergence(expected, actual, config.divergence_tolerance_percent)
+            else {
+                state.diverged.remove(&key);
+                continue;
+            };
+
+            if state.diverged.insert(key) {
+                bus.publish(PulsgramEvent::Error(ErrorEvent {
+                    source: "Mirror::Divergence",
+                    message_text: format!(
+                        "Follower {} diverged from master on {}\nExpected: {}\nActual: {}\nDifference: {}",
+                        follower.config.name, symbol, expected, actual, difference
+                    ),
+                }));
+            }
+        }
+    }
+
+    // Trades whose close failed are dropped once the position is flat again.
+    state.trades.retain(|(index, _), trade| {
+        trade.expected_position() != 0.0 || state.diverged.contains(&(*index, trade.symbol))
+    });
+}
+
+fn publish_outcome(
+    bus: &EventBus,
+    follower: &Follower,
+    opened: &TradeOpened,
+    outcome: AccountOutcome,
+) {
+    println!(
+        "[MIRROR] follower={} id={} symbol={} {:?}",
+        follower.config.name, opened.intent_id, opened.symbol, outcome
+    );
+
+    bus.publish(PulsgramEvent::AccountExecution(AccountExecution {
+        account: follower.config.name.clone(),
+        intent_id: opened.intent_id,
+        symbol: opened.symbol,
+        outcome,
+    }));
+}
+
+fn report(
+    bus: &EventBus,
+    source: &'static str,
+    follower: &Follower,
+    intent_id: Uuid,
+    message: String,
+) {
+    bus.publish(PulsgramEvent::Error(ErrorEvent {
+        source,
+        message_text: format!(
+            "Follower: {}\nTrade ID: {}\n{}",
+            follower.config.name, intent_id, message
+        ),
+    }));
+}
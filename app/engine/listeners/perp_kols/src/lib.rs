@@ -1,13 +1,14 @@
-use publisher::types::{ErrorEvent, PulsgramEvent};
+use publisher::types::PulsgramEvent;
 use publisher::{EventBus, handle_recv_error};
 use shared::{postprocess_html, remove_emojis};
 use std::sync::Arc;
 use telegram::media::extract_photo_url_from_raw;
-use telegram_types::{Client, PeerRef};
+use telegram::outbox::Outbox;
+use telegram_types::PeerRef;
 
 pub async fn run(
     bus: Arc<EventBus>,
-    dispatcher: Arc<Client>,
+    outbox: Outbox,
     from_target_id: i64,
     perp_kols_peer: PeerRef,
     target_kols: Vec<String>,
@@ -53,18 +54,11 @@ pub async fn run(
                         .link_preview(true)
                         .invert_media(true);
 
-                    if let Err(error) = dispatcher.send_message(perp_kols_peer, input_message).await
-                    {
-                        let msg = format!(
-                            "Perp KOL send failed.\nTo Peer: {}\nError: {}",
-                            perp_kols_peer.id, error
-                        );
-
-                        bus.publish(PulsgramEvent::Error(ErrorEvent {
-                            message_text: msg,
-                            source: "PerpKols::SendMessage",
-                        }));
-                    }
+                    outbox.send(perp_kols_peer, input_message).report_failure(
+                        Arc::clone(&bus),
+                        "PerpKols::SendMessage",
+                        format!("Perp KOL send failed.\nTo Peer: {}", perp_kols_peer.id),
+                    );
                 }
             }
 
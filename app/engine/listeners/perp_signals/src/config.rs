@@ -0,0 +1,78 @@
+use domain::types::trade_intent::SanityLimits;
+use serde::Deserialize;
+
+/// A chat signals are read from, with the parser for the layout it posts in.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignalSource {
+    pub chat_id: i64,
+    /// A built-in parser (`labeled`, `zone`) or the name of a template.
+    pub parser: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PerpSignalsConfig {
+    pub sources: Vec<SignalSource>,
+    pub templates: Vec<SignalTemplate>,
+    /// Checks a parsed signal must pass before it becomes a trade intent.
+    pub validation: SanityLimits,
+}
+
+/// A provider's layout, declared as one pattern per field. Patterns are regexes in which
+/// `{number}` stands for a price written in the template's number format. Other groups in a
+/// pattern must be non-capturing, `(?:...)`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignalTemplate {
+    pub name: String,
+    /// The first group is the coin, e.g. `#([A-Z0-9]+)/USDT`.
+    pub symbol: String,
+    #[serde(default)]
+    pub direction: DirectionKeywords,
+    /// One `{number}` for a single price, or two for a range entered at its middle.
+    pub entry: String,
+    /// One `{number}`; every match is a target, in order.
+    pub targets: String,
+    /// One `{number}`.
+    pub stop: String,
+    /// The first group is the timeframe, e.g. `(\d+[mhdw])`. 1h when missing or not found.
+    pub timeframe: Option<String>,
+    /// One `{number}`.
+    pub leverage: Option<String>,
+    #[serde(default)]
+    pub numbers: NumberFormat,
+}
+
+/// Words or emojis giving the side, matched anywhere in the message and ignoring case.
+/// The earliest one wins. Without any, the side follows from where the stop is.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DirectionKeywords {
+    pub long: Vec<String>,
+    pub short: Vec<String>,
+}
+
+impl Default for DirectionKeywords {
+    fn default() -> Self {
+        Self {
+            long: vec!["LONG".into(), "BUY".into()],
+            short: vec!["SHORT".into(), "SELL".into()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub decimal: char,
+    /// Digit group separator, e.g. the comma in `62,500`.
+    pub thousands: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal: '.',
+            thousands: Some(','),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Invalid `[perp_signals]` settings, found when the parser registry is built at startup.
+#[derive(Debug)]
+pub enum ParserConfigError {
+    InvalidPattern {
+        template: String,
+        field: &'static str,
+        error: regex::Error,
+    },
+    /// The pattern has the wrong number of `{number}` placeholders or capture groups.
+    WrongCaptures {
+        template: String,
+        field: &'static str,
+        expected: &'static str,
+    },
+    DuplicateName(String),
+    UnknownParser {
+        chat_id: i64,
+        parser: String,
+    },
+}
+
+impl Display for ParserConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParserConfigError::InvalidPattern {
+                template,
+                field,
+                error,
+            } => write!(
+                f,
+                "Template {}: invalid {} pattern: {}",
+                template, field, error
+            ),
+
+            ParserConfigError::WrongCaptures {
+                template,
+                field,
+                expected,
+            } => write!(
+                f,
+                "Template {}: {} pattern needs {}",
+                template, field, expected
+            ),
+
+            ParserConfigError::DuplicateName(name) => {
+                write!(f, "Parser name {} is used more than once", name)
+            }
+
+            ParserConfigError::UnknownParser { chat_id, parser } => {
+                write!(f, "Source {} uses unknown parser {}", chat_id, parser)
+            }
+        }
+    }
+}
+
+impl Error for ParserConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParserConfigError::InvalidPattern { error, .. } => Some(error),
+            ParserConfigError::WrongCaptures { .. } => None,
+            ParserConfigError::DuplicateName(_) => None,
+            ParserConfigError::UnknownParser { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::parsers::{SignalParser, clean};
+
+struct FieldRegexes {
+    entry: Regex,
+    targets: Regex,
+    stop_loss: Regex,
+}
+
+fn regexes() -> &'static FieldRegexes {
+    static REGEXES: OnceLock<FieldRegexes> = OnceLock::new();
+
+    // A field is present when its label is followed by a number on the same line.
+    REGEXES.get_or_init(|| FieldRegexes {
+        entry: Regex::new(
+            r"(?i)\b(?:entry|entries|enter|buy|sell|zone|range)\b[^\n0-9]{0,20}[0-9]",
+        )
+        .expect("Invalid regex: entry"),
+
+        targets: Regex::new(r"(?i)\b(?:tps?\s*[0-9]*|targets?)\b[^\n0-9]{0,20}[0-9]")
+            .expect("Invalid regex: targets"),
+
+        stop_loss: Regex::new(r"(?i)\b(?:sl|stop(?:[\s-]*loss)?)\b[^\n0-9]{0,20}[0-9]")
+            .expect("Invalid regex: stop_loss"),
+    })
+}
+
+/// Why `parser` did not read a message that looks like a signal: the first missing field, or
+/// a layout it does not know when every field is there.
+pub fn missing_field(text: &str, parser: &dyn SignalParser) -> String {
+    let re = regexes();
+
+    let text = clean(text);
+
+    if !re.entry.is_match(&text) {
+        "Missing entry".to_string()
+    } else if !re.targets.is_match(&text) {
+        "Missing targets".to_string()
+    } else if !re.stop_loss.is_match(&text) {
+        "Missing stop loss".to_string()
+    } else {
+        format!("Layout not recognised by the {} parser", parser.name())
+    }
+}
@@ -1,83 +1,178 @@
+pub mod config;
+pub mod errors;
+mod failures;
+mod local_tests;
+pub mod parsers;
 mod regex;
+mod updates;
 
-use domain::types::trade::TradeApproved;
-use domain::types::trade_intent::TradeIntent;
+pub use crate::regex::TradingSignal;
+
+use domain::clock::now_ms;
+use domain::types::parse_failure::SignalParseFailure;
+use domain::types::signal_update::{SignalUpdate, SignalUpdateKind};
+use domain::types::symbol::Symbol;
+use domain::types::trade_intent::{SanityLimits, TradeIntent, TradeIntentError};
 use publisher::types::{ErrorEvent, PulsgramEvent};
 use publisher::{EventBus, handle_recv_error};
+use std::collections::HashMap;
 use std::sync::Arc;
-use telegram_types::{Client, InputMessage, PeerRef};
+use telegram::outbox::Outbox;
+use telegram_types::{InputMessage, PeerRef};
 
-use crate::regex::{format_signal, parse_trading_signal, remove_emojis};
+use crate::failures::missing_field;
+use crate::parsers::{ParserRegistry, SignalParser, looks_like_signal};
+use crate::regex::format_signal;
+use crate::updates::{
+    SignalTracker, TrackedSignal, amendment, mentioned_symbols, parse_standalone_update,
+    parse_update,
+};
 
+/// Reads signals from every chat in `parsers`, each with the parser for its layout.
+///
+/// Follow-ups on a posted signal become `SignalUpdate`s: edits of the signal message, replies
+/// to it, and messages naming its symbol ("close BTC now").
+///
+/// Messages that look like a signal but are not parsed are published as `SignalParseFailed`
+/// with the reason. Parsed signals that make no valid trade, e.g. failing the `limits` checks,
+/// are reported and dropped before any `TradeIntent` is published. A `SignalReplay` sends a
+/// failure back through the current parsers; like any signal, it is not traded once older than
+/// `max_signal_age_secs`.
 pub async fn run(
     bus: Arc<EventBus>,
-    client_dispatcher: Arc<Client>,
-    target_id: i64,
+    outbox: Outbox,
+    parsers: Arc<ParserRegistry>,
+    limits: SanityLimits,
     signals: PeerRef,
 ) {
     println!("Perp Signals running...");
     let mut rx = bus.subscribe();
 
+    let mut tracker = SignalTracker::default();
+
+    let mut prices: HashMap<Symbol, f64> = HashMap::new();
+
+    let outputs = Outputs {
+        bus: &bus,
+        outbox: &outbox,
+        signals,
+        limits: &limits,
+    };
+
     loop {
         match rx.recv().await {
-            Ok(event) => {
-                if let PulsgramEvent::Telegram(event) = event {
+            Ok(event) => match event {
+                PulsgramEvent::Telegram(event) => {
                     let message = event.message;
+                    let source_id = message.peer_id().bare_id();
+
+                    let Some(parser) = parsers.get(source_id) else {
+                        continue;
+                    };
+
+                    // A reply to a signal is a follow-up, never a new signal.
+                    if let Some(tracked) = message
+                        .reply_to_message_id()
+                        .and_then(|reply_to| tracker.get_mut(source_id, reply_to))
+                    {
+                        publish_updates(&bus, tracked, parse_update(message.text()));
+                        continue;
+                    }
+
+                    let posted = Posted {
+                        source_id,
+                        message_id: message.id(),
+                        text: message.text(),
+                        posted_at_ms: message.date().timestamp_millis(),
+                    };
+
+                    if let Some(signal) = parser.parse(posted.text) {
+                        if is_stale(&posted, &limits) {
+                            continue;
+                        }
+
+                        let mark_price = prices.get(&signal.symbol).copied();
+
+                        take_signal(&outputs, &mut tracker, &posted, parser, signal, mark_price);
+                        continue;
+                    }
+
+                    let kinds = parse_standalone_update(posted.text);
 
-                    if message.peer_id().bare_id() != target_id {
+                    if kinds.is_empty() {
+                        if looks_like_signal(posted.text) {
+                            parse_failed(&bus, &posted, missing_field(posted.text, parser));
+                        }
                         continue;
                     }
 
-                    let message_cleaned_up = remove_emojis(message.text());
+                    for symbol in mentioned_symbols(posted.text) {
+                        if let Some(tracked) = tracker.latest(source_id, symbol) {
+                            publish_updates(&bus, tracked, kinds.clone());
+                        }
+                    }
+                }
+
+                PulsgramEvent::TelegramEdited(event) => {
+                    let message = event.message;
+                    let source_id = message.peer_id().bare_id();
 
-                    let Some(signal) = parse_trading_signal(&message_cleaned_up) else {
+                    let Some(parser) = parsers.get(source_id) else {
                         continue;
                     };
 
-                    let symbol = &signal.symbol;
+                    let Some(tracked) = tracker.get_mut(source_id, message.id()) else {
+                        continue;
+                    };
 
-                    let intent = match TradeIntent::builder(symbol)
-                        .entry(signal.entry)
-                        .side(signal.is_long.into())
-                        .stop_loss(signal.stop_loss)
-                        .targets(&signal.targets)
-                        .timeframe(&signal.timeframe)
-                        .build()
-                    {
-                        Ok(trade) => trade,
-                        Err(error) => {
-                            bus.publish(PulsgramEvent::Error(ErrorEvent {
-                                source: "Perp Signals",
-                                message_text: format!("Error creating trade intent: {}", error),
-                            }));
-                            continue;
+                    match parser.parse(message.text()) {
+                        Some(edited) => {
+                            let kinds = amendment(&tracked.signal, &edited).into_iter().collect();
+                            publish_updates(&bus, tracked, kinds);
+                            tracked.signal = edited;
                         }
+
+                        // Edited into something else, e.g. "CANCELLED" on top of the signal.
+                        None => publish_updates(&bus, tracked, parse_update(message.text())),
+                    }
+                }
+
+                PulsgramEvent::SignalReplay(replay) => {
+                    let Some(parser) = parsers.get(replay.chat_id) else {
+                        continue;
                     };
 
-                    // TODO: Move approval in risk manager.
-                    // intent.approve() -> self.into -> TradeApproved
-                    let approved: TradeApproved = intent.into();
+                    let posted = Posted::replayed(&replay);
 
-                    bus.publish(PulsgramEvent::TradeApproved(approved));
+                    match parser.parse(posted.text) {
+                        Some(signal) => {
+                            // Fixed after the fact; the entry may be long gone by now.
+                            if is_stale(&posted, &limits) {
+                                continue;
+                            }
 
-                    let formatted_signal = format_signal(&signal);
+                            let mark_price = prices.get(&signal.symbol).copied();
 
-                    let input_message = InputMessage::new().html(formatted_signal);
+                            take_signal(
+                                &outputs,
+                                &mut tracker,
+                                &posted,
+                                parser,
+                                signal,
+                                mark_price,
+                            );
+                        }
 
-                    if let Err(error) = client_dispatcher.send_message(signals, input_message).await
-                    {
-                        let msg = format!(
-                            "Perp Signals failed.\nTarget: {}\nSignals Peer: {}\nError: {}",
-                            target_id, signals.id, error
-                        );
-
-                        bus.publish(PulsgramEvent::Error(ErrorEvent {
-                            message_text: msg,
-                            source: "PerpSignals::SendMessage",
-                        }));
+                        None => parse_failed(&bus, &posted, missing_field(posted.text, parser)),
                     }
                 }
-            }
+
+                PulsgramEvent::PriceUpdate(tick) => {
+                    prices.insert(tick.symbol, tick.price);
+                }
+
+                _ => {}
+            },
 
             Err(error) => {
                 if handle_recv_error("PerpSignals RecvError", error, &bus) {
@@ -87,3 +182,178 @@ pub async fn run(
         }
     }
 }
+
+/// A message that may be a new signal, as posted or replayed.
+struct Posted<'a> {
+    source_id: i64,
+    message_id: i32,
+    text: &'a str,
+    posted_at_ms: i64,
+}
+
+impl<'a> Posted<'a> {
+    /// Keeps the original post time, so a replay is as stale as the message it fixes.
+    fn replayed(failure: &'a SignalParseFailure) -> Self {
+        Self {
+            source_id: failure.chat_id,
+            message_id: failure.message_id,
+            text: &failure.text,
+            posted_at_ms: failure.posted_at_ms,
+        }
+    }
+}
+
+/// Where accepted signals go.
+struct Outputs<'a> {
+    bus: &'a Arc<EventBus>,
+    outbox: &'a Outbox,
+    signals: PeerRef,
+    limits: &'a SanityLimits,
+}
+
+/// Signals posted while the engine was down come in through the catch-up; by then the entry
+/// may be long gone.
+fn is_stale(posted: &Posted<'_>, limits: &SanityLimits) -> bool {
+    let age_secs = (now_ms() - posted.posted_at_ms) / 1000;
+
+    if age_secs <= limits.max_signal_age_secs {
+        return false;
+    }
+
+    println!(
+        "[STALE_SIGNAL] source={} message={} age={}s, not traded",
+        posted.source_id, posted.message_id, age_secs
+    );
+
+    true
+}
+
+/// Publishes the signal as a `TradeIntent` and forwards it, or rejects it when its levels do not
+/// pass validation.
+fn take_signal(
+    outputs: &Outputs<'_>,
+    tracker: &mut SignalTracker,
+    posted: &Posted<'_>,
+    parser: &dyn SignalParser,
+    signal: TradingSignal,
+    mark_price: Option<f64>,
+) {
+    // The signal was read correctly, so neither case counts as a parse failure of its source.
+    let intent = match intent(posted, &signal) {
+        Ok(intent) => intent,
+        Err(error) => {
+            println!(
+                "[INTENT_ERROR] source={} message={} {}",
+                posted.source_id, posted.message_id, error
+            );
+
+            outputs.bus.publish(PulsgramEvent::Error(ErrorEvent {
+                source: "Perp Signals",
+                message_text: format!(
+                    "Cannot build a trade from {} signal: {}",
+                    signal.symbol, error
+                ),
+            }));
+            return;
+        }
+    };
+
+    if let Err(error) = intent.validate(outputs.limits, mark_price) {
+        println!(
+            "[INVALID_SIGNAL] source={} message={} {}",
+            posted.source_id, posted.message_id, error
+        );
+
+        outputs.bus.publish(PulsgramEvent::Error(ErrorEvent {
+            source: "Perp Signals",
+            message_text: format!("Rejected {} signal: {}", signal.symbol, error),
+        }));
+        return;
+    }
+
+    tracker.track(
+        posted.source_id,
+        posted.message_id,
+        intent.intent_id,
+        signal.clone(),
+    );
+
+    // Approved or rejected by the risk manager.
+    outputs.bus.publish(PulsgramEvent::TradeIntent(intent));
+
+    forward(
+        outputs.bus,
+        outputs.outbox,
+        outputs.signals,
+        posted.source_id,
+        parser,
+        &signal,
+    );
+}
+
+fn intent(posted: &Posted<'_>, signal: &TradingSignal) -> Result<TradeIntent, TradeIntentError> {
+    TradeIntent::builder(&signal.symbol)
+        .entry(signal.entry)
+        .side(signal.is_long.into())
+        .stop_loss(signal.stop_loss)
+        .targets(&signal.targets)
+        .timeframe(&signal.timeframe)
+        .source(posted.source_id)
+        .signal_time_ms(posted.posted_at_ms)
+        .build()
+}
+
+/// Captured for review, and counted against the source in the provider registry.
+fn parse_failed(bus: &EventBus, posted: &Posted<'_>, reason: String) {
+    println!(
+        "[PARSE_FAILURE] source={} message={} {}",
+        posted.source_id, posted.message_id, reason
+    );
+
+    bus.publish(PulsgramEvent::SignalParseFailed(SignalParseFailure {
+        chat_id: posted.source_id,
+        message_id: posted.message_id,
+        text: posted.text.to_string(),
+        reason,
+        posted_at_ms: posted.posted_at_ms,
+    }));
+}
+
+fn forward(
+    bus: &Arc<EventBus>,
+    outbox: &Outbox,
+    signals: PeerRef,
+    source_id: i64,
+    parser: &dyn SignalParser,
+    signal: &TradingSignal,
+) {
+    let formatted_signal = format_signal(signal);
+
+    let input_message = InputMessage::new().html(formatted_signal);
+
+    outbox.send(signals, input_message).report_failure(
+        Arc::clone(bus),
+        "PerpSignals::SendMessage",
+        format!(
+            "Perp Signals failed.\nSource: {}\nParser: {}\nSignals Peer: {}",
+            source_id,
+            parser.name(),
+            signals.id
+        ),
+    );
+}
+
+fn publish_updates(bus: &EventBus, tracked: &TrackedSignal, kinds: Vec<SignalUpdateKind>) {
+    for kind in kinds {
+        println!(
+            "[SIGNAL_UPDATE] id={} symbol={} {:?}",
+            tracked.intent_id, tracked.signal.symbol, kind
+        );
+
+        bus.publish(PulsgramEvent::SignalUpdate(SignalUpdate {
+            intent_id: tracked.intent_id,
+            symbol: tracked.signal.symbol,
+            kind,
+        }));
+    }
+}
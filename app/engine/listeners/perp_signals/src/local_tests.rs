@@ -0,0 +1,504 @@
+#[cfg(test)]
+mod tests {
+    use domain::types::signal_update::{SignalUpdateKind, StopLevel};
+    use domain::types::symbol::Symbol;
+    use uuid::Uuid;
+
+    use crate::config::{
+        DirectionKeywords, NumberFormat, PerpSignalsConfig, SignalSource, SignalTemplate,
+    };
+    use crate::errors::ParserConfigError;
+    use crate::failures::missing_field;
+    use crate::parsers::*;
+    use crate::updates::*;
+
+    #[test]
+    fn test_labeled_long_with_timeframe() {
+        let message = r#"🟢 BTCUSDT LONG · 4h
+Entry: 62000
+TP1: 63000
+TP2: 64000
+TP3: 65500
+SL: 61000
+Disclaimer: not financial advice"#;
+
+        let signal = LabeledParser.parse(message).expect("Expected a signal");
+
+        assert_eq!(signal.symbol, Symbol::BTC);
+        assert!(signal.is_long);
+        assert_eq!(signal.timeframe, "4h");
+        assert_eq!(signal.entry, 62000.0);
+        assert_eq!(signal.targets, vec![63000.0, 64000.0, 65500.0]);
+        assert_eq!(signal.stop_loss, 61000.0);
+        assert_eq!(signal.leverage, None);
+    }
+
+    #[test]
+    fn test_labeled_short_defaults_to_1h_and_reads_leverage() {
+        let message = r#"🔴 ETHUSDT SHORT
+📍 Entry: 3500.5
+🎯 TP1: 3400
+🎯 TP2: 3300
+🛑 SL: 3600
+⚡ Leverage: 20x"#;
+
+        let signal = LabeledParser.parse(message).expect("Expected a signal");
+
+        assert_eq!(signal.symbol, Symbol::ETH);
+        assert!(!signal.is_long);
+        assert_eq!(signal.timeframe, "1h");
+        assert_eq!(signal.entry, 3500.5);
+        assert_eq!(signal.targets, vec![3400.0, 3300.0]);
+        assert_eq!(signal.leverage, Some(20));
+    }
+
+    #[test]
+    fn test_labeled_without_targets_is_not_a_signal() {
+        let message = r#"BTCUSDT LONG · 1h
+Entry: 62000
+SL: 61000"#;
+
+        assert_eq!(LabeledParser.parse(message), None);
+    }
+
+    #[test]
+    fn test_labeled_unsupported_symbol_is_not_a_signal() {
+        let message = r#"PEPEUSDT LONG · 1h
+Entry: 0.00001
+TP1: 0.000012
+SL: 0.000009"#;
+
+        assert_eq!(LabeledParser.parse(message), None);
+    }
+
+    #[test]
+    fn test_zone_with_numbered_targets_and_cross_leverage() {
+        let message = r#"#SOL/USDT LONG
+Buy zone: 142.5 - 145
+Targets: 1) 148 2) 152 3) 160
+Stop: 138
+Leverage: Cross 10x"#;
+
+        let signal = ZoneParser.parse(message).expect("Expected a signal");
+
+        assert_eq!(signal.symbol, Symbol::SOL);
+        assert!(signal.is_long);
+        assert_eq!(signal.entry, 143.75);
+        assert_eq!(signal.targets, vec![148.0, 152.0, 160.0]);
+        assert_eq!(signal.stop_loss, 138.0);
+        assert_eq!(signal.timeframe, "1h");
+        assert_eq!(signal.leverage, Some(10));
+    }
+
+    #[test]
+    fn test_zone_emoji_prefixed_short_with_keycap_targets() {
+        let message = r#"🔴 SHORT $ETH
+📍 Sell zone: 3,450 ~ 3,500
+🎯 Targets:
+1️⃣ 3,380
+2️⃣ 3,300
+3️⃣ 3,150
+🛑 Stop loss: 3,580
+⚡ Leverage: Isolated 5x"#;
+
+        let signal = ZoneParser.parse(message).expect("Expected a signal");
+
+        assert_eq!(signal.symbol, Symbol::ETH);
+        assert!(!signal.is_long);
+        assert_eq!(signal.entry, 3475.0);
+        assert_eq!(signal.targets, vec![3380.0, 3300.0, 3150.0]);
+        assert_eq!(signal.stop_loss, 3580.0);
+        assert_eq!(signal.leverage, Some(5));
+    }
+
+    #[test]
+    fn test_zone_side_inferred_from_stop_without_direction() {
+        let message = r#"XRP/USDT
+Entry zone: 0.52 - 0.53
+Targets:
+1) 0.50
+2) 0.48
+Stop: 0.56
+TF: 4H"#;
+
+        let signal = ZoneParser.parse(message).expect("Expected a signal");
+
+        assert_eq!(signal.symbol, Symbol::XRP);
+        assert!(!signal.is_long);
+        assert_eq!(signal.targets, vec![0.50, 0.48]);
+        assert_eq!(signal.timeframe, "4h");
+        assert_eq!(signal.leverage, None);
+    }
+
+    #[test]
+    fn test_zone_single_entry_price() {
+        let message = r#"BNBUSDT
+Buy 580
+Target: 600
+SL: 560"#;
+
+        let signal = ZoneParser.parse(message).expect("Expected a signal");
+
+        assert_eq!(signal.symbol, Symbol::BNB);
+        assert!(signal.is_long);
+        assert_eq!(signal.entry, 580.0);
+        assert_eq!(signal.targets, vec![600.0]);
+        assert_eq!(signal.stop_loss, 560.0);
+    }
+
+    #[test]
+    fn test_zone_ignores_chatter_and_labeled_layout() {
+        let chatter = r#"BTC/USDT looking strong today 🚀
+Buy the dip, targets soon"#;
+
+        let labeled = r#"BTCUSDT LONG · 4h
+Entry: 62000
+TP1: 63000
+SL: 61000"#;
+
+        assert_eq!(ZoneParser.parse(chatter), None);
+        assert_eq!(ZoneParser.parse(labeled), None);
+    }
+
+    fn config(sources: &[(i64, &str)], templates: Vec<SignalTemplate>) -> PerpSignalsConfig {
+        PerpSignalsConfig {
+            sources: sources
+                .iter()
+                .map(|(chat_id, parser)| SignalSource {
+                    chat_id: *chat_id,
+                    parser: parser.to_string(),
+                })
+                .collect(),
+            templates,
+            ..PerpSignalsConfig::default()
+        }
+    }
+
+    fn whales_template() -> SignalTemplate {
+        SignalTemplate {
+            name: "whales".into(),
+            symbol: r"\$([A-Z0-9]+)".into(),
+            direction: DirectionKeywords {
+                long: vec!["🟢".into(), "LONG".into()],
+                short: vec!["🔴".into(), "SHORT".into()],
+            },
+            entry: r"Entrada:\s*{number}\s*/\s*{number}".into(),
+            targets: r"Objetivo \d+:\s*{number}".into(),
+            stop: r"Stop:\s*{number}".into(),
+            timeframe: Some(r"\[(\d+[mhdwMHDW])\]".into()),
+            leverage: Some(r"{number}x".into()),
+            numbers: NumberFormat {
+                decimal: ',',
+                thousands: Some('.'),
+            },
+        }
+    }
+
+    #[test]
+    fn test_registry_maps_chats_to_parsers() {
+        let registry = ParserRegistry::from_config(&config(
+            &[(1, "labeled"), (2, "zone"), (4, "whales")],
+            vec![whales_template()],
+        ))
+        .unwrap()
+        .or_default_source(3);
+
+        assert_eq!(registry.get(1).map(|p| p.name()), Some("labeled"));
+        assert_eq!(registry.get(2).map(|p| p.name()), Some("zone"));
+        assert_eq!(registry.get(4).map(|p| p.name()), Some("whales"));
+        assert!(registry.get(3).is_none());
+    }
+
+    #[test]
+    fn test_registry_default_source_when_none_configured() {
+        let registry = ParserRegistry::from_config(&PerpSignalsConfig::default())
+            .unwrap()
+            .or_default_source(3);
+
+        assert_eq!(registry.get(3).map(|p| p.name()), Some("labeled"));
+    }
+
+    #[test]
+    fn test_registry_rejects_unknown_parser_and_duplicate_names() {
+        let unknown = ParserRegistry::from_config(&config(&[(1, "whales")], vec![]));
+        assert!(matches!(
+            unknown,
+            Err(ParserConfigError::UnknownParser { chat_id: 1, .. })
+        ));
+
+        let mut template = whales_template();
+        template.name = "zone".into();
+
+        let duplicate = ParserRegistry::from_config(&config(&[], vec![template]));
+        assert!(matches!(
+            duplicate,
+            Err(ParserConfigError::DuplicateName(_))
+        ));
+    }
+
+    #[test]
+    fn test_template_with_european_numbers_and_emoji_direction() {
+        let message = r#"🔴 $ETH [4H]
+Entrada: 3.450,5 / 3.500,5
+Objetivo 1: 3.380
+Objetivo 2: 3.300,25
+Stop: 3.580
+Apalancamiento 5x"#;
+
+        let parser = TemplateParser::compile(&whales_template()).unwrap();
+        let signal = parser.parse(message).expect("Expected a signal");
+
+        assert_eq!(signal.symbol, Symbol::ETH);
+        assert!(!signal.is_long);
+        assert_eq!(signal.entry, 3475.5);
+        assert_eq!(signal.targets, vec![3380.0, 3300.25]);
+        assert_eq!(signal.stop_loss, 3580.0);
+        assert_eq!(signal.timeframe, "4h");
+        assert_eq!(signal.leverage, Some(5));
+    }
+
+    #[test]
+    fn test_template_side_from_stop_without_keyword() {
+        let message = r#"$BTC
+Entrada: 62.000 / 62.500
+Objetivo 1: 64.000
+Stop: 61.000"#;
+
+        let parser = TemplateParser::compile(&whales_template()).unwrap();
+        let signal = parser.parse(message).expect("Expected a signal");
+
+        assert!(signal.is_long);
+        assert_eq!(signal.entry, 62250.0);
+        assert_eq!(signal.timeframe, "1h");
+        assert_eq!(signal.leverage, None);
+    }
+
+    #[test]
+    fn test_template_rejects_wrong_placeholders() {
+        let mut template = whales_template();
+        template.stop = r"Stop:\s*{number}\s*{number}".into();
+
+        assert!(matches!(
+            TemplateParser::compile(&template),
+            Err(ParserConfigError::WrongCaptures { field: "stop", .. })
+        ));
+
+        let mut template = whales_template();
+        template.symbol = r"\$([A-Z".into();
+
+        assert!(matches!(
+            TemplateParser::compile(&template),
+            Err(ParserConfigError::InvalidPattern {
+                field: "symbol",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_registry_tests_message_against_every_parser() {
+        let registry = ParserRegistry::from_config(&config(&[], vec![whales_template()])).unwrap();
+
+        let results = registry.test("BTCUSDT LONG · 4h\nEntry: 62000\nTP1: 63000\nSL: 61000");
+
+        let matched: Vec<&str> = results
+            .iter()
+            .filter(|(_, signal)| signal.is_some())
+            .map(|(name, _)| *name)
+            .collect();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(matched, vec!["labeled"]);
+    }
+
+    #[test]
+    fn test_update_tp_hit_move_stop_to_entry() {
+        let message = "TP1 hit ✅ move SL to entry";
+
+        assert_eq!(
+            parse_update(message),
+            vec![SignalUpdateKind::MoveStop {
+                to: StopLevel::Entry
+            }]
+        );
+    }
+
+    #[test]
+    fn test_update_partial_close_with_stop_price() {
+        let message = r#"Take 50% profit here 💰
+Stop loss moved to 3,420"#;
+
+        assert_eq!(
+            parse_update(message),
+            vec![
+                SignalUpdateKind::ClosePartial { percent: 50.0 },
+                SignalUpdateKind::MoveStop {
+                    to: StopLevel::Price(3420.0)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_close_now() {
+        let message = "close BTC now, market looks weak";
+
+        assert_eq!(parse_update(message), vec![SignalUpdateKind::CloseAll]);
+        assert_eq!(mentioned_symbols(message), vec![Symbol::BTC]);
+    }
+
+    #[test]
+    fn test_update_close_100_percent_is_close_all() {
+        assert_eq!(
+            parse_update("Close 100% of the position"),
+            vec![SignalUpdateKind::CloseAll]
+        );
+    }
+
+    #[test]
+    fn test_update_cancel_excludes_other_instructions() {
+        let message = r#"❌ SOLUSDT signal cancelled, do not enter.
+Close any position opened."#;
+
+        assert_eq!(parse_update(message), vec![SignalUpdateKind::Cancel]);
+    }
+
+    #[test]
+    fn test_update_chart_talk_is_not_an_instruction() {
+        let message = "BTC needs a 4h close above 65k before the next leg";
+
+        assert!(parse_update(message).is_empty());
+    }
+
+    #[test]
+    fn test_standalone_update_prose_is_not_a_close() {
+        for message in [
+            "BTC close to TP1, hold tight",
+            "ETH exit liquidity everywhere",
+            "Not planning to exit SOL yet",
+        ] {
+            assert!(parse_standalone_update(message).is_empty(), "{message}");
+        }
+    }
+
+    #[test]
+    fn test_standalone_update_close_command() {
+        for message in [
+            "close BTC now, market looks weak",
+            "🚨 Exit all\nMarket is dumping",
+            "Update:\nclose #ETHUSDT",
+        ] {
+            assert_eq!(
+                parse_standalone_update(message),
+                vec![SignalUpdateKind::CloseAll],
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_edited_levels_become_an_amendment() {
+        let original = LabeledParser
+            .parse("BTCUSDT LONG · 4h\nEntry: 62000\nTP1: 63000\nSL: 61000")
+            .unwrap();
+
+        let edited = LabeledParser
+            .parse("BTCUSDT LONG · 4h\nEntry: 62000\nTP1: 63500\nSL: 61000")
+            .unwrap();
+
+        assert_eq!(amendment(&original, &original), None);
+        assert_eq!(
+            amendment(&original, &edited),
+            Some(SignalUpdateKind::AmendSignal {
+                entry: 62000.0,
+                targets: vec![63500.0],
+                stop_loss: 61000.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_tracker_links_symbol_to_latest_signal_of_the_chat() {
+        let signal = LabeledParser
+            .parse("BTCUSDT LONG · 4h\nEntry: 62000\nTP1: 63000\nSL: 61000")
+            .unwrap();
+
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut tracker = SignalTracker::default();
+        tracker.track(1, 10, first, signal.clone());
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        tracker.track(1, 11, second, signal.clone());
+        tracker.track(2, 12, Uuid::new_v4(), signal);
+
+        assert_eq!(
+            tracker.latest(1, Symbol::BTC).map(|t| t.intent_id),
+            Some(second)
+        );
+        assert_eq!(tracker.get_mut(1, 10).map(|t| t.intent_id), Some(first));
+        assert!(tracker.latest(1, Symbol::ETH).is_none());
+    }
+
+    #[test]
+    fn test_symbol_and_side_look_like_a_signal() {
+        assert!(looks_like_signal("🚀 ETH short now, entry around 3,100"));
+        assert!(looks_like_signal("#SOL/USDT LONG\nBuy zone: 142.5 - 145"));
+
+        assert!(!looks_like_signal("ETH looking strong today"));
+        assert!(!looks_like_signal("Going long on patience, short on sleep"));
+    }
+
+    #[test]
+    fn test_failure_reason_is_the_first_missing_field() {
+        assert_eq!(
+            missing_field("ETH SHORT\nTargets: 3000 2900\nSL: 3200", &ZoneParser),
+            "Missing entry"
+        );
+        assert_eq!(
+            missing_field("ETH SHORT\nEntry: 3100\nStop: 3200", &ZoneParser),
+            "Missing targets"
+        );
+        assert_eq!(
+            missing_field("ETH SHORT\nEntry: 3100\nTP1: 3000", &LabeledParser),
+            "Missing stop loss"
+        );
+        assert_eq!(
+            missing_field(
+                "ETH SHORT\nEntry: 3100\nTP1: 3000\nSL: 3200",
+                &LabeledParser
+            ),
+            "Layout not recognised by the labeled parser"
+        );
+    }
+
+    #[test]
+    fn test_signals_older_than_the_limit_are_stale() {
+        let limits = domain::types::trade_intent::SanityLimits::default();
+        let now_ms = domain::clock::now_ms();
+
+        let posted = |age_secs: i64| crate::Posted {
+            source_id: 1,
+            message_id: 10,
+            text: "",
+            posted_at_ms: now_ms - age_secs * 1000,
+        };
+
+        assert!(!crate::is_stale(&posted(30), &limits));
+        assert!(crate::is_stale(&posted(2 * 60 * 60), &limits));
+    }
+
+    #[test]
+    fn test_replayed_failures_keep_their_age() {
+        let limits = domain::types::trade_intent::SanityLimits::default();
+
+        let failure = domain::types::parse_failure::SignalParseFailure {
+            chat_id: 1,
+            message_id: 10,
+            text: "BTCUSDT LONG · 4h\nEntry: 62000\nTP1: 63000\nSL: 61000".to_string(),
+            reason: "Missing stop loss".to_string(),
+            posted_at_ms: domain::clock::now_ms() - 3 * 60 * 60 * 1000,
+        };
+
+        assert!(crate::is_stale(&crate::Posted::replayed(&failure), &limits));
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::OnceLock;
+
+use domain::types::symbol::Symbol;
+use regex::Regex;
+
+use crate::parsers::{SignalParser, clean, leverage};
+use crate::regex::TradingSignal;
+
+/// One value per labeled line: `Entry:`, `TP1:`, `TP2:`..., `SL:`, with the timeframe
+/// after a `·` in the header.
+///
+/// ```text
+/// BTCUSDT LONG · 4h
+/// Entry: 62000
+/// TP1: 63000
+/// SL: 61000
+/// ```
+pub struct LabeledParser;
+
+struct LabeledRegexes {
+    symbol: Regex,
+    direction: Regex,
+    entry: Regex,
+    targets: Regex,
+    stop_loss: Regex,
+    timeframe: Regex,
+}
+
+fn regexes() -> &'static LabeledRegexes {
+    static REGEXES: OnceLock<LabeledRegexes> = OnceLock::new();
+
+    REGEXES.get_or_init(|| LabeledRegexes {
+        symbol: Regex::new(r"\b([A-Z]+)USDT\b").expect("Invalid regex: symbol"),
+
+        timeframe: Regex::new(r"·\s*(\d+[hmdw])").expect("Invalid regex: timeframe"),
+
+        direction: Regex::new(r"(LONG|SHORT)").expect("Invalid regex: direction"),
+
+        entry: Regex::new(r"Entry:\s*([0-9]+\.?[0-9]*)").expect("Invalid regex: entry"),
+
+        targets: Regex::new(r"TP[0-9]+:\s*([0-9]+\.?[0-9]*)").expect("Invalid regex: targets"),
+
+        stop_loss: Regex::new(r"SL:\s*([0-9]+\.?[0-9]*)").expect("Invalid regex: stop_loss"),
+    })
+}
+
+impl SignalParser for LabeledParser {
+    fn name(&self) -> &str {
+        "labeled"
+    }
+
+    fn parse(&self, text: &str) -> Option<TradingSignal> {
+        let re = regexes();
+
+        let cleaned_text = clean(text);
+
+        let symbol = re.symbol.captures(&cleaned_text)?.get(1)?.as_str();
+
+        let direction_str = re.direction.captures(&cleaned_text)?.get(1)?.as_str();
+
+        let is_long = direction_str == "LONG";
+
+        let timeframe = re
+            .timeframe
+            .captures(&cleaned_text)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_string())
+            .unwrap_or("1h".into());
+
+        let entry = re
+            .entry
+            .captures(&cleaned_text)?
+            .get(1)?
+            .as_str()
+            .parse::<f64>()
+            .ok()?;
+
+        let targets: Vec<f64> = re
+            .targets
+            .captures_iter(&cleaned_text)
+            .filter_map(|cap| cap.get(1)?.as_str().parse::<f64>().ok())
+            .collect();
+
+        let stop_loss = re
+            .stop_loss
+            .captures(&cleaned_text)?
+            .get(1)?
+            .as_str()
+            .parse::<f64>()
+            .ok()?;
+
+        if targets.is_empty() {
+            return None;
+        }
+
+        let symbol = symbol.parse::<Symbol>().ok()?;
+
+        Some(TradingSignal {
+            symbol,
+            is_long,
+            entry,
+            targets,
+            stop_loss,
+            timeframe,
+            leverage: leverage(&cleaned_text),
+        })
+    }
+}
@@ -0,0 +1,142 @@
+mod labeled;
+mod template;
+mod zone;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use regex::Regex;
+
+use crate::config::PerpSignalsConfig;
+use crate::errors::ParserConfigError;
+use crate::regex::{TradingSignal, remove_emojis};
+use crate::updates::mentioned_symbols;
+
+pub use labeled::LabeledParser;
+pub use template::TemplateParser;
+pub use zone::ZoneParser;
+
+/// Reads trading signals written in one channel's layout.
+pub trait SignalParser: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Parses the raw message text. None when the message is not a signal in this layout.
+    fn parse(&self, text: &str) -> Option<TradingSignal>;
+}
+
+/// Every known parser, and the one each source chat uses.
+pub struct ParserRegistry {
+    // Built-in layouts first, then the configured templates.
+    parsers: Vec<Arc<dyn SignalParser>>,
+    sources: HashMap<i64, Arc<dyn SignalParser>>,
+}
+
+impl ParserRegistry {
+    /// Compiles the configured templates and resolves each source's parser by name.
+    pub fn from_config(config: &PerpSignalsConfig) -> Result<Self, ParserConfigError> {
+        let mut parsers: Vec<Arc<dyn SignalParser>> =
+            vec![Arc::new(LabeledParser), Arc::new(ZoneParser)];
+
+        for template in &config.templates {
+            if parsers.iter().any(|parser| parser.name() == template.name) {
+                return Err(ParserConfigError::DuplicateName(template.name.clone()));
+            }
+
+            parsers.push(Arc::new(TemplateParser::compile(template)?));
+        }
+
+        let mut sources = HashMap::new();
+
+        for source in &config.sources {
+            let parser = parsers
+                .iter()
+                .find(|parser| parser.name() == source.parser)
+                .ok_or_else(|| ParserConfigError::UnknownParser {
+                    chat_id: source.chat_id,
+                    parser: source.parser.clone(),
+                })?;
+
+            sources.insert(source.chat_id, Arc::clone(parser));
+        }
+
+        Ok(Self { parsers, sources })
+    }
+
+    /// Reads `chat_id` with the labeled parser when no source is configured.
+    pub fn or_default_source(mut self, chat_id: i64) -> Self {
+        if self.sources.is_empty() {
+            self.sources.insert(chat_id, Arc::new(LabeledParser));
+        }
+
+        self
+    }
+
+    pub fn get(&self, chat_id: i64) -> Option<&dyn SignalParser> {
+        self.sources.get(&chat_id).map(|parser| parser.as_ref())
+    }
+
+    /// Runs `text` through every parser, to check a layout against a pasted message.
+    pub fn test(&self, text: &str) -> Vec<(&str, Option<TradingSignal>)> {
+        self.parsers
+            .iter()
+            .map(|parser| (parser.name(), parser.parse(text)))
+            .collect()
+    }
+}
+
+struct SharedRegexes {
+    disclaimer: Regex,
+    leverage: Regex,
+    side: Regex,
+}
+
+fn regexes() -> &'static SharedRegexes {
+    static REGEXES: OnceLock<SharedRegexes> = OnceLock::new();
+
+    REGEXES.get_or_init(|| SharedRegexes {
+        disclaimer: Regex::new(r"(?i)disclaimer:.*").expect("Invalid regex: disclaimer"),
+
+        // "Leverage: 10x", "Leverage: Cross 20x", "Isolated (5x)", "Lev 10"
+        leverage: Regex::new(r"(?i)\b(?:leverage|lev|cross|isolated)\b[^0-9\n]{0,20}(\d+)")
+            .expect("Invalid regex: leverage"),
+
+        side: Regex::new(r"(?i)\b(?:long|short)\b").expect("Invalid regex: side"),
+    })
+}
+
+/// Strips emojis and the disclaimer line.
+pub(crate) fn clean(text: &str) -> String {
+    strip_disclaimer(&remove_emojis(text)).into_owned()
+}
+
+/// The disclaimer line can carry numbers of its own.
+pub(crate) fn strip_disclaimer(text: &str) -> Cow<'_, str> {
+    regexes().disclaimer.replace_all(text, "")
+}
+
+/// A listed symbol and a side, which every layout has. Tells a failed parse from chatter.
+pub(crate) fn looks_like_signal(text: &str) -> bool {
+    let text = clean(text);
+
+    regexes().side.is_match(&text) && !mentioned_symbols(&text).is_empty()
+}
+
+pub(crate) fn leverage(text: &str) -> Option<u32> {
+    regexes()
+        .leverage
+        .captures(text)?
+        .get(1)?
+        .as_str()
+        .parse::<u32>()
+        .ok()
+        .filter(|leverage| *leverage > 0)
+}
+
+pub(crate) fn parse_price(value: &str) -> Option<f64> {
+    value
+        .replace(',', "")
+        .parse::<f64>()
+        .ok()
+        .filter(|price| *price > 0.0)
+}
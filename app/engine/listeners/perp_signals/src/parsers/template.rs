@@ -0,0 +1,205 @@
+use domain::types::symbol::Symbol;
+use regex::Regex;
+
+use crate::config::{NumberFormat, SignalTemplate};
+use crate::errors::ParserConfigError;
+use crate::parsers::{SignalParser, strip_disclaimer};
+use crate::regex::TradingSignal;
+
+const NUMBER_PLACEHOLDER: &str = "{number}";
+
+/// A `SignalTemplate` compiled at startup.
+#[derive(Debug)]
+pub struct TemplateParser {
+    name: String,
+    symbol: Regex,
+    entry: Regex,
+    targets: Regex,
+    stop: Regex,
+    timeframe: Option<Regex>,
+    leverage: Option<Regex>,
+    long: Vec<String>,
+    short: Vec<String>,
+    numbers: NumberFormat,
+}
+
+impl TemplateParser {
+    pub fn compile(template: &SignalTemplate) -> Result<Self, ParserConfigError> {
+        let compiler = Compiler { template };
+
+        Ok(Self {
+            name: template.name.clone(),
+            symbol: compiler.pattern("symbol", &template.symbol, 1..=1, "one group")?,
+            entry: compiler.pattern("entry", &template.entry, 1..=2, "one or two {number}")?,
+            targets: compiler.pattern("targets", &template.targets, 1..=1, "one {number}")?,
+            stop: compiler.pattern("stop", &template.stop, 1..=1, "one {number}")?,
+            timeframe: template
+                .timeframe
+                .as_deref()
+                .map(|pattern| compiler.pattern("timeframe", pattern, 1..=1, "one group"))
+                .transpose()?,
+            leverage: template
+                .leverage
+                .as_deref()
+                .map(|pattern| compiler.pattern("leverage", pattern, 1..=1, "one {number}"))
+                .transpose()?,
+            long: lowercase(&template.direction.long),
+            short: lowercase(&template.direction.short),
+            numbers: template.numbers.clone(),
+        })
+    }
+
+    fn number(&self, value: &str) -> Option<f64> {
+        let mut value = value.to_string();
+
+        if let Some(thousands) = self.numbers.thousands {
+            value.retain(|c| c != thousands);
+        }
+
+        value
+            .replace(self.numbers.decimal, ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|number| *number > 0.0)
+    }
+
+    fn first_number(&self, regex: &Regex, text: &str) -> Option<f64> {
+        self.number(regex.captures(text)?.get(1)?.as_str())
+    }
+
+    /// The side of the earliest keyword in the message.
+    fn is_long(&self, text: &str) -> Option<bool> {
+        let text = text.to_lowercase();
+
+        let earliest = |keywords: &[String]| {
+            keywords
+                .iter()
+                .filter_map(|keyword| text.find(keyword.as_str()))
+                .min()
+        };
+
+        match (earliest(&self.long), earliest(&self.short)) {
+            (Some(long), Some(short)) => Some(long < short),
+            (Some(_), None) => Some(true),
+            (None, Some(_)) => Some(false),
+            (None, None) => None,
+        }
+    }
+}
+
+impl SignalParser for TemplateParser {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn parse(&self, text: &str) -> Option<TradingSignal> {
+        // Emojis are kept, templates may use them as keywords.
+        let text = strip_disclaimer(text);
+
+        let symbol = self
+            .symbol
+            .captures(&text)?
+            .get(1)?
+            .as_str()
+            .parse::<Symbol>()
+            .ok()?;
+
+        let entry_captures = self.entry.captures(&text)?;
+
+        let low = self.number(entry_captures.get(1)?.as_str())?;
+
+        let entry = match entry_captures.get(2) {
+            Some(high) => (low + self.number(high.as_str())?) / 2.0,
+            None => low,
+        };
+
+        let targets: Vec<f64> = self
+            .targets
+            .captures_iter(&text)
+            .filter_map(|captures| self.number(captures.get(1)?.as_str()))
+            .collect();
+
+        if targets.is_empty() {
+            return None;
+        }
+
+        let stop_loss = self.first_number(&self.stop, &text)?;
+
+        let is_long = self.is_long(&text).unwrap_or(stop_loss < entry);
+
+        let timeframe = self
+            .timeframe
+            .as_ref()
+            .and_then(|regex| regex.captures(&text))
+            .and_then(|captures| captures.get(1))
+            .map(|m| m.as_str().to_ascii_lowercase())
+            .unwrap_or("1h".into());
+
+        let leverage = self
+            .leverage
+            .as_ref()
+            .and_then(|regex| self.first_number(regex, &text))
+            .map(|leverage| leverage as u32);
+
+        Some(TradingSignal {
+            symbol,
+            is_long,
+            entry,
+            targets,
+            stop_loss,
+            timeframe,
+            leverage,
+        })
+    }
+}
+
+struct Compiler<'a> {
+    template: &'a SignalTemplate,
+}
+
+impl Compiler<'_> {
+    fn pattern(
+        &self,
+        field: &'static str,
+        pattern: &str,
+        groups: std::ops::RangeInclusive<usize>,
+        expected: &'static str,
+    ) -> Result<Regex, ParserConfigError> {
+        let pattern = pattern.replace(NUMBER_PLACEHOLDER, &number_pattern(&self.template.numbers));
+
+        let regex = Regex::new(&pattern).map_err(|error| ParserConfigError::InvalidPattern {
+            template: self.template.name.clone(),
+            field,
+            error,
+        })?;
+
+        // The first capture is the whole match.
+        if !groups.contains(&(regex.captures_len() - 1)) {
+            return Err(ParserConfigError::WrongCaptures {
+                template: self.template.name.clone(),
+                field,
+                expected,
+            });
+        }
+
+        Ok(regex)
+    }
+}
+
+fn number_pattern(format: &NumberFormat) -> String {
+    let decimal = regex::escape(&format.decimal.to_string());
+
+    let thousands = format
+        .thousands
+        .map(|separator| regex::escape(&separator.to_string()))
+        .unwrap_or_default();
+
+    format!("([0-9][0-9{thousands}]*(?:{decimal}[0-9]+)?)")
+}
+
+fn lowercase(keywords: &[String]) -> Vec<String> {
+    keywords
+        .iter()
+        .map(|keyword| keyword.to_lowercase())
+        .collect()
+}
@@ -0,0 +1,158 @@
+use std::sync::OnceLock;
+
+use domain::types::symbol::Symbol;
+use regex::Regex;
+
+use crate::parsers::{SignalParser, clean, leverage, parse_price};
+use crate::regex::TradingSignal;
+
+/// An entry range with numbered targets, as posted by most paid-group channels.
+///
+/// ```text
+/// #SOL/USDT LONG
+/// Buy zone: 142.5 - 145
+/// Targets: 1) 148 2) 152 3) 160
+/// Stop: 138
+/// Leverage: Cross 10x
+/// ```
+///
+/// The entry is the middle of the range. Without a direction keyword, the side is taken from
+/// where the stop sits relative to the entry.
+pub struct ZoneParser;
+
+struct ZoneRegexes {
+    pair: Regex,
+    tag: Regex,
+    direction: Regex,
+    entry: Regex,
+    targets: Regex,
+    targets_end: Regex,
+    enumerator: Regex,
+    price: Regex,
+    stop_loss: Regex,
+    timeframe: Regex,
+}
+
+const PRICE: &str = r"[0-9][0-9,]*\.?[0-9]*";
+
+fn regexes() -> &'static ZoneRegexes {
+    static REGEXES: OnceLock<ZoneRegexes> = OnceLock::new();
+
+    REGEXES.get_or_init(|| ZoneRegexes {
+        // BTCUSDT, BTC/USDT, BTC / USDT
+        pair: Regex::new(r"\b([A-Z0-9]{2,10}?)\s*/?\s*USDT\b").expect("Invalid regex: pair"),
+
+        // #BTC, $BTC
+        tag: Regex::new(r"[#$]([A-Z0-9]{2,10})\b").expect("Invalid regex: tag"),
+
+        direction: Regex::new(r"(?i)\b(long|short|buy|sell)\b").expect("Invalid regex: direction"),
+
+        entry: Regex::new(&format!(
+            r"(?i)\b(?:buy|sell|entry|entries)\s*(?:zone|range|area)?\s*:?\s*({PRICE})(?:\s*(?:-|–|~|to)\s*({PRICE}))?"
+        ))
+        .expect("Invalid regex: entry"),
+
+        targets: Regex::new(r"(?i)\b(?:targets?|tps?)\b\s*:?").expect("Invalid regex: targets"),
+
+        targets_end: Regex::new(r"(?i)\b(?:stop|sl|leverage|lev|cross|isolated|timeframe|tf)\b")
+            .expect("Invalid regex: targets_end"),
+
+        // "1)", "2.", "3 -" in front of each target
+        enumerator: Regex::new(r"\b\d+\s*\)|\b\d+\.\s|\b\d+\s+-\s")
+            .expect("Invalid regex: enumerator"),
+
+        price: Regex::new(PRICE).expect("Invalid regex: price"),
+
+        stop_loss: Regex::new(&format!(
+            r"(?i)\b(?:stop(?:[\s-]*loss)?|sl)\b\s*:?\s*({PRICE})"
+        ))
+        .expect("Invalid regex: stop_loss"),
+
+        timeframe: Regex::new(r"(?i)\b(?:timeframe|tf)\s*:?\s*(\d+[mhdw])\b")
+            .expect("Invalid regex: timeframe"),
+    })
+}
+
+impl SignalParser for ZoneParser {
+    fn name(&self) -> &str {
+        "zone"
+    }
+
+    fn parse(&self, text: &str) -> Option<TradingSignal> {
+        let re = regexes();
+
+        let cleaned_text = clean(text);
+
+        let symbol = re
+            .pair
+            .captures(&cleaned_text)
+            .or_else(|| re.tag.captures(&cleaned_text))?
+            .get(1)?
+            .as_str()
+            .parse::<Symbol>()
+            .ok()?;
+
+        let entry_captures = re.entry.captures(&cleaned_text)?;
+
+        let low = parse_price(entry_captures.get(1)?.as_str())?;
+
+        let entry = match entry_captures.get(2) {
+            Some(high) => (low + parse_price(high.as_str())?) / 2.0,
+            None => low,
+        };
+
+        let targets = targets(re, &cleaned_text);
+
+        if targets.is_empty() {
+            return None;
+        }
+
+        let stop_loss = parse_price(re.stop_loss.captures(&cleaned_text)?.get(1)?.as_str())?;
+
+        let is_long = match re.direction.captures(&cleaned_text) {
+            Some(captures) => {
+                let direction = captures.get(1)?.as_str().to_ascii_lowercase();
+                direction == "long" || direction == "buy"
+            }
+            None => stop_loss < entry,
+        };
+
+        let timeframe = re
+            .timeframe
+            .captures(&cleaned_text)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_ascii_lowercase())
+            .unwrap_or("1h".into());
+
+        Some(TradingSignal {
+            symbol,
+            is_long,
+            entry,
+            targets,
+            stop_loss,
+            timeframe,
+            leverage: leverage(&cleaned_text),
+        })
+    }
+}
+
+/// Prices between the `Targets` label and the next section.
+fn targets(re: &ZoneRegexes, text: &str) -> Vec<f64> {
+    let Some(label) = re.targets.find(text) else {
+        return Vec::new();
+    };
+
+    let rest = &text[label.end()..];
+
+    let block = match re.targets_end.find(rest) {
+        Some(end) => &rest[..end.start()],
+        None => rest,
+    };
+
+    let block = re.enumerator.replace_all(block, " ");
+
+    re.price
+        .find_iter(&block)
+        .filter_map(|price| parse_price(price.as_str()))
+        .collect()
+}
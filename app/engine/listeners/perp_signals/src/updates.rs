@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use domain::types::signal_update::{SignalUpdateKind, StopLevel};
+use domain::types::symbol::Symbol;
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::parsers::{clean, parse_price};
+use crate::regex::TradingSignal;
+
+// Oldest signals are forgotten first; providers rarely follow up on old ones.
+const MAX_TRACKED_SIGNALS: usize = 500;
+
+/// A signal that became a `TradeIntent`, kept so that edits and follow-ups can be linked to it.
+#[derive(Debug, Clone)]
+pub struct TrackedSignal {
+    pub intent_id: Uuid,
+    pub signal: TradingSignal,
+    posted_at: Instant,
+}
+
+/// Signals posted by each source, keyed by chat and message ID.
+#[derive(Default)]
+pub struct SignalTracker {
+    signals: HashMap<(i64, i32), TrackedSignal>,
+}
+
+impl SignalTracker {
+    pub fn track(&mut self, chat_id: i64, message_id: i32, intent_id: Uuid, signal: TradingSignal) {
+        if self.signals.len() >= MAX_TRACKED_SIGNALS
+            && let Some(oldest) = self
+                .signals
+                .iter()
+                .min_by_key(|(_, tracked)| tracked.posted_at)
+                .map(|(key, _)| *key)
+        {
+            self.signals.remove(&oldest);
+        }
+
+        self.signals.insert(
+            (chat_id, message_id),
+            TrackedSignal {
+                intent_id,
+                signal,
+                posted_at: Instant::now(),
+            },
+        );
+    }
+
+    pub fn get_mut(&mut self, chat_id: i64, message_id: i32) -> Option<&mut TrackedSignal> {
+        self.signals.get_mut(&(chat_id, message_id))
+    }
+
+    /// The latest signal `chat_id` posted on `symbol`.
+    pub fn latest(&self, chat_id: i64, symbol: Symbol) -> Option<&TrackedSignal> {
+        self.signals
+            .iter()
+            .filter(|((chat, _), tracked)| *chat == chat_id && tracked.signal.symbol == symbol)
+            .max_by_key(|(_, tracked)| tracked.posted_at)
+            .map(|(_, tracked)| tracked)
+    }
+}
+
+/// Level changes between a signal and its edited version, if any.
+pub fn amendment(original: &TradingSignal, edited: &TradingSignal) -> Option<SignalUpdateKind> {
+    let changed = original.entry != edited.entry
+        || original.targets != edited.targets
+        || original.stop_loss != edited.stop_loss;
+
+    changed.then(|| SignalUpdateKind::AmendSignal {
+        entry: edited.entry,
+        targets: edited.targets.clone(),
+        stop_loss: edited.stop_loss,
+    })
+}
+
+struct UpdateRegexes {
+    cancel: Regex,
+    close_partial: Regex,
+    close_all: Regex,
+    close_command: Regex,
+    candle_close: Regex,
+    move_stop: Regex,
+    symbol: Regex,
+}
+
+fn regexes() -> &'static UpdateRegexes {
+    static REGEXES: OnceLock<UpdateRegexes> = OnceLock::new();
+
+    REGEXES.get_or_init(|| UpdateRegexes {
+        cancel: Regex::new(
+            r"(?i)\b(?:cancel(?:l?ed)?|invalid(?:ated)?|void|don'?t enter|do not enter)\b",
+        )
+        .expect("Invalid regex: cancel"),
+
+        // "close 50%", "take 30% profit", "book 25% here"
+        close_partial: Regex::new(
+            r"(?i)\b(?:close|take|book|secure)\b[^%\n]{0,20}?(\d{1,3}(?:\.\d+)?)\s*%",
+        )
+        .expect("Invalid regex: close_partial"),
+
+        close_all: Regex::new(r"(?i)\b(?:close|exit)\b").expect("Invalid regex: close_all"),
+
+        // "close BTC now", "exit all", "Close #ETHUSDT" at the start of a line
+        close_command: Regex::new(
+            r"(?im)^\s*(?:close|exit)\s+(?:all|now|#?(?-i:[A-Z]{2,10})(?:/?USDT)?)\b",
+        )
+        .expect("Invalid regex: close_command"),
+
+        // Chart talk, not an instruction: "4h close above 65k"
+        candle_close: Regex::new(r"(?i)\bclose[sd]?\s+(?:above|below|under|over)\b")
+            .expect("Invalid regex: candle_close"),
+
+        // "move SL to entry", "SL moved to BE", "stop loss to 1.25"
+        move_stop: Regex::new(
+            r"(?i)\b(?:sl|stop(?:[\s-]*loss)?)\b[^\n0-9]{0,20}?\b(?:to|at)\s*(entry|be|break[\s-]?even|[0-9][0-9,]*\.?[0-9]*)",
+        )
+        .expect("Invalid regex: move_stop"),
+
+        symbol: Regex::new(r"\b([A-Z]{2,10})(?:/?USDT)?\b").expect("Invalid regex: symbol"),
+    })
+}
+
+/// Reads the instructions in a provider's follow-up message.
+///
+/// Cancelling or closing everything excludes any other instruction; a partial close and a
+/// stop move can come together ("TP1 hit, close 50% and move SL to entry").
+pub fn parse_update(text: &str) -> Vec<SignalUpdateKind> {
+    let re = regexes();
+
+    parse(text, |text| {
+        re.close_all.is_match(text) && !re.candle_close.is_match(text)
+    })
+}
+
+/// Reads the instructions in a message that only names the symbol, without replying to the
+/// signal.
+///
+/// Chatter mentions closing all the time ("BTC close to TP1"), so closing everything takes an
+/// imperative at the start of a line: "close BTC now", "exit all".
+pub fn parse_standalone_update(text: &str) -> Vec<SignalUpdateKind> {
+    parse(text, |text| regexes().close_command.is_match(text))
+}
+
+fn parse(text: &str, closes_all: impl Fn(&str) -> bool) -> Vec<SignalUpdateKind> {
+    let re = regexes();
+    let text = clean(text);
+
+    if re.cancel.is_match(&text) {
+        return vec![SignalUpdateKind::Cancel];
+    }
+
+    let mut updates = Vec::new();
+
+    let partial = re
+        .close_partial
+        .captures(&text)
+        .and_then(|captures| captures.get(1)?.as_str().parse::<f64>().ok())
+        .filter(|percent| *percent > 0.0);
+
+    match partial {
+        Some(percent) if percent >= 100.0 => return vec![SignalUpdateKind::CloseAll],
+        Some(percent) => updates.push(SignalUpdateKind::ClosePartial { percent }),
+        None if closes_all(&text) => {
+            return vec![SignalUpdateKind::CloseAll];
+        }
+        None => {}
+    }
+
+    let stop = re
+        .move_stop
+        .captures(&text)
+        .and_then(|captures| captures.get(1))
+        .and_then(|level| match level.as_str().to_ascii_lowercase().as_str() {
+            "entry" | "be" => Some(StopLevel::Entry),
+            level if level.starts_with("break") => Some(StopLevel::Entry),
+            level => parse_price(level).map(StopLevel::Price),
+        });
+
+    if let Some(to) = stop {
+        updates.push(SignalUpdateKind::MoveStop { to });
+    }
+
+    updates
+}
+
+/// Supported symbols a message mentions, for follow-ups that are not replies ("close BTC now").
+pub fn mentioned_symbols(text: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+
+    for symbol in regexes()
+        .symbol
+        .captures_iter(text)
+        .filter_map(|captures| captures.get(1)?.as_str().parse::<Symbol>().ok())
+    {
+        if !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+
+    symbols
+}
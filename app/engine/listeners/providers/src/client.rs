@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use domain::types::kol_follow::{FollowTarget, KolFollow};
+use domain::types::parse_failure::{CapturedParseFailure, SignalParseFailure};
+use domain::types::provider::{Provider, ProviderActivity, ProviderStats};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::errors::ProvidersError;
+
+#[derive(Debug, Deserialize)]
+struct StatsRow {
+    chat_id: i64,
+    #[serde(flatten)]
+    stats: ProviderStats,
+}
+
+/// The provider registry, parse-failure queue and KOL follow graph kept by the persistence service.
+#[derive(Debug, Clone)]
+pub struct PersistenceClient {
+    http: Client,
+    url: String,
+}
+
+impl PersistenceClient {
+    pub fn new(http: Client, url: &str) -> Self {
+        Self {
+            http,
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Every provider, with its stats over the last `stats_days` days.
+    pub async fn providers(&self, stats_days: u32) -> Result<Vec<Provider>, ProvidersError> {
+        let mut providers: Vec<Provider> = self
+            .send(
+                "/providers",
+                self.http.get(format!("{}/providers", self.url)),
+            )
+            .await?
+            .json()
+            .await?;
+
+        let rows: Vec<StatsRow> = self
+            .send(
+                "/providers/stats",
+                self.http
+                    .get(format!("{}/providers/stats?days={}", self.url, stats_days)),
+            )
+            .await?
+            .json()
+            .await?;
+
+        let mut stats: HashMap<i64, ProviderStats> = rows
+            .into_iter()
+            .map(|row| (row.chat_id, row.stats))
+            .collect();
+
+        for provider in &mut providers {
+            provider.stats = stats.remove(&provider.chat_id).unwrap_or_default();
+        }
+
+        Ok(providers)
+    }
+
+    pub async fn record(
+        &self,
+        chat_id: i64,
+        activity: ProviderActivity,
+    ) -> Result<(), ProvidersError> {
+        let path = format!("/providers/{}/activity", chat_id);
+
+        self.send(
+            &path,
+            self.http
+                .post(format!("{}{}", self.url, path))
+                .json(&activity),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn capture(&self, failure: &SignalParseFailure) -> Result<(), ProvidersError> {
+        self.send(
+            "/parse-failures",
+            self.http
+                .post(format!("{}/parse-failures", self.url))
+                .json(failure),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Unresolved failures, newest first.
+    pub async fn open_failures(
+        &self,
+        limit: u32,
+    ) -> Result<Vec<CapturedParseFailure>, ProvidersError> {
+        Ok(self
+            .send(
+                "/parse-failures",
+                self.http
+                    .get(format!("{}/parse-failures?limit={}", self.url, limit)),
+            )
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub async fn failure(&self, id: Uuid) -> Result<CapturedParseFailure, ProvidersError> {
+        let path = format!("/parse-failures/{}", id);
+
+        Ok(self
+            .send(&path, self.http.get(format!("{}{}", self.url, path)))
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub async fn resolve(&self, id: Uuid) -> Result<(), ProvidersError> {
+        let path = format!("/parse-failures/{}/resolve", id);
+
+        self.send(&path, self.http.post(format!("{}{}", self.url, path)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Stores the edge and returns every tracked KOL following the same account, this one included.
+    pub async fn record_follow(&self, follow: &KolFollow) -> Result<FollowTarget, ProvidersError> {
+        Ok(self
+            .send(
+                "/follows",
+                self.http.post(format!("{}/follows", self.url)).json(follow),
+            )
+            .await?
+            .json()
+            .await?)
+    }
+
+    async fn send(&self, path: &str, request: RequestBuilder) -> Result<Response, ProvidersError> {
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            return Err(ProvidersError::Status {
+                path: path.to_string(),
+                status: response.status().as_u16(),
+            });
+        }
+
+        Ok(response)
+    }
+}
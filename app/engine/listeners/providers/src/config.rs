@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ProvidersConfig {
+    /// Base URL of the persistence service, e.g. `http://127.0.0.1:8180/persistance`.
+    /// Without it, sources come from `[perp_signals]` only and no stats are kept.
+    pub url: Option<String>,
+    /// How often settings and stats are reloaded.
+    pub refresh_secs: u64,
+    /// Length of the rolling window the stats cover.
+    pub stats_days: u32,
+}
+
+impl Default for ProvidersConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            refresh_secs: 5 * 60,
+            stats_days: 30,
+        }
+    }
+}
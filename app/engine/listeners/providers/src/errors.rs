@@ -0,0 +1,38 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum ProvidersError {
+    Http(reqwest::Error),
+    /// The persistence service answered with an error status.
+    Status {
+        path: String,
+        status: u16,
+    },
+}
+
+impl Display for ProvidersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProvidersError::Http(err) => write!(f, "HTTP error: {}", err),
+            ProvidersError::Status { path, status } => {
+                write!(f, "Persistence returned {} for {}", status, path)
+            }
+        }
+    }
+}
+
+impl Error for ProvidersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProvidersError::Http(err) => Some(err),
+            ProvidersError::Status { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ProvidersError {
+    fn from(err: reqwest::Error) -> Self {
+        ProvidersError::Http(err)
+    }
+}
@@ -0,0 +1,122 @@
+pub mod client;
+pub mod config;
+pub mod errors;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use domain::types::lifecycle::LifecycleEvent;
+use domain::types::provider::ProviderActivity;
+use domain::types::trade::TradeUpdateKind;
+use publisher::types::{ErrorEvent, PulsgramEvent};
+use publisher::{EventBus, handle_recv_error};
+use uuid::Uuid;
+
+use crate::client::PersistenceClient;
+use crate::config::ProvidersConfig;
+
+/// Keeps the provider registry in the persistence service up to date and publishes it.
+///
+/// Signals, parse failures and closed trades are recorded against the provider whose chat
+/// they came from. Parse failures are also captured for review, whatever their source. Every
+/// `refresh_secs`, providers are reloaded with their rolling stats and published as
+/// `PulsgramEvent::Providers`.
+pub async fn run(bus: Arc<EventBus>, client: PersistenceClient, config: ProvidersConfig) {
+    println!("Providers running...");
+    let mut rx = bus.subscribe();
+
+    // Chats of the registered providers; activity from other sources is not recorded.
+    let mut known: HashSet<i64> = HashSet::new();
+
+    // Source chat of each trade still open or pending.
+    let mut sources: HashMap<Uuid, i64> = HashMap::new();
+
+    let mut refresh = tokio::time::interval(Duration::from_secs(config.refresh_secs.max(1)));
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Ok(event) => {
+                    let recorded = match event {
+                        PulsgramEvent::TradeIntent(intent) => intent.source.map(|chat_id| {
+                            sources.insert(intent.intent_id, chat_id);
+                            (chat_id, ProviderActivity::Signal)
+                        }),
+
+                        PulsgramEvent::SignalParseFailed(failure) => {
+                            if let Err(error) = client.capture(&failure).await {
+                                bus.publish(PulsgramEvent::Error(ErrorEvent {
+                                    source: "Providers::Capture",
+                                    message_text: format!(
+                                        "Failed to capture parse failure of {}/{}: {}",
+                                        failure.chat_id, failure.message_id, error
+                                    ),
+                                }));
+                            }
+
+                            Some((failure.chat_id, ProviderActivity::ParseFailure))
+                        }
+
+                        PulsgramEvent::TradeUpdate(update) => match update.kind {
+                            TradeUpdateKind::Closed { pnl, .. } => sources
+                                .remove(&update.intent_id)
+                                .map(|chat_id| (chat_id, ProviderActivity::Closed { pnl })),
+                            _ => None,
+                        },
+
+                        PulsgramEvent::TradeRejected(rejected) => {
+                            sources.remove(&rejected.intent_id);
+                            None
+                        }
+
+                        PulsgramEvent::TradeTransition(transition)
+                            if matches!(
+                                transition.event,
+                                LifecycleEvent::Cancel { .. } | LifecycleEvent::Fail { .. }
+                            ) =>
+                        {
+                            sources.remove(&transition.intent_id);
+                            None
+                        }
+
+                        _ => None,
+                    };
+
+                    if let Some((chat_id, activity)) = recorded
+                        && known.contains(&chat_id)
+                        && let Err(error) = client.record(chat_id, activity).await
+                    {
+                        bus.publish(PulsgramEvent::Error(ErrorEvent {
+                            source: "Providers::Record",
+                            message_text: format!(
+                                "Failed to record {:?} for {}: {}",
+                                activity, chat_id, error
+                            ),
+                        }));
+                    }
+                }
+
+                Err(error) => {
+                    if handle_recv_error("Providers RecvError", error, &bus) {
+                        break;
+                    }
+                }
+            },
+
+            _ = refresh.tick() => match client.providers(config.stats_days).await {
+                Ok(providers) => {
+                    known = providers.iter().map(|provider| provider.chat_id).collect();
+                    bus.publish(PulsgramEvent::Providers(providers));
+                }
+
+                Err(error) => {
+                    bus.publish(PulsgramEvent::Error(ErrorEvent {
+                        source: "Providers::Refresh",
+                        message_text: format!("Failed to load providers: {}", error),
+                    }));
+                }
+            },
+        }
+    }
+}
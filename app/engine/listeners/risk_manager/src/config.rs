@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+use crate::kill_switch::KillSwitchConfig;
+use crate::providers::ProviderPolicy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Keep the open trade and reject the new signal.
+    Ignore,
+    /// Close the open trade and take the new signal when it is on the opposite side.
+    /// Same-side conflicts are rejected.
+    Flip,
+    /// Close the open trade and take the new signal, whatever its side.
+    CloseAndReopen,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RiskManagerConfig {
+    /// Signals with the same symbol, side, entry and stop loss within this window are duplicates.
+    pub dedupe_window_secs: u64,
+    pub conflict_policy: ConflictPolicy,
+    /// How long a replacing signal waits for the conflicting trade to close before it is rejected.
+    pub close_timeout_secs: u64,
+    pub kill_switch: KillSwitchConfig,
+    pub providers: ProviderPolicy,
+}
+
+impl Default for RiskManagerConfig {
+    fn default() -> Self {
+        Self {
+            dedupe_window_secs: 6 * 60 * 60,
+            conflict_policy: ConflictPolicy::Ignore,
+            close_timeout_secs: 30,
+            kill_switch: KillSwitchConfig::default(),
+            providers: ProviderPolicy::default(),
+        }
+    }
+}
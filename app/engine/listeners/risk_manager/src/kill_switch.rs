@@ -0,0 +1,198 @@
+use serde::Deserialize;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KillSwitchConfig {
+    /// Whether the limits are enforced at startup. Can be changed through the API.
+    pub armed: bool,
+    /// Realised loss per UTC day, in USDT, that halts new entries.
+    pub max_daily_loss: Option<f64>,
+    /// Losing trades in a row that halt new entries.
+    pub max_consecutive_losses: Option<u32>,
+    /// Close every position and cancel every open order when a limit trips.
+    pub flatten_on_trip: bool,
+}
+
+impl Default for KillSwitchConfig {
+    fn default() -> Self {
+        Self {
+            armed: true,
+            max_daily_loss: None,
+            max_consecutive_losses: None,
+            flatten_on_trip: false,
+        }
+    }
+}
+
+/// Tracks realised PnL per UTC day and losing streaks, and decides when entries must stop.
+/// A tripped switch stays tripped, across days too, until it is reset through the API.
+#[derive(Debug)]
+pub struct KillSwitch {
+    armed: bool,
+    tripped: Option<String>,
+    day: u64,
+    daily_pnl: f64,
+    consecutive_losses: u32,
+}
+
+impl KillSwitch {
+    pub fn new(config: &KillSwitchConfig) -> Self {
+        Self {
+            armed: config.armed,
+            tripped: None,
+            day: 0,
+            daily_pnl: 0.0,
+            consecutive_losses: 0,
+        }
+    }
+
+    /// New entries are refused while this returns true.
+    pub fn is_halted(&self) -> bool {
+        self.armed && self.tripped.is_some()
+    }
+
+    pub fn daily_pnl(&self) -> f64 {
+        self.daily_pnl
+    }
+
+    pub fn consecutive_losses(&self) -> u32 {
+        self.consecutive_losses
+    }
+
+    /// Records the realised PnL of a closed trade. Returns the reason if this trips the switch.
+    pub fn record_close(
+        &mut self,
+        pnl: f64,
+        unix_secs: u64,
+        config: &KillSwitchConfig,
+    ) -> Option<String> {
+        let day = unix_secs / SECS_PER_DAY;
+
+        if day != self.day {
+            self.day = day;
+            self.daily_pnl = 0.0;
+        }
+
+        self.daily_pnl += pnl;
+
+        if pnl < 0.0 {
+            self.consecutive_losses += 1;
+        } else {
+            self.consecutive_losses = 0;
+        }
+
+        if !self.armed || self.tripped.is_some() {
+            return None;
+        }
+
+        let reason = if let Some(limit) = config.max_daily_loss
+            && self.daily_pnl <= -limit
+        {
+            format!(
+                "Daily loss limit reached: {:.2} USDT (limit {:.2})",
+                self.daily_pnl, limit
+            )
+        } else if let Some(limit) = config.max_consecutive_losses
+            && self.consecutive_losses >= limit
+        {
+            format!("{} consecutive losing trades", self.consecutive_losses)
+        } else {
+            return None;
+        };
+
+        self.trip(reason.clone());
+        Some(reason)
+    }
+
+    pub fn trip(&mut self, reason: String) {
+        self.tripped = Some(reason);
+    }
+
+    /// Allows entries again. The losing streak starts over; the day's PnL is kept.
+    pub fn reset(&mut self) {
+        self.tripped = None;
+        self.consecutive_losses = 0;
+    }
+
+    pub fn arm(&mut self) {
+        self.armed = true;
+    }
+
+    pub fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_ONE: u64 = 20_000 * SECS_PER_DAY;
+
+    fn config() -> KillSwitchConfig {
+        KillSwitchConfig {
+            max_daily_loss: Some(100.0),
+            max_consecutive_losses: Some(3),
+            ..KillSwitchConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_daily_loss_limit_trips() {
+        let mut switch = KillSwitch::new(&config());
+
+        assert_eq!(switch.record_close(-60.0, DAY_ONE, &config()), None);
+        assert!(
+            switch
+                .record_close(-50.0, DAY_ONE + 10, &config())
+                .is_some()
+        );
+        assert!(switch.is_halted());
+    }
+
+    #[test]
+    fn test_daily_pnl_resets_at_utc_midnight() {
+        let mut switch = KillSwitch::new(&config());
+
+        switch.record_close(-60.0, DAY_ONE + SECS_PER_DAY - 1, &config());
+        switch.record_close(10.0, DAY_ONE + SECS_PER_DAY, &config());
+
+        assert_eq!(switch.daily_pnl(), 10.0);
+        assert!(!switch.is_halted());
+    }
+
+    #[test]
+    fn test_consecutive_losses_trip_and_wins_reset_streak() {
+        let mut switch = KillSwitch::new(&config());
+
+        switch.record_close(-1.0, DAY_ONE, &config());
+        switch.record_close(-1.0, DAY_ONE, &config());
+        switch.record_close(5.0, DAY_ONE, &config());
+        assert_eq!(switch.consecutive_losses(), 0);
+
+        switch.record_close(-1.0, DAY_ONE, &config());
+        switch.record_close(-1.0, DAY_ONE, &config());
+        assert!(switch.record_close(-1.0, DAY_ONE, &config()).is_some());
+    }
+
+    #[test]
+    fn test_disarmed_switch_never_halts() {
+        let mut switch = KillSwitch::new(&config());
+        switch.disarm();
+
+        assert_eq!(switch.record_close(-500.0, DAY_ONE, &config()), None);
+        assert!(!switch.is_halted());
+
+        // Manual trips are ignored too until the switch is armed again.
+        switch.trip("manual".into());
+        assert!(!switch.is_halted());
+
+        switch.arm();
+        assert!(switch.is_halted());
+
+        switch.reset();
+        assert!(!switch.is_halted());
+    }
+}
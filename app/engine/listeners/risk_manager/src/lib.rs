@@ -0,0 +1,435 @@
+pub mod config;
+pub mod kill_switch;
+pub mod providers;
+mod screen;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use binance::client::BinanceClient;
+use domain::clock::now_ms;
+use domain::types::lifecycle::LifecycleEvent;
+use domain::types::symbol::Symbol;
+use domain::types::trade::{
+    CloseTrade, ExitReason, TradeApproved, TradeRejected, TradeRejectionReason, TradeUpdateKind,
+};
+use domain::types::trade_intent::TradeIntent;
+use publisher::types::{ErrorEvent, KillSwitchCommand, PulsgramEvent};
+use publisher::{EventBus, handle_recv_error};
+use uuid::Uuid;
+
+use crate::config::RiskManagerConfig;
+use crate::kill_switch::KillSwitch;
+use crate::providers::{Allowance, ProviderBook};
+use crate::screen::{SignalScreen, Verdict};
+
+const REPLACEMENT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+struct RiskState {
+    screen: SignalScreen,
+    kill_switch: KillSwitch,
+    providers: ProviderBook,
+    // Intents waiting for the conflicting trade (the key) to close, with their risk multiplier.
+    replacements: HashMap<Uuid, (TradeIntent, f64, Instant)>,
+}
+
+/// Screens every `TradeIntent` and turns it into `TradeApproved` or `TradeRejected`.
+///
+/// `accounts` are the enabled Binance accounts by name, the primary first. The kill switch
+/// flattens all of them.
+pub async fn run(
+    bus: Arc<EventBus>,
+    accounts: Vec<(String, Arc<BinanceClient>)>,
+    config: RiskManagerConfig,
+) {
+    println!("Risk Manager running...");
+    let mut rx = bus.subscribe();
+
+    let mut state = RiskState {
+        screen: SignalScreen::default(),
+        kill_switch: KillSwitch::new(&config.kill_switch),
+        providers: ProviderBook::default(),
+        replacements: HashMap::new(),
+    };
+
+    let mut check = tokio::time::interval(REPLACEMENT_CHECK_INTERVAL);
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Ok(event) => match event {
+                    PulsgramEvent::TradeIntent(intent) => screen(&bus, &mut state, &config, intent),
+
+                    PulsgramEvent::TradeUpdate(update) => {
+                        if let TradeUpdateKind::Closed { pnl, .. } = update.kind {
+                            finish(&bus, &mut state, update.intent_id);
+
+                            let tripped = state.kill_switch.record_close(
+                                pnl,
+                                unix_secs(),
+                                &config.kill_switch,
+                            );
+
+                            if let Some(reason) = tripped {
+                                announce(&bus, format!("Kill switch tripped: {}", reason));
+
+                                if config.kill_switch.flatten_on_trip {
+                                    flatten(&bus, &accounts, &state.screen).await;
+                                }
+                            }
+                        }
+                    }
+
+                    PulsgramEvent::TradeRejected(rejected) => {
+                        finish(&bus, &mut state, rejected.intent_id);
+                    }
+
+                    PulsgramEvent::TradeTransition(transition)
+                        if matches!(
+                            transition.event,
+                            LifecycleEvent::Cancel { .. } | LifecycleEvent::Fail { .. }
+                        ) =>
+                    {
+                        finish(&bus, &mut state, transition.intent_id);
+                    }
+
+                    PulsgramEvent::Providers(providers) => {
+                        for (name, reason) in state.providers.replace(providers, &config.providers) {
+                            bus.publish(PulsgramEvent::Error(ErrorEvent {
+                                source: "RiskManager::Providers",
+                                message_text: format!("Provider {} disabled: {}", name, reason),
+                            }));
+                        }
+                    }
+
+                    PulsgramEvent::KillSwitch(command) => {
+                        command_kill_switch(&bus, &accounts, &mut state, command).await;
+                    }
+
+                    _ => {}
+                },
+
+                Err(error) => {
+                    if handle_recv_error("RiskManager RecvError", error, &bus) {
+                        break;
+                    }
+                }
+            },
+
+            _ = check.tick(), if !state.replacements.is_empty() => {
+                let timeout = Duration::from_secs(config.close_timeout_secs);
+
+                state.replacements.retain(|existing, (intent, _, since)| {
+                    if since.elapsed() < timeout {
+                        return true;
+                    }
+
+                    bus.publish(PulsgramEvent::Error(ErrorEvent {
+                        source: "RiskManager::Conflict",
+                        message_text: format!(
+                            "Trade {} was not closed within {}s, dropping replacement {} on {}",
+                            existing, config.close_timeout_secs, intent.intent_id, intent.symbol
+                        ),
+                    }));
+
+                    reject(&bus, intent, TradeRejectionReason::Conflict);
+                    false
+                });
+            }
+        }
+    }
+}
+
+fn screen(bus: &EventBus, state: &mut RiskState, config: &RiskManagerConfig, intent: TradeIntent) {
+    // Checked first, so halted signals are not remembered for dedupe.
+    if state.kill_switch.is_halted() {
+        reject(bus, &intent, TradeRejectionReason::KillSwitch);
+        return;
+    }
+
+    let risk_multiplier = match state.providers.allowance(intent.source, &config.providers) {
+        Allowance::Trade { risk_multiplier } => risk_multiplier,
+        Allowance::Disabled(reason) => {
+            reject(bus, &intent, TradeRejectionReason::ProviderDisabled(reason));
+            return;
+        }
+    };
+
+    match state.screen.screen(&intent, config, Instant::now()) {
+        Verdict::Approve => approve(bus, &mut state.screen, intent, risk_multiplier),
+
+        Verdict::Reject(reason) => reject(bus, &intent, reason),
+
+        Verdict::Replace { existing } => {
+            println!(
+                "[CONFLICT] id={} symbol={} replaces={}",
+                intent.intent_id, intent.symbol, existing
+            );
+
+            let since = match state.replacements.remove(&existing) {
+                // The close is already under way; the newer signal takes the waiting one's place.
+                Some((displaced, _, since)) => {
+                    reject(bus, &displaced, TradeRejectionReason::Conflict);
+                    since
+                }
+                None => {
+                    bus.publish(PulsgramEvent::CloseTrade(CloseTrade {
+                        intent_id: existing,
+                        symbol: intent.symbol,
+                        reason: ExitReason::Conflict,
+                    }));
+                    Instant::now()
+                }
+            };
+
+            state
+                .replacements
+                .insert(existing, (intent, risk_multiplier, since));
+        }
+    }
+}
+
+/// Stops tracking a finished trade and releases any signal that was waiting for it.
+fn finish(bus: &EventBus, state: &mut RiskState, intent_id: Uuid) {
+    state.screen.finish(&intent_id);
+
+    let Some((intent, risk_multiplier, _)) = state.replacements.remove(&intent_id) else {
+        return;
+    };
+
+    if state.kill_switch.is_halted() {
+        reject(bus, &intent, TradeRejectionReason::KillSwitch);
+    } else {
+        approve(bus, &mut state.screen, intent, risk_multiplier);
+    }
+}
+
+async fn command_kill_switch(
+    bus: &EventBus,
+    accounts: &[(String, Arc<BinanceClient>)],
+    state: &mut RiskState,
+    command: KillSwitchCommand,
+) {
+    let switch = &mut state.kill_switch;
+
+    match command {
+        KillSwitchCommand::Arm => {
+            switch.arm();
+            announce(bus, "Kill switch armed".to_string());
+        }
+
+        KillSwitchCommand::Disarm => {
+            switch.disarm();
+            announce(
+                bus,
+                "Kill switch disarmed, loss limits are not enforced".to_string(),
+            );
+        }
+
+        // A manual trip always halts, so it arms the switch as well.
+        KillSwitchCommand::Trip { flatten: close_all } => {
+            switch.arm();
+            switch.trip("Tripped manually".to_string());
+            announce(
+                bus,
+                format!(
+                    "Kill switch tripped manually. Daily PnL: {:.2} USDT, losing streak: {}",
+                    switch.daily_pnl(),
+                    switch.consecutive_losses()
+                ),
+            );
+
+            if close_all {
+                flatten(bus, accounts, &state.screen).await;
+            }
+        }
+
+        KillSwitchCommand::Reset => {
+            switch.reset();
+            announce(bus, "Kill switch reset, new entries allowed".to_string());
+        }
+    }
+}
+
+/// Closes every position and cancels every open order, on every account.
+/// Managed trades are closed through `CloseTrade`, by the trade manager on the primary account and
+/// by the executor on the others, so two market closes never race on a symbol.
+async fn flatten(bus: &EventBus, accounts: &[(String, Arc<BinanceClient>)], screen: &SignalScreen) {
+    let mut managed = HashSet::new();
+
+    for (intent_id, symbol) in screen.active() {
+        managed.insert(symbol);
+
+        bus.publish(PulsgramEvent::CloseTrade(CloseTrade {
+            intent_id,
+            symbol,
+            reason: ExitReason::KillSwitch,
+        }));
+    }
+
+    let mut failures = Vec::new();
+
+    for (name, client) in accounts {
+        for failure in flatten_account(client, &managed).await {
+            failures.push(format!("{}: {}", name, failure));
+        }
+    }
+
+    if failures.is_empty() {
+        announce(bus, "Flattened all positions and open orders".to_string());
+    } else {
+        announce(
+            bus,
+            format!(
+                "Flatten incomplete, check the accounts:\n{}",
+                failures.join("\n")
+            ),
+        );
+    }
+}
+
+/// Closes the positions on symbols without a managed trade and cancels every open order.
+/// Returns what failed.
+async fn flatten_account(client: &BinanceClient, managed: &HashSet<Symbol>) -> Vec<String> {
+    let mut symbols: HashSet<Symbol> = HashSet::new();
+    let mut failures = Vec::new();
+
+    match client.get_position_risk(None).await {
+        Ok(positions) => {
+            for position in positions {
+                let Ok(symbol) = position.symbol.parse::<Symbol>() else {
+                    continue;
+                };
+
+                let amount: f64 = position.position_amt.parse().unwrap_or(0.0);
+
+                if amount == 0.0 {
+                    continue;
+                }
+
+                symbols.insert(symbol);
+
+                if !managed.contains(&symbol)
+                    && let Err(error) = client.close_full_position(symbol).await
+                {
+                    failures.push(format!("close {}: {}", symbol, error));
+                }
+            }
+        }
+        Err(error) => failures.push(format!("positions: {}", error)),
+    }
+
+    match client.get_open_orders(None).await {
+        Ok(orders) => symbols.extend(
+            orders
+                .iter()
+                .filter_map(|order| order.symbol.parse::<Symbol>().ok()),
+        ),
+        Err(error) => failures.push(format!("open orders: {}", error)),
+    }
+
+    for symbol in symbols {
+        if let Err(error) = client.cancel_all_open_orders(symbol).await {
+            failures.push(format!("cancel orders {}: {}", symbol, error));
+        }
+    }
+
+    failures
+}
+
+fn approve(bus: &EventBus, screen: &mut SignalScreen, intent: TradeIntent, risk_multiplier: f64) {
+    screen.track(&intent);
+
+    let mut approved = TradeApproved::new(intent, now_ms());
+    approved.risk_multiplier = risk_multiplier;
+
+    bus.publish(PulsgramEvent::TradeApproved(approved));
+}
+
+fn reject(bus: &EventBus, intent: &TradeIntent, reason: TradeRejectionReason) {
+    bus.publish(PulsgramEvent::TradeRejected(TradeRejected {
+        intent_id: intent.intent_id,
+        symbol: intent.symbol,
+        reason,
+    }));
+}
+
+fn announce(bus: &EventBus, message_text: String) {
+    println!("[KILL_SWITCH] {}", message_text);
+
+    bus.publish(PulsgramEvent::Error(ErrorEvent {
+        source: "RiskManager::KillSwitch",
+        message_text,
+    }));
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::{order_side::OrderSide, symbol::Symbol};
+
+    use super::*;
+    use crate::config::ConflictPolicy;
+
+    fn intent(entry: f64) -> TradeIntent {
+        TradeIntent::builder(&Symbol::BTC)
+            .side(OrderSide::Buy)
+            .entry(entry)
+            .stop_loss(entry * 0.9)
+            .targets(&[entry * 1.1])
+            .timeframe("1h")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_newer_replacement_displaces_the_waiting_one() {
+        let config = RiskManagerConfig {
+            conflict_policy: ConflictPolicy::CloseAndReopen,
+            ..RiskManagerConfig::default()
+        };
+
+        let bus = publisher::new_event_bus();
+        let mut rx = bus.subscribe();
+
+        let mut state = RiskState {
+            screen: SignalScreen::default(),
+            kill_switch: KillSwitch::new(&config.kill_switch),
+            providers: ProviderBook::default(),
+            replacements: HashMap::new(),
+        };
+
+        let (open, waiting, newest) = (intent(100.0), intent(101.0), intent(102.0));
+        let ids = [open.intent_id, waiting.intent_id, newest.intent_id];
+
+        screen(&bus, &mut state, &config, open);
+        screen(&bus, &mut state, &config, waiting);
+        screen(&bus, &mut state, &config, newest);
+        finish(&bus, &mut state, ids[0]);
+
+        let (mut approved, mut rejected, mut closes) = (Vec::new(), Vec::new(), 0);
+
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                PulsgramEvent::TradeApproved(trade) => approved.push(trade.intent_id),
+                PulsgramEvent::TradeRejected(trade) => {
+                    rejected.push((trade.intent_id, trade.reason))
+                }
+                PulsgramEvent::CloseTrade(_) => closes += 1,
+                _ => {}
+            }
+        }
+
+        assert_eq!(approved, vec![ids[0], ids[2]]);
+        assert_eq!(rejected, vec![(ids[1], TradeRejectionReason::Conflict)]);
+        assert_eq!(closes, 1);
+        assert!(state.replacements.is_empty());
+    }
+}
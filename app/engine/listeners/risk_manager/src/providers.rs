@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use domain::types::provider::Provider;
+use serde::Deserialize;
+
+/// How provider stats scale or disable a provider's trades.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ProviderPolicy {
+    /// Stats are only acted on once a provider has this many closed trades in the window.
+    pub min_closed_trades: u32,
+    /// Providers winning less often are disabled.
+    pub min_win_rate: f64,
+    /// Providers failing to parse more often are disabled; their layout has likely changed.
+    pub max_parse_failure_rate: f64,
+    /// Applied on top of the provider's own multiplier while its expectancy is negative.
+    pub losing_multiplier: f64,
+}
+
+impl Default for ProviderPolicy {
+    fn default() -> Self {
+        Self {
+            min_closed_trades: 20,
+            min_win_rate: 0.3,
+            max_parse_failure_rate: 0.5,
+            losing_multiplier: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Allowance {
+    Trade { risk_multiplier: f64 },
+    Disabled(String),
+}
+
+/// The last provider snapshot, keyed by chat.
+#[derive(Debug, Default)]
+pub struct ProviderBook {
+    providers: HashMap<i64, Provider>,
+}
+
+impl ProviderBook {
+    /// Replaces the snapshot. Returns the providers the stats just disabled, with the reason.
+    pub fn replace(
+        &mut self,
+        providers: Vec<Provider>,
+        policy: &ProviderPolicy,
+    ) -> Vec<(String, String)> {
+        let mut disabled = Vec::new();
+
+        for provider in &providers {
+            let was_allowed = self
+                .providers
+                .get(&provider.chat_id)
+                .is_none_or(|previous| {
+                    matches!(allowance(previous, policy), Allowance::Trade { .. })
+                });
+
+            if let Allowance::Disabled(reason) = allowance(provider, policy)
+                && was_allowed
+                && provider.enabled
+            {
+                disabled.push((provider.name.clone(), reason));
+            }
+        }
+
+        self.providers = providers
+            .into_iter()
+            .map(|provider| (provider.chat_id, provider))
+            .collect();
+
+        disabled
+    }
+
+    /// Signals from unknown sources are traded at full risk.
+    pub fn allowance(&self, source: Option<i64>, policy: &ProviderPolicy) -> Allowance {
+        match source.and_then(|chat_id| self.providers.get(&chat_id)) {
+            Some(provider) => allowance(provider, policy),
+            None => Allowance::Trade {
+                risk_multiplier: 1.0,
+            },
+        }
+    }
+}
+
+fn allowance(provider: &Provider, policy: &ProviderPolicy) -> Allowance {
+    if !provider.enabled {
+        return Allowance::Disabled("disabled in the registry".to_string());
+    }
+
+    if provider.risk_multiplier <= 0.0 {
+        return Allowance::Disabled("risk multiplier is zero".to_string());
+    }
+
+    let stats = &provider.stats;
+
+    if stats.closed_trades < policy.min_closed_trades {
+        return Allowance::Trade {
+            risk_multiplier: provider.risk_multiplier,
+        };
+    }
+
+    if stats.parse_failure_rate > policy.max_parse_failure_rate {
+        return Allowance::Disabled(format!(
+            "{:.0}% of its signals failed to parse",
+            stats.parse_failure_rate * 100.0
+        ));
+    }
+
+    if stats.win_rate < policy.min_win_rate {
+        return Allowance::Disabled(format!(
+            "win rate {:.0}% over {} trades",
+            stats.win_rate * 100.0,
+            stats.closed_trades
+        ));
+    }
+
+    let risk_multiplier = if stats.expectancy < 0.0 {
+        provider.risk_multiplier * policy.losing_multiplier
+    } else {
+        provider.risk_multiplier
+    };
+
+    Allowance::Trade { risk_multiplier }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::provider::ProviderStats;
+
+    use super::*;
+
+    fn provider(chat_id: i64, stats: ProviderStats) -> Provider {
+        Provider {
+            name: format!("provider {}", chat_id),
+            chat_id,
+            parser: "labeled".into(),
+            enabled: true,
+            weight: 1.0,
+            risk_multiplier: 1.0,
+            stats,
+        }
+    }
+
+    fn stats(closed_trades: u32, win_rate: f64, expectancy: f64) -> ProviderStats {
+        ProviderStats {
+            closed_trades,
+            win_rate,
+            expectancy,
+            ..ProviderStats::default()
+        }
+    }
+
+    #[test]
+    fn test_stats_are_ignored_until_enough_trades() {
+        let policy = ProviderPolicy::default();
+        let mut book = ProviderBook::default();
+
+        book.replace(vec![provider(1, stats(5, 0.0, -10.0))], &policy);
+
+        assert_eq!(
+            book.allowance(Some(1), &policy),
+            Allowance::Trade {
+                risk_multiplier: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_negative_expectancy_scales_risk_down() {
+        let policy = ProviderPolicy::default();
+        let mut book = ProviderBook::default();
+
+        book.replace(vec![provider(1, stats(30, 0.4, -1.5))], &policy);
+
+        assert_eq!(
+            book.allowance(Some(1), &policy),
+            Allowance::Trade {
+                risk_multiplier: 0.5
+            }
+        );
+    }
+
+    #[test]
+    fn test_low_win_rate_disables_once() {
+        let policy = ProviderPolicy::default();
+        let mut book = ProviderBook::default();
+
+        assert!(
+            book.replace(vec![provider(1, stats(30, 0.5, 2.0))], &policy)
+                .is_empty()
+        );
+
+        let disabled = book.replace(vec![provider(1, stats(30, 0.2, -3.0))], &policy);
+        assert_eq!(disabled.len(), 1);
+
+        assert!(matches!(
+            book.allowance(Some(1), &policy),
+            Allowance::Disabled(_)
+        ));
+
+        // Still disabled on the next refresh, but not announced again.
+        assert!(
+            book.replace(vec![provider(1, stats(31, 0.2, -3.0))], &policy)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_unknown_source_trades_at_full_risk() {
+        let policy = ProviderPolicy::default();
+        let book = ProviderBook::default();
+
+        assert_eq!(
+            book.allowance(Some(7), &policy),
+            Allowance::Trade {
+                risk_multiplier: 1.0
+            }
+        );
+        assert_eq!(
+            book.allowance(None, &policy),
+            Allowance::Trade {
+                risk_multiplier: 1.0
+            }
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use domain::types::{
+    order_side::OrderSide, symbol::Symbol, trade::TradeRejectionReason, trade_intent::TradeIntent,
+};
+use uuid::Uuid;
+
+use crate::config::{ConflictPolicy, RiskManagerConfig};
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Approve,
+    Reject(TradeRejectionReason),
+    /// Approve once the conflicting trade `existing` has been closed.
+    Replace {
+        existing: Uuid,
+    },
+}
+
+// Prices are compared by bit pattern: a repost carries the exact same text, so it
+// parses to the exact same f64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SignalKey {
+    symbol: Symbol,
+    side: OrderSide,
+    entry: u64,
+    stop_loss: u64,
+}
+
+impl From<&TradeIntent> for SignalKey {
+    fn from(intent: &TradeIntent) -> Self {
+        Self {
+            symbol: intent.symbol,
+            side: intent.side,
+            entry: intent.entry.to_bits(),
+            stop_loss: intent.stop_loss.to_bits(),
+        }
+    }
+}
+
+/// Remembers recent signals and the trades currently active, to screen new intents.
+#[derive(Debug, Default)]
+pub struct SignalScreen {
+    recent: Vec<(SignalKey, Instant)>,
+    active: HashMap<Uuid, (Symbol, OrderSide)>,
+}
+
+impl SignalScreen {
+    pub fn screen(
+        &mut self,
+        intent: &TradeIntent,
+        config: &RiskManagerConfig,
+        now: Instant,
+    ) -> Verdict {
+        let window = Duration::from_secs(config.dedupe_window_secs);
+        self.recent
+            .retain(|(_, seen_at)| now.duration_since(*seen_at) < window);
+
+        let key = SignalKey::from(intent);
+
+        if self.recent.iter().any(|(seen, _)| *seen == key) {
+            return Verdict::Reject(TradeRejectionReason::Duplicate);
+        }
+
+        self.recent.push((key, now));
+
+        let Some((&existing, &(_, existing_side))) = self
+            .active
+            .iter()
+            .find(|(_, (symbol, _))| *symbol == intent.symbol)
+        else {
+            return Verdict::Approve;
+        };
+
+        let is_opposite = existing_side != intent.side;
+
+        match config.conflict_policy {
+            ConflictPolicy::Flip if is_opposite => Verdict::Replace { existing },
+            ConflictPolicy::CloseAndReopen => Verdict::Replace { existing },
+            _ => Verdict::Reject(TradeRejectionReason::Conflict),
+        }
+    }
+
+    /// Starts tracking an approved trade, so later signals on its symbol conflict with it.
+    pub fn track(&mut self, intent: &TradeIntent) {
+        self.active
+            .insert(intent.intent_id, (intent.symbol, intent.side));
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = (Uuid, Symbol)> + '_ {
+        self.active.iter().map(|(id, (symbol, _))| (*id, *symbol))
+    }
+
+    pub fn finish(&mut self, intent_id: &Uuid) {
+        self.active.remove(intent_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(symbol: Symbol, side: OrderSide, entry: f64, stop_loss: f64) -> TradeIntent {
+        TradeIntent::builder(&symbol)
+            .side(side)
+            .entry(entry)
+            .stop_loss(stop_loss)
+            .targets(&[entry * 1.1])
+            .timeframe("1h")
+            .build()
+            .unwrap()
+    }
+
+    fn config(conflict_policy: ConflictPolicy) -> RiskManagerConfig {
+        RiskManagerConfig {
+            conflict_policy,
+            ..RiskManagerConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_repost_within_window_is_duplicate() {
+        let mut screen = SignalScreen::default();
+        let config = config(ConflictPolicy::Ignore);
+        let now = Instant::now();
+
+        let first = intent(Symbol::BTC, OrderSide::Buy, 100.0, 90.0);
+        let repost = intent(Symbol::BTC, OrderSide::Buy, 100.0, 90.0);
+
+        assert_eq!(screen.screen(&first, &config, now), Verdict::Approve);
+        assert_eq!(
+            screen.screen(&repost, &config, now + Duration::from_secs(60)),
+            Verdict::Reject(TradeRejectionReason::Duplicate)
+        );
+    }
+
+    #[test]
+    fn test_repost_after_window_is_not_duplicate() {
+        let mut screen = SignalScreen::default();
+        let config = config(ConflictPolicy::Ignore);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(config.dedupe_window_secs);
+
+        let signal = intent(Symbol::BTC, OrderSide::Buy, 100.0, 90.0);
+
+        screen.screen(&signal, &config, now);
+
+        assert_eq!(screen.screen(&signal, &config, later), Verdict::Approve);
+    }
+
+    #[test]
+    fn test_conflict_policies() {
+        let now = Instant::now();
+        let long = intent(Symbol::ETH, OrderSide::Buy, 100.0, 90.0);
+        let short = intent(Symbol::ETH, OrderSide::Sell, 100.0, 110.0);
+        let other_long = intent(Symbol::ETH, OrderSide::Buy, 101.0, 95.0);
+
+        let cases = [
+            (ConflictPolicy::Ignore, &short, false),
+            (ConflictPolicy::Flip, &short, true),
+            (ConflictPolicy::Flip, &other_long, false),
+            (ConflictPolicy::CloseAndReopen, &other_long, true),
+        ];
+
+        for (policy, incoming, replaces) in cases {
+            let mut screen = SignalScreen::default();
+            screen.track(&long);
+
+            let expected = if replaces {
+                Verdict::Replace {
+                    existing: long.intent_id,
+                }
+            } else {
+                Verdict::Reject(TradeRejectionReason::Conflict)
+            };
+
+            assert_eq!(screen.screen(incoming, &config(policy), now), expected);
+        }
+    }
+
+    #[test]
+    fn test_finished_trade_no_longer_conflicts() {
+        let mut screen = SignalScreen::default();
+        let long = intent(Symbol::SOL, OrderSide::Buy, 100.0, 90.0);
+
+        screen.track(&long);
+        screen.finish(&long.intent_id);
+
+        let short = intent(Symbol::SOL, OrderSide::Sell, 100.0, 110.0);
+
+        assert_eq!(
+            screen.screen(&short, &config(ConflictPolicy::Ignore), Instant::now()),
+            Verdict::Approve
+        );
+    }
+}
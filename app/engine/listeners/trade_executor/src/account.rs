@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use binance::client::BinanceClient;
+use binance::network::Network;
+use domain::types::trade::TradeApproved;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "sizing", rename_all = "snake_case")]
+pub enum Sizing {
+    /// Smallest order the exchange accepts for the symbol.
+    Minimum,
+    /// Fixed position value, in USDT.
+    Notional { usdt: f64 },
+    /// Sized so that hitting the signal's stop loss loses `usdt`.
+    Risk { usdt: f64 },
+}
+
+impl Sizing {
+    /// Quantity to order when entering at `price`. None means the exchange minimum.
+    /// The trade's risk multiplier scales every sizing but the minimum.
+    pub fn quantity(&self, trade: &TradeApproved, price: f64) -> Option<f64> {
+        match *self {
+            Sizing::Minimum => None,
+            Sizing::Notional { usdt } => Some(usdt * trade.risk_multiplier / price),
+            Sizing::Risk { usdt } => {
+                let usdt = usdt * trade.risk_multiplier;
+                let risk_per_unit = (price - trade.stop_loss).abs();
+                (risk_per_unit > 0.0).then(|| usdt / risk_per_unit)
+            }
+        }
+    }
+}
+
+/// A Binance account trades are executed on. Credentials are read from the environment.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountConfig {
+    pub name: String,
+    /// Environment variables holding the API key and secret.
+    pub api_key_env: String,
+    pub api_secret_env: String,
+    #[serde(default = "default_network")]
+    pub network: Network,
+    #[serde(default = "default_sizing")]
+    pub sizing: Sizing,
+    /// Source peer IDs traded on this account. Every source when empty.
+    #[serde(default)]
+    pub sources: Vec<i64>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_network() -> Network {
+    Network::Testnet
+}
+
+fn default_sizing() -> Sizing {
+    Sizing::Minimum
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl AccountConfig {
+    /// Used when no account is configured.
+    pub fn testnet() -> Self {
+        Self {
+            name: "testnet".to_string(),
+            api_key_env: "BINANCE_API_KEY_TEST".to_string(),
+            api_secret_env: "BINANCE_API_SECRET_TEST".to_string(),
+            network: Network::Testnet,
+            sizing: Sizing::Minimum,
+            sources: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    pub fn is_eligible(&self, source: Option<i64>) -> bool {
+        self.enabled
+            && (self.sources.is_empty()
+                || source.is_some_and(|source| self.sources.contains(&source)))
+    }
+}
+
+pub struct Account {
+    pub config: AccountConfig,
+    pub client: Arc<BinanceClient>,
+}
+
+impl Account {
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::{order_side::OrderSide, symbol::Symbol, trade_intent::TradeIntent};
+
+    use super::*;
+
+    fn trade() -> TradeApproved {
+        TradeIntent::builder(&Symbol::BTC)
+            .side(OrderSide::Sell)
+            .entry(100.0)
+            .stop_loss(104.0)
+            .targets(&[90.0])
+            .timeframe("1h")
+            .build()
+            .map(|intent| TradeApproved::new(intent, 0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_risk_sizing_uses_distance_to_stop_from_fill_price() {
+        let quantity = Sizing::Risk { usdt: 10.0 }.quantity(&trade(), 99.0);
+
+        assert_eq!(quantity, Some(2.0));
+        assert_eq!(Sizing::Minimum.quantity(&trade(), 99.0), None);
+        assert_eq!(
+            Sizing::Notional { usdt: 500.0 }.quantity(&trade(), 100.0),
+            Some(5.0)
+        );
+    }
+
+    #[test]
+    fn test_risk_multiplier_scales_size() {
+        let mut trade = trade();
+        trade.risk_multiplier = 0.5;
+
+        assert_eq!(
+            Sizing::Risk { usdt: 10.0 }.quantity(&trade, 99.0),
+            Some(1.0)
+        );
+        assert_eq!(
+            Sizing::Notional { usdt: 500.0 }.quantity(&trade, 100.0),
+            Some(2.5)
+        );
+    }
+
+    #[test]
+    fn test_account_eligibility_by_source() {
+        let mut config = AccountConfig::testnet();
+        assert!(config.is_eligible(None));
+
+        config.sources = vec![-100];
+        assert!(config.is_eligible(Some(-100)));
+        assert!(!config.is_eligible(Some(-200)));
+        assert!(!config.is_eligible(None));
+
+        config.sources.clear();
+        config.enabled = false;
+        assert!(!config.is_eligible(Some(-100)));
+    }
+}
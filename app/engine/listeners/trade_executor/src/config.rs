@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TradeExecutorConfig {
+    pub entry: EntryConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum EntryPolicy {
+    /// Enter immediately at market.
+    Market,
+    /// Rest a limit order at the signal's entry price.
+    Limit,
+    /// Enter at market while price is within `tolerance_percent` of entry,
+    /// otherwise rest a limit order at the edge of the zone.
+    Zone { tolerance_percent: f64 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct EntryConfig {
+    pub default: EntryPolicy,
+    /// Keyed by source peer ID.
+    pub by_source: HashMap<String, EntryPolicy>,
+    /// A resting entry is cancelled after this many candles of the signal's timeframe.
+    pub expiry_candles: u32,
+}
+
+impl Default for EntryConfig {
+    fn default() -> Self {
+        Self {
+            default: EntryPolicy::Market,
+            by_source: HashMap::new(),
+            expiry_candles: 3,
+        }
+    }
+}
+
+impl EntryConfig {
+    pub fn policy_for(&self, source: Option<i64>) -> &EntryPolicy {
+        source
+            .and_then(|source| self.by_source.get(&source.to_string()))
+            .unwrap_or(&self.default)
+    }
+}
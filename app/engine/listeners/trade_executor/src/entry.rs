@@ -0,0 +1,139 @@
+use domain::types::trade::{TradeApproved, TradeRejectionReason};
+
+use crate::config::EntryPolicy;
+
+#[derive(Debug, PartialEq)]
+pub enum EntryOrder {
+    Market,
+    Limit { price: f64 },
+}
+
+/// Decides how to enter `trade` with price currently at `price`.
+/// Refuses to chase once price has already reached TP1 or the stop loss.
+pub fn plan_entry(
+    trade: &TradeApproved,
+    policy: &EntryPolicy,
+    price: f64,
+) -> Result<EntryOrder, TradeRejectionReason> {
+    let is_long = trade.side.is_long();
+
+    // `beyond(a, b)`: a is at or past b in the trade's favourable direction.
+    let beyond = |a: f64, b: f64| if is_long { a >= b } else { a <= b };
+
+    if let Some(&first_target) = trade.targets.first()
+        && beyond(price, first_target)
+    {
+        return Err(TradeRejectionReason::MissedFirstTarget);
+    }
+
+    if beyond(trade.stop_loss, price) {
+        return Err(TradeRejectionReason::PastStopLoss);
+    }
+
+    let order = match *policy {
+        EntryPolicy::Market => EntryOrder::Market,
+
+        EntryPolicy::Limit => EntryOrder::Limit { price: trade.entry },
+
+        EntryPolicy::Zone { tolerance_percent } => {
+            let offset = trade.entry * tolerance_percent / 100.0;
+            let worst_price = if is_long {
+                trade.entry + offset
+            } else {
+                trade.entry - offset
+            };
+
+            // Anything up to the zone's far edge is taken at market.
+            let above_zone = if is_long {
+                price > worst_price
+            } else {
+                price < worst_price
+            };
+
+            if above_zone {
+                EntryOrder::Limit { price: worst_price }
+            } else {
+                EntryOrder::Market
+            }
+        }
+    };
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::{order_side::OrderSide, symbol::Symbol};
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn trade(side: OrderSide, entry: f64, targets: &[f64], stop_loss: f64) -> TradeApproved {
+        TradeApproved {
+            intent_id: Uuid::new_v4(),
+            symbol: Symbol::BTC,
+            side,
+            entry,
+            targets: targets.to_vec(),
+            timeframe: "1h".to_string(),
+            stop_loss,
+            source: None,
+            signal_time_ms: 0,
+            approved_at_ms: 0,
+            risk_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_refuses_to_chase_past_tp1_or_stop() {
+        let long = trade(OrderSide::Buy, 100.0, &[110.0, 120.0], 90.0);
+
+        assert!(matches!(
+            plan_entry(&long, &EntryPolicy::Market, 111.0),
+            Err(TradeRejectionReason::MissedFirstTarget)
+        ));
+        assert!(matches!(
+            plan_entry(&long, &EntryPolicy::Limit, 89.0),
+            Err(TradeRejectionReason::PastStopLoss)
+        ));
+
+        let short = trade(OrderSide::Sell, 100.0, &[90.0], 110.0);
+
+        assert!(matches!(
+            plan_entry(&short, &EntryPolicy::Market, 90.0),
+            Err(TradeRejectionReason::MissedFirstTarget)
+        ));
+    }
+
+    #[test]
+    fn test_limit_rests_at_entry() {
+        let long = trade(OrderSide::Buy, 100.0, &[110.0], 90.0);
+
+        assert_eq!(
+            plan_entry(&long, &EntryPolicy::Limit, 104.0).unwrap(),
+            EntryOrder::Limit { price: 100.0 }
+        );
+    }
+
+    #[test]
+    fn test_zone_enters_at_market_inside_zone_only() {
+        let zone = EntryPolicy::Zone {
+            tolerance_percent: 2.0,
+        };
+        let long = trade(OrderSide::Buy, 100.0, &[110.0], 90.0);
+
+        assert_eq!(plan_entry(&long, &zone, 101.5).unwrap(), EntryOrder::Market);
+        assert_eq!(plan_entry(&long, &zone, 95.0).unwrap(), EntryOrder::Market);
+        assert_eq!(
+            plan_entry(&long, &zone, 105.0).unwrap(),
+            EntryOrder::Limit { price: 102.0 }
+        );
+
+        let short = trade(OrderSide::Sell, 100.0, &[90.0], 110.0);
+
+        assert_eq!(
+            plan_entry(&short, &zone, 95.0).unwrap(),
+            EntryOrder::Limit { price: 98.0 }
+        );
+    }
+}
@@ -1,55 +1,707 @@
+pub mod account;
+pub mod config;
+mod entry;
+mod protection;
+mod quality;
 mod utils;
 
-use binance::client::BinanceClient;
+use binance::errors::BinanceError;
+use binance::response_types::FuturesOrderResponse;
+use domain::clock::now_ms;
+use domain::types::execution::{
+    AccountExecution, AccountOutcome, ExecutionQuality, ExecutionReport,
+};
+use domain::types::lifecycle::{LifecycleEvent, TradeTransition};
+use domain::types::signal_update::{SignalUpdateKind, StopLevel};
+use domain::types::timeframe::Timeframe;
+use domain::types::trade::{CloseTrade, ExitReason, TradeApproved, TradeOpened, TradeRejected};
 use publisher::types::{ErrorEvent, PulsgramEvent};
 use publisher::{EventBus, handle_recv_error};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use uuid::Uuid;
 
-use crate::utils::{OrderExecutionStatus, format_trade_error, handle_order_status};
+use crate::account::Account;
+use crate::config::{EntryConfig, TradeExecutorConfig};
+use crate::entry::{EntryOrder, plan_entry};
+use crate::protection::ProtectiveOrders;
+use crate::quality::ExecutionTracker;
+use crate::utils::{
+    OrderExecutionStatus, entry_outcome, filled_part, format_trade_error, handle_order_status,
+    opened_trade,
+};
 
-pub async fn run(bus: Arc<EventBus>, client: Arc<BinanceClient>) {
+const PENDING_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Used when the signal's timeframe cannot be parsed, or its expiry is out of range.
+const FALLBACK_ENTRY_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+struct PendingEntry {
+    trade: TradeApproved,
+    order_id: i64,
+    sent_at_ms: i64,
+    expires_at: Instant,
+    /// Set when the trade was asked to close before its entry filled.
+    close_reason: Option<ExitReason>,
+}
+
+/// Executes every approved trade on each eligible account, in order.
+///
+/// The first account is the primary one: its orders drive the trade lifecycle (`TradeOpened`,
+/// `TradeTransition`, `ExecutionReport`) that the trade and risk managers follow. Every account,
+/// primary included, reports its own result as an `AccountExecution`. Positions opened on the
+/// other accounts are not managed by the trade manager; each gets an exchange-side stop loss and
+/// take profit as soon as it is filled, which follow the provider's updates and close requests.
+pub async fn run(bus: Arc<EventBus>, accounts: Vec<Account>, config: TradeExecutorConfig) {
     println!("Trade Executor running...");
     let mut rx = bus.subscribe();
 
+    // Keyed by account index and intent.
+    let mut pending: HashMap<(usize, Uuid), PendingEntry> = HashMap::new();
+    let mut quality = ExecutionTracker::default();
+    let mut protective = ProtectiveOrders::default();
+    let mut poll = tokio::time::interval(PENDING_POLL_INTERVAL);
+
     loop {
-        match rx.recv().await {
-            Ok(event) => match event {
-                PulsgramEvent::TradeApproved(trade) => {
-                    match client
-                        .place_minimum_market_order(trade.symbol, &trade.side)
-                        .await
-                    {
-                        //TODO: Publish event for persistance worker to save it to db if filled/partially filled.
-                        Ok(response) => {
-                            let status = OrderExecutionStatus::from(&response);
-                            handle_order_status(&trade, status);
+        tokio::select! {
+            received = rx.recv() => match received {
+                Ok(event) => match event {
+                    PulsgramEvent::TradeIntent(intent) => quality.on_signal(&intent),
+
+                    PulsgramEvent::PriceUpdate(tick) => quality.observe(&tick),
+
+                    PulsgramEvent::TradeApproved(trade) => {
+                        for (index, account) in accounts.iter().enumerate() {
+                            let venue = Venue { account, primary: index == 0 };
+
+                            if !account.config.is_eligible(trade.source) {
+                                skip(&bus, &venue, &trade);
+                                continue;
+                            }
+
+                            let entry = enter(
+                                &bus,
+                                &config.entry,
+                                &mut quality,
+                                &mut protective,
+                                &venue,
+                                trade.clone(),
+                            );
+
+                            if let Some(entry) = entry.await {
+                                pending.insert((index, trade.intent_id), entry);
+                            }
                         }
+                    }
 
-                        Err(error) => {
-                            let message = format_trade_error(&trade, &error);
-                            bus.publish(PulsgramEvent::Error(ErrorEvent {
-                                source: "TradeExecutor",
-                                message_text: message,
-                            }));
+                    // Open trades on the primary account are handled by the trade manager.
+                    PulsgramEvent::SignalUpdate(update) => {
+                        for ((_, intent_id), entry) in pending.iter_mut() {
+                            if *intent_id == update.intent_id {
+                                amend_pending(entry, &update.kind);
+                            }
                         }
+
+                        protective
+                            .follow(&bus, &accounts, update.intent_id, &update.kind)
+                            .await;
+                    }
+
+                    // A trade replaced or flattened before its entry filled: the entry is
+                    // cancelled now, so its outcome reaches the risk manager without delay.
+                    // Trades open on secondary accounts are closed along with the primary's.
+                    PulsgramEvent::CloseTrade(request) => {
+                        let keys = request_close(&mut pending, &request);
+
+                        settle_pending(
+                            &bus,
+                            &accounts,
+                            &mut quality,
+                            &mut protective,
+                            &mut pending,
+                            keys,
+                        )
+                        .await;
+
+                        protective
+                            .follow(&bus, &accounts, request.intent_id, &SignalUpdateKind::CloseAll)
+                            .await;
+                    }
+
+                    PulsgramEvent::TradeRejected(trade) => {
+                        println!(
+                            "[REJECTED] id={} symbol={} reason={:?}",
+                            trade.intent_id, trade.symbol, trade.reason,
+                        );
                     }
-                }
 
-                PulsgramEvent::TradeRejected(trade) => {
-                    println!(
-                        "[REJECTED] id={} symbol={} reason={:?}",
-                        trade.intent_id, trade.symbol, trade.reason,
-                    );
+                    _ => {}
+                },
+
+                Err(error) => {
+                    if handle_recv_error("PerpSignals RecvError", error, &bus) {
+                        break;
+                    }
                 }
+            },
+
+            _ = poll.tick(), if !pending.is_empty() || !protective.is_empty() => {
+                let keys: Vec<(usize, Uuid)> = pending.keys().copied().collect();
+
+                settle_pending(&bus, &accounts, &mut quality, &mut protective, &mut pending, keys)
+                    .await;
+
+                protective.sweep(&accounts).await;
+            }
+        }
+    }
+}
+
+/// Checks the given resting entries, dropping those no longer on the book.
+async fn settle_pending(
+    bus: &EventBus,
+    accounts: &[Account],
+    quality: &mut ExecutionTracker,
+    protective: &mut ProtectiveOrders,
+    pending: &mut HashMap<(usize, Uuid), PendingEntry>,
+    keys: Vec<(usize, Uuid)>,
+) {
+    let now = Instant::now();
+
+    for key @ (index, _) in keys {
+        let venue = Venue {
+            account: &accounts[index],
+            primary: index == 0,
+        };
+
+        let still_pending = match pending.get(&key) {
+            Some(entry) => check_pending(bus, quality, protective, &venue, entry, now).await,
+            None => continue,
+        };
+
+        if !still_pending {
+            pending.remove(&key);
+        }
+    }
+}
+
+/// Expires every resting entry of the trade to close. Returns their keys.
+fn request_close(
+    pending: &mut HashMap<(usize, Uuid), PendingEntry>,
+    request: &CloseTrade,
+) -> Vec<(usize, Uuid)> {
+    pending
+        .iter_mut()
+        .filter(|((_, intent_id), _)| *intent_id == request.intent_id)
+        .map(|(key, entry)| {
+            entry.expires_at = Instant::now();
+            entry.close_reason = Some(request.reason);
+            *key
+        })
+        .collect()
+}
 
-                _ => {}
+/// Account an order is placed on, and whether it drives the trade lifecycle.
+struct Venue<'a> {
+    account: &'a Account,
+    primary: bool,
+}
+
+fn skip(bus: &EventBus, venue: &Venue, trade: &TradeApproved) {
+    // Closes the lifecycle, so the risk manager stops tracking the trade.
+    if venue.primary {
+        publish_transition(
+            bus,
+            trade,
+            LifecycleEvent::Cancel {
+                reason: format!("Source not enabled on account {}", venue.account.name()),
             },
+        );
+    }
+}
 
-            Err(error) => {
-                if handle_recv_error("PerpSignals RecvError", error, &bus) {
-                    break;
-                }
+/// Places the entry order for `trade`. Returns the entry if it is left resting on the book.
+async fn enter(
+    bus: &EventBus,
+    config: &EntryConfig,
+    quality: &mut ExecutionTracker,
+    protective: &mut ProtectiveOrders,
+    venue: &Venue<'_>,
+    trade: TradeApproved,
+) -> Option<PendingEntry> {
+    let client = &venue.account.client;
+
+    let price = match client.get_current_price(trade.symbol).await {
+        Ok(price) => price,
+        Err(error) => {
+            report_failure(bus, venue, &trade, &error);
+            return None;
+        }
+    };
+
+    let order = match plan_entry(&trade, config.policy_for(trade.source), price) {
+        Ok(order) => order,
+        Err(reason) => {
+            publish_account(bus, venue, &trade, AccountOutcome::Rejected(reason.clone()));
+
+            if venue.primary {
+                bus.publish(PulsgramEvent::TradeRejected(TradeRejected {
+                    intent_id: trade.intent_id,
+                    symbol: trade.symbol,
+                    reason,
+                }));
             }
+            return None;
         }
+    };
+
+    let sizing = &venue.account.config.sizing;
+    let sent_at_ms = now_ms();
+
+    let result = match order {
+        EntryOrder::Market => match sizing.quantity(&trade, price) {
+            Some(quantity) => {
+                client
+                    .place_market_order(trade.symbol, &trade.side, quantity)
+                    .await
+            }
+            None => {
+                client
+                    .place_minimum_market_order(trade.symbol, &trade.side)
+                    .await
+            }
+        },
+        EntryOrder::Limit { price } => match sizing.quantity(&trade, price) {
+            Some(quantity) => {
+                client
+                    .place_limit_order(trade.symbol, &trade.side, quantity, price)
+                    .await
+            }
+            None => {
+                client
+                    .place_minimum_limit_order(trade.symbol, &trade.side, price)
+                    .await
+            }
+        },
+    };
+
+    //TODO: Publish event for persistance worker to save it to db if filled/partially filled.
+    let response = match result {
+        Ok(response) => response,
+        Err(error) => {
+            report_failure(bus, venue, &trade, &error);
+            return None;
+        }
+    };
+
+    if venue.primary {
+        publish_transition(
+            bus,
+            &trade,
+            LifecycleEvent::EntrySubmitted {
+                order_id: response.order_id,
+            },
+        );
+    }
+
+    let status = OrderExecutionStatus::from(&response);
+    let is_resting = matches!(
+        status,
+        OrderExecutionStatus::New | OrderExecutionStatus::PartiallyFilled
+    );
+
+    let fill = Fill {
+        sent_at_ms,
+        filled_at_ms: response.update_time,
+    };
+
+    let opened = publish_outcome(bus, quality, venue, &trade, &status, &fill);
+    handle_order_status(&trade, status);
+
+    if let Some(opened) = opened
+        && !venue.primary
+    {
+        protective.protect(bus, venue.account, &opened).await;
+    }
+
+    if !is_resting {
+        return None;
+    }
+
+    publish_account(
+        bus,
+        venue,
+        &trade,
+        AccountOutcome::Resting {
+            order_id: response.order_id,
+        },
+    );
+
+    let expiry = trade
+        .timeframe
+        .parse::<Timeframe>()
+        .map(|timeframe| timeframe.candles(config.expiry_candles))
+        .unwrap_or(FALLBACK_ENTRY_EXPIRY);
+
+    Some(PendingEntry {
+        order_id: response.order_id,
+        sent_at_ms,
+        expires_at: Instant::now()
+            .checked_add(expiry)
+            .unwrap_or_else(|| Instant::now() + FALLBACK_ENTRY_EXPIRY),
+        close_reason: None,
+        trade,
+    })
+}
+
+/// Applies a provider's follow-up to a resting entry. A withdrawn signal expires the entry, so
+/// the next poll cancels it; new levels are carried into the opened trade. The entry order
+/// itself is not repriced.
+fn amend_pending(entry: &mut PendingEntry, kind: &SignalUpdateKind) {
+    match kind {
+        SignalUpdateKind::Cancel | SignalUpdateKind::CloseAll => {
+            entry.expires_at = Instant::now();
+        }
+
+        SignalUpdateKind::AmendSignal {
+            targets, stop_loss, ..
+        } => {
+            entry.trade.targets = targets.clone();
+            entry.trade.stop_loss = *stop_loss;
+        }
+
+        SignalUpdateKind::MoveStop {
+            to: StopLevel::Price(price),
+        } => entry.trade.stop_loss = *price,
+
+        // Break-even and partial closes only make sense for an open trade.
+        SignalUpdateKind::MoveStop {
+            to: StopLevel::Entry,
+        }
+        | SignalUpdateKind::ClosePartial { .. } => {}
+    }
+}
+
+/// Polls a resting entry and cancels it once expired. Returns false when it is no longer pending.
+async fn check_pending(
+    bus: &EventBus,
+    quality: &mut ExecutionTracker,
+    protective: &mut ProtectiveOrders,
+    venue: &Venue<'_>,
+    entry: &PendingEntry,
+    now: Instant,
+) -> bool {
+    let trade = &entry.trade;
+    let client = &venue.account.client;
+
+    let response = if now >= entry.expires_at {
+        client.cancel_order(trade.symbol, entry.order_id).await
+    } else {
+        client.get_order(trade.symbol, entry.order_id).await
+    };
+
+    let response = match response {
+        Ok(response) => response,
+        Err(error) => {
+            // Retried on the next poll; the order stays on the book meanwhile.
+            eprintln!(
+                "[PENDING] account={} id={} order_id={} poll failed: {}",
+                venue.account.name(),
+                trade.intent_id,
+                entry.order_id,
+                error
+            );
+            return true;
+        }
+    };
+
+    let status = OrderExecutionStatus::from(&response);
+
+    if matches!(
+        status,
+        OrderExecutionStatus::New | OrderExecutionStatus::PartiallyFilled
+    ) {
+        return true;
+    }
+
+    if now >= entry.expires_at {
+        println!(
+            "[EXPIRED] account={} id={} order_id={} entry not filled in time",
+            venue.account.name(),
+            trade.intent_id,
+            entry.order_id
+        );
+    }
+
+    let fill = Fill {
+        sent_at_ms: entry.sent_at_ms,
+        filled_at_ms: response.update_time,
+    };
+
+    let opened = publish_cancelled_entry(bus, quality, venue, trade, &response, &status, &fill);
+    handle_order_status(trade, status);
+
+    if let Some(opened) = opened {
+        match entry.close_reason {
+            // Whatever filled before the cancel is closed as well.
+            Some(reason) => close_filled(bus, venue, &opened, reason).await,
+            None if !venue.primary => protective.protect(bus, venue.account, &opened).await,
+            None => {}
+        }
+    }
+
+    false
+}
+
+/// Closes a position opened by an entry that was cancelled to close its trade. On the primary
+/// account the trade manager closes it, as it now manages the position.
+async fn close_filled(bus: &EventBus, venue: &Venue<'_>, opened: &TradeOpened, reason: ExitReason) {
+    if venue.primary {
+        bus.publish(PulsgramEvent::CloseTrade(CloseTrade {
+            intent_id: opened.intent_id,
+            symbol: opened.symbol,
+            reason,
+        }));
+        return;
+    }
+
+    let closing = opened.side.opposite();
+
+    if let Err(error) = venue
+        .account
+        .client
+        .place_market_order(opened.symbol, &closing, opened.quantity)
+        .await
+    {
+        bus.publish(PulsgramEvent::Error(ErrorEvent {
+            source: "TradeExecutor",
+            message_text: format!(
+                "Account: {}\nTrade ID: {}\nSymbol: {}\nFailed to close the filled entry: {}",
+                venue.account.name(),
+                opened.intent_id,
+                opened.symbol,
+                error
+            ),
+        }));
+    }
+}
+
+fn publish_cancelled_entry(
+    bus: &EventBus,
+    quality: &mut ExecutionTracker,
+    venue: &Venue,
+    trade: &TradeApproved,
+    response: &FuturesOrderResponse,
+    status: &OrderExecutionStatus,
+    fill: &Fill,
+) -> Option<TradeOpened> {
+    // A partially filled entry that gets cancelled still leaves an open position.
+    match filled_part(response) {
+        Some(partial) if !matches!(status, OrderExecutionStatus::Filled { .. }) => {
+            publish_outcome(bus, quality, venue, trade, &partial, fill)
+        }
+        _ => publish_outcome(bus, quality, venue, trade, status, fill),
+    }
+}
+
+/// Timing of an entry order, for the execution report.
+struct Fill {
+    sent_at_ms: i64,
+    /// Exchange transaction time of the order's last update.
+    filled_at_ms: i64,
+}
+
+/// Returns the opened trade when the entry was filled.
+fn publish_outcome(
+    bus: &EventBus,
+    quality: &mut ExecutionTracker,
+    venue: &Venue,
+    trade: &TradeApproved,
+    status: &OrderExecutionStatus,
+    fill: &Fill,
+) -> Option<TradeOpened> {
+    let opened = opened_trade(trade, status);
+
+    if let Some(opened) = &opened {
+        publish_account(
+            bus,
+            venue,
+            trade,
+            AccountOutcome::Filled {
+                price: opened.entry_price,
+                quantity: opened.quantity,
+            },
+        );
+
+        if venue.primary {
+            let report = quality.report(trade, opened, fill.sent_at_ms, fill.filled_at_ms);
+            let aggregates = quality.quality(&report);
+            log_execution(&report, &aggregates);
+
+            bus.publish(PulsgramEvent::TradeOpened(opened.clone()));
+            bus.publish(PulsgramEvent::ExecutionReport(report));
+            bus.publish(PulsgramEvent::ExecutionQuality(aggregates));
+        }
+    }
+
+    if let Some(event) = entry_outcome(status) {
+        if let LifecycleEvent::Cancel { reason } = &event {
+            publish_account(
+                bus,
+                venue,
+                trade,
+                AccountOutcome::Cancelled {
+                    reason: reason.clone(),
+                },
+            );
+        }
+
+        if venue.primary {
+            publish_transition(bus, trade, event);
+        }
+    }
+
+    opened
+}
+
+fn log_execution(report: &ExecutionReport, quality: &ExecutionQuality) {
+    let (source, symbol) = (&quality.by_source, &quality.by_symbol);
+
+    println!(
+        "[EXECUTION] id={} symbol={} latency={}ms (approve={} send={} fill={}) \
+         slippage={:.1}bps/{:.3}R market={} | source avg {:.1}bps/{:.3}R {}ms over {} | \
+         symbol avg {:.1}bps/{:.3}R {}ms over {}",
+        report.intent_id,
+        report.symbol,
+        report.total_latency_ms(),
+        report.approval_latency_ms(),
+        report.send_latency_ms(),
+        report.fill_latency_ms(),
+        report.slippage_bps(),
+        report.slippage_r(),
+        report
+            .market_slippage_bps()
+            .map(|bps| format!("{:.1}bps", bps))
+            .unwrap_or_else(|| "n/a".to_string()),
+        source.avg_slippage_bps(),
+        source.avg_slippage_r(),
+        source.avg_latency_ms(),
+        source.fills,
+        symbol.avg_slippage_bps(),
+        symbol.avg_slippage_r(),
+        symbol.avg_latency_ms(),
+        symbol.fills,
+    );
+}
+
+fn report_failure(bus: &EventBus, venue: &Venue, trade: &TradeApproved, error: &BinanceError) {
+    publish_account(bus, venue, trade, AccountOutcome::Failed(error.to_string()));
+
+    if venue.primary {
+        publish_transition(
+            bus,
+            trade,
+            LifecycleEvent::Fail {
+                reason: error.to_string(),
+            },
+        );
+    }
+
+    bus.publish(PulsgramEvent::Error(ErrorEvent {
+        source: "TradeExecutor",
+        message_text: format!(
+            "Account: {}\n{}",
+            venue.account.name(),
+            format_trade_error(trade, error)
+        ),
+    }));
+}
+
+fn publish_account(bus: &EventBus, venue: &Venue, trade: &TradeApproved, outcome: AccountOutcome) {
+    println!(
+        "[ACCOUNT] name={} id={} symbol={} {:?}",
+        venue.account.name(),
+        trade.intent_id,
+        trade.symbol,
+        outcome
+    );
+
+    bus.publish(PulsgramEvent::AccountExecution(AccountExecution {
+        account: venue.account.name().to_string(),
+        intent_id: trade.intent_id,
+        symbol: trade.symbol,
+        outcome,
+    }));
+}
+
+fn publish_transition(bus: &EventBus, trade: &TradeApproved, event: LifecycleEvent) {
+    bus.publish(PulsgramEvent::TradeTransition(TradeTransition {
+        intent_id: trade.intent_id,
+        symbol: trade.symbol,
+        event,
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::{order_side::OrderSide, symbol::Symbol, trade_intent::TradeIntent};
+
+    use super::*;
+
+    fn resting(trade: &TradeApproved) -> PendingEntry {
+        PendingEntry {
+            trade: trade.clone(),
+            order_id: 1,
+            sent_at_ms: 0,
+            expires_at: Instant::now() + FALLBACK_ENTRY_EXPIRY,
+            close_reason: None,
+        }
+    }
+
+    fn trade() -> TradeApproved {
+        TradeIntent::builder(&Symbol::BTC)
+            .side(OrderSide::Buy)
+            .entry(100.0)
+            .stop_loss(95.0)
+            .targets(&[110.0])
+            .timeframe("1h")
+            .build()
+            .map(|intent| TradeApproved::new(intent, 0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_replacing_a_resting_entry_cancels_it_on_every_account() {
+        let (replaced, other) = (trade(), trade());
+
+        let mut pending = HashMap::from([
+            ((0, replaced.intent_id), resting(&replaced)),
+            ((1, replaced.intent_id), resting(&replaced)),
+            ((0, other.intent_id), resting(&other)),
+        ]);
+
+        let request = CloseTrade {
+            intent_id: replaced.intent_id,
+            symbol: replaced.symbol,
+            reason: ExitReason::Conflict,
+        };
+
+        let mut keys = request_close(&mut pending, &request);
+        keys.sort();
+
+        assert_eq!(keys, vec![(0, replaced.intent_id), (1, replaced.intent_id)]);
+
+        let now = Instant::now();
+        for key in &keys {
+            let entry = &pending[key];
+            assert!(entry.expires_at <= now);
+            assert_eq!(entry.close_reason, Some(ExitReason::Conflict));
+        }
+
+        let untouched = &pending[&(0, other.intent_id)];
+        assert!(untouched.expires_at > now);
+        assert_eq!(untouched.close_reason, None);
     }
 }
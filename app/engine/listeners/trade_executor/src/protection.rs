@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+
+use binance::errors::BinanceError;
+use domain::types::signal_update::{SignalUpdateKind, StopLevel};
+use domain::types::symbol::Symbol;
+use domain::types::trade::TradeOpened;
+use publisher::EventBus;
+use publisher::types::{ErrorEvent, PulsgramEvent};
+use uuid::Uuid;
+
+use crate::account::Account;
+
+/// Exchange-side exits of the positions opened on secondary accounts, which the trade manager
+/// does not manage.
+///
+/// Each trade gets its own reduce-only orders, so trades on the same symbol do not replace each
+/// other's. Provider follow-ups and close requests are applied to them here, and whatever is left
+/// of them is cancelled once the position is flat.
+#[derive(Default)]
+pub struct ProtectiveOrders {
+    // Keyed by account name and intent.
+    trades: HashMap<(String, Uuid), Protection>,
+}
+
+/// Exits of one trade on one account.
+struct Protection {
+    opened: TradeOpened,
+    /// Still open, after provider partial closes.
+    quantity: f64,
+    stop_loss: f64,
+    stop_order: Option<i64>,
+    target: Option<f64>,
+    target_order: Option<i64>,
+}
+
+impl ProtectiveOrders {
+    pub fn is_empty(&self) -> bool {
+        self.trades.is_empty()
+    }
+
+    /// Places a stop loss at the trade's stop and a take profit at its last target, both for the
+    /// filled quantity. A position that cannot get a stop loss is closed right away.
+    pub async fn protect(&mut self, bus: &EventBus, account: &Account, opened: &TradeOpened) {
+        let mut protection = Protection {
+            opened: opened.clone(),
+            quantity: opened.quantity,
+            stop_loss: opened.stop_loss,
+            stop_order: None,
+            target: None,
+            target_order: None,
+        };
+
+        protection.move_stop(bus, account, opened.stop_loss).await;
+
+        if protection.stop_order.is_none() {
+            let outcome = match protection.close(account, 100.0).await {
+                Ok(_) => format!("{} position closed.", opened.symbol),
+                Err(error) => format!("Failed to close the position: {}", error),
+            };
+
+            report(bus, account, opened, outcome);
+            return;
+        }
+
+        if let Some(target) = opened.targets.last() {
+            protection.move_target(bus, account, *target).await;
+        }
+
+        self.trades
+            .insert((account.name().to_string(), opened.intent_id), protection);
+    }
+
+    /// Applies a provider's follow-up to the trade on every account it is open on.
+    pub async fn follow(
+        &mut self,
+        bus: &EventBus,
+        accounts: &[Account],
+        intent_id: Uuid,
+        kind: &SignalUpdateKind,
+    ) {
+        for account in accounts {
+            let key = (account.name().to_string(), intent_id);
+
+            let Some(protection) = self.trades.get_mut(&key) else {
+                continue;
+            };
+
+            let percent = match kind {
+                SignalUpdateKind::CloseAll | SignalUpdateKind::Cancel => 100.0,
+
+                SignalUpdateKind::ClosePartial { percent } => *percent,
+
+                SignalUpdateKind::MoveStop { to } => {
+                    let price = match to {
+                        StopLevel::Entry => protection.opened.entry_price,
+                        StopLevel::Price(price) => *price,
+                    };
+
+                    protection.move_stop(bus, account, price).await;
+                    continue;
+                }
+
+                SignalUpdateKind::AmendSignal {
+                    targets, stop_loss, ..
+                } => {
+                    protection.move_stop(bus, account, *stop_loss).await;
+
+                    if let Some(target) = targets.last() {
+                        protection.move_target(bus, account, *target).await;
+                    }
+                    continue;
+                }
+            };
+
+            match protection.close(account, percent).await {
+                Ok(true) => {
+                    if let Some(protection) = self.trades.remove(&key) {
+                        protection.cancel(account).await;
+                    }
+                }
+
+                // What is left keeps exits sized to it.
+                Ok(false) => {
+                    protection
+                        .move_stop(bus, account, protection.stop_loss)
+                        .await;
+
+                    if let Some(target) = protection.target {
+                        protection.move_target(bus, account, target).await;
+                    }
+                }
+
+                Err(error) => report(
+                    bus,
+                    account,
+                    &protection.opened,
+                    format!("Failed to close {:.1}% of the position: {}", percent, error),
+                ),
+            }
+        }
+    }
+
+    /// Cancels the orders left on positions that have been closed, e.g. the take profit once the
+    /// stop loss was hit.
+    pub async fn sweep(&mut self, accounts: &[Account]) {
+        let positions: HashSet<(String, Symbol)> = self
+            .trades
+            .iter()
+            .map(|((name, _), protection)| (name.clone(), protection.opened.symbol))
+            .collect();
+
+        for (name, symbol) in positions {
+            let on_position = |(account, _): &(String, Uuid), protection: &Protection| {
+                *account == name && protection.opened.symbol == symbol
+            };
+
+            let Some(account) = accounts.iter().find(|account| account.name() == name) else {
+                self.trades
+                    .retain(|key, protection| !on_position(key, protection));
+                continue;
+            };
+
+            let is_flat = match account.client.get_position_risk(Some(symbol)).await {
+                Ok(positions) => positions
+                    .iter()
+                    .filter(|position| position.symbol == symbol.to_string())
+                    .all(|position| position.position_amt.parse::<f64>().unwrap_or(0.0) == 0.0),
+                Err(error) => {
+                    // Checked again on the next poll.
+                    eprintln!(
+                        "[PROTECTION] account={} symbol={} position check failed: {}",
+                        name, symbol, error
+                    );
+                    continue;
+                }
+            };
+
+            if !is_flat {
+                continue;
+            }
+
+            let keys: Vec<(String, Uuid)> = self
+                .trades
+                .iter()
+                .filter(|(key, protection)| on_position(key, protection))
+                .map(|(key, _)| key.clone())
+                .collect();
+
+            for key in keys {
+                if let Some(protection) = self.trades.remove(&key) {
+                    // Expected to fail for the order that closed the position.
+                    protection.cancel(account).await;
+                }
+            }
+        }
+    }
+}
+
+impl Protection {
+    /// Closes `percent` of what is left at market. Returns whether the trade is fully closed.
+    async fn close(&mut self, account: &Account, percent: f64) -> Result<bool, BinanceError> {
+        let fraction = (percent / 100.0).clamp(0.0, 1.0);
+        let quantity = self.quantity * fraction;
+
+        account
+            .client
+            .place_market_order(self.opened.symbol, &self.opened.side.opposite(), quantity)
+            .await?;
+
+        self.quantity -= quantity;
+        Ok(fraction >= 1.0)
+    }
+
+    /// Places a stop loss at `price` for what is left, then cancels the one it replaces, so the
+    /// position is never left without one.
+    async fn move_stop(&mut self, bus: &EventBus, account: &Account, price: f64) {
+        let placed = account
+            .client
+            .place_reduce_only_stop_order(
+                self.opened.symbol,
+                &self.opened.side.opposite(),
+                self.quantity,
+                price,
+            )
+            .await;
+
+        match placed {
+            Ok(order) => {
+                let replaced = self.stop_order.replace(order.order_id);
+                self.stop_loss = price;
+                cancel_order(account, self.opened.symbol, replaced).await;
+            }
+            Err(error) => report(
+                bus,
+                account,
+                &self.opened,
+                format!("Failed to place stop loss at {}: {}", price, error),
+            ),
+        }
+    }
+
+    /// Places a take profit at `price` for what is left, then cancels the one it replaces.
+    async fn move_target(&mut self, bus: &EventBus, account: &Account, price: f64) {
+        let placed = account
+            .client
+            .place_take_profit_order(
+                self.opened.symbol,
+                &self.opened.side.opposite(),
+                self.quantity,
+                price,
+            )
+            .await;
+
+        match placed {
+            Ok(order) => {
+                let replaced = self.target_order.replace(order.order_id);
+                self.target = Some(price);
+                cancel_order(account, self.opened.symbol, replaced).await;
+            }
+            Err(error) => report(
+                bus,
+                account,
+                &self.opened,
+                format!("Failed to place take profit at {}: {}", price, error),
+            ),
+        }
+    }
+
+    async fn cancel(self, account: &Account) {
+        for order_id in [self.stop_order, self.target_order] {
+            cancel_order(account, self.opened.symbol, order_id).await;
+        }
+    }
+}
+
+async fn cancel_order(account: &Account, symbol: Symbol, order_id: Option<i64>) {
+    let Some(order_id) = order_id else {
+        return;
+    };
+
+    if let Err(error) = account.client.cancel_order(symbol, order_id).await {
+        eprintln!(
+            "[PROTECTION] account={} symbol={} order_id={} cancel failed: {}",
+            account.name(),
+            symbol,
+            order_id,
+            error
+        );
+    }
+}
+
+fn report(bus: &EventBus, account: &Account, opened: &TradeOpened, message: String) {
+    bus.publish(PulsgramEvent::Error(ErrorEvent {
+        source: "TradeExecutor::Protection",
+        message_text: format!(
+            "Account: {}\nTrade ID: {}\nSymbol: {}\n{}",
+            account.name(),
+            opened.intent_id,
+            opened.symbol,
+            message
+        ),
+    }));
+}
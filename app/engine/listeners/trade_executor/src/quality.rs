@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use domain::types::execution::{ExecutionQuality, ExecutionReport, ExecutionStats};
+use domain::types::market::PriceTick;
+use domain::types::symbol::Symbol;
+use domain::types::trade::{TradeApproved, TradeOpened};
+use domain::types::trade_intent::TradeIntent;
+use uuid::Uuid;
+
+// Market prices of signals that never fill are dropped after a day.
+const SIGNAL_RETENTION_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Remembers the market price at signal time and aggregates fills per source and symbol.
+#[derive(Debug, Default)]
+pub struct ExecutionTracker {
+    last_prices: HashMap<Symbol, f64>,
+    // Market price and signal time, by intent.
+    signal_prices: HashMap<Uuid, (f64, i64)>,
+    by_source: HashMap<Option<i64>, ExecutionStats>,
+    by_symbol: HashMap<Symbol, ExecutionStats>,
+}
+
+impl ExecutionTracker {
+    pub fn observe(&mut self, tick: &PriceTick) {
+        self.last_prices.insert(tick.symbol, tick.price);
+    }
+
+    pub fn on_signal(&mut self, intent: &TradeIntent) {
+        self.signal_prices.retain(|_, (_, signal_time_ms)| {
+            intent.signal_time_ms - *signal_time_ms < SIGNAL_RETENTION_MS
+        });
+
+        if let Some(&price) = self.last_prices.get(&intent.symbol) {
+            self.signal_prices
+                .insert(intent.intent_id, (price, intent.signal_time_ms));
+        }
+    }
+
+    /// Builds the report for a filled entry and adds it to the aggregates.
+    pub fn report(
+        &mut self,
+        trade: &TradeApproved,
+        opened: &TradeOpened,
+        sent_at_ms: i64,
+        filled_at_ms: i64,
+    ) -> ExecutionReport {
+        let report = ExecutionReport {
+            intent_id: trade.intent_id,
+            symbol: trade.symbol,
+            side: trade.side,
+            source: trade.source,
+            signal_time_ms: trade.signal_time_ms,
+            approved_at_ms: trade.approved_at_ms,
+            sent_at_ms,
+            filled_at_ms,
+            signal_entry: trade.entry,
+            signal_market_price: self
+                .signal_prices
+                .remove(&trade.intent_id)
+                .map(|(price, _)| price),
+            stop_loss: trade.stop_loss,
+            fill_price: opened.entry_price,
+        };
+
+        self.by_source
+            .entry(report.source)
+            .or_default()
+            .add(&report);
+        self.by_symbol
+            .entry(report.symbol)
+            .or_default()
+            .add(&report);
+
+        report
+    }
+
+    /// Aggregates of the report's source and symbol, including the report itself.
+    pub fn quality(&self, report: &ExecutionReport) -> ExecutionQuality {
+        ExecutionQuality {
+            source: report.source,
+            by_source: self.source_stats(report.source),
+            symbol: report.symbol,
+            by_symbol: self.symbol_stats(report.symbol),
+        }
+    }
+
+    pub fn source_stats(&self, source: Option<i64>) -> ExecutionStats {
+        self.by_source.get(&source).copied().unwrap_or_default()
+    }
+
+    pub fn symbol_stats(&self, symbol: Symbol) -> ExecutionStats {
+        self.by_symbol.get(&symbol).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::order_side::OrderSide;
+
+    use super::*;
+
+    fn intent(symbol: Symbol, source: i64) -> TradeIntent {
+        TradeIntent::builder(&symbol)
+            .side(OrderSide::Buy)
+            .entry(100.0)
+            .stop_loss(90.0)
+            .targets(&[110.0])
+            .timeframe("1h")
+            .source(source)
+            .signal_time_ms(1_000)
+            .build()
+            .unwrap()
+    }
+
+    fn fill(tracker: &mut ExecutionTracker, intent: TradeIntent, price: f64) -> ExecutionReport {
+        let trade = TradeApproved::new(intent, 2_000);
+        let opened = TradeOpened {
+            intent_id: trade.intent_id,
+            symbol: trade.symbol,
+            side: trade.side,
+            entry_price: price,
+            quantity: 1.0,
+            targets: trade.targets.clone(),
+            timeframe: trade.timeframe.clone(),
+            stop_loss: trade.stop_loss,
+            source: trade.source,
+        };
+
+        tracker.report(&trade, &opened, trade.approved_at_ms, trade.approved_at_ms)
+    }
+
+    #[test]
+    fn test_report_uses_market_price_at_signal_time() {
+        let mut tracker = ExecutionTracker::default();
+        let signal = intent(Symbol::BTC, 1);
+
+        tracker.observe(&PriceTick {
+            symbol: Symbol::BTC,
+            price: 100.2,
+        });
+        tracker.on_signal(&signal);
+        tracker.observe(&PriceTick {
+            symbol: Symbol::BTC,
+            price: 105.0,
+        });
+
+        let report = fill(&mut tracker, signal, 100.5);
+
+        assert_eq!(report.signal_market_price, Some(100.2));
+    }
+
+    #[test]
+    fn test_aggregates_per_source_and_symbol() {
+        let mut tracker = ExecutionTracker::default();
+
+        fill(&mut tracker, intent(Symbol::BTC, 1), 101.0);
+        fill(&mut tracker, intent(Symbol::ETH, 1), 103.0);
+        fill(&mut tracker, intent(Symbol::BTC, 2), 100.0);
+
+        let source = tracker.source_stats(Some(1));
+        assert_eq!(source.fills, 2);
+        assert!((source.avg_slippage_bps() - 200.0).abs() < 1e-9);
+        assert!((source.avg_slippage_r() - 0.2).abs() < 1e-9);
+
+        let symbol = tracker.symbol_stats(Symbol::BTC);
+        assert_eq!(symbol.fills, 2);
+        assert!((symbol.avg_slippage_bps() - 50.0).abs() < 1e-9);
+
+        assert_eq!(tracker.source_stats(None).fills, 0);
+    }
+}
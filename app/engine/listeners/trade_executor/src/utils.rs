@@ -1,5 +1,6 @@
 use binance::{errors::BinanceError, response_types::FuturesOrderResponse};
-use domain::types::trade::TradeApproved;
+use domain::types::lifecycle::LifecycleEvent;
+use domain::types::trade::{TradeApproved, TradeOpened};
 
 pub enum OrderExecutionStatus {
     Filled {
@@ -33,6 +34,50 @@ impl From<&FuturesOrderResponse> for OrderExecutionStatus {
     }
 }
 
+/// Filled part of an order that did not fill completely, e.g. a cancelled resting entry.
+pub fn filled_part(response: &FuturesOrderResponse) -> Option<OrderExecutionStatus> {
+    let executed = response.executed_qty.parse::<f64>().ok()?;
+
+    (executed > 0.0).then(|| OrderExecutionStatus::Filled {
+        order_id: response.order_id,
+        qty: response.executed_qty.clone(),
+        avg_price: response.avg_price.clone(),
+    })
+}
+
+pub fn opened_trade(trade: &TradeApproved, status: &OrderExecutionStatus) -> Option<TradeOpened> {
+    let OrderExecutionStatus::Filled { qty, avg_price, .. } = status else {
+        return None;
+    };
+
+    Some(TradeOpened {
+        intent_id: trade.intent_id,
+        symbol: trade.symbol,
+        side: trade.side,
+        entry_price: avg_price.parse().ok()?,
+        quantity: qty.parse().ok()?,
+        targets: trade.targets.clone(),
+        timeframe: trade.timeframe.clone(),
+        stop_loss: trade.stop_loss,
+        source: trade.source,
+    })
+}
+
+/// Lifecycle event for an entry order that ended without a fill.
+/// Fills are reported through `TradeOpened` instead.
+pub fn entry_outcome(status: &OrderExecutionStatus) -> Option<LifecycleEvent> {
+    let reason = match status {
+        OrderExecutionStatus::Canceled => "Entry order canceled",
+        OrderExecutionStatus::Rejected => "Entry order rejected",
+        OrderExecutionStatus::Expired => "Entry order expired",
+        _ => return None,
+    };
+
+    Some(LifecycleEvent::Cancel {
+        reason: reason.to_string(),
+    })
+}
+
 pub fn handle_order_status(trade: &TradeApproved, status: OrderExecutionStatus) {
     match status {
         OrderExecutionStatus::New => {
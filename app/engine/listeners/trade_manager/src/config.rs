@@ -0,0 +1,33 @@
+use serde::Deserialize;
+
+use crate::time_exit::TimeExitConfig;
+use crate::trailing::TrailingConfig;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TradeManagerConfig {
+    /// Fraction of the original position closed at each target (TP1, TP2, ...).
+    /// The last target of a trade always closes whatever is left.
+    pub take_profit_fractions: Vec<f64>,
+    /// Move the stop to break-even once TP1 is hit.
+    pub break_even_after_tp1: bool,
+    /// Offset the break-even stop by the round-trip taker fee.
+    pub break_even_include_fees: bool,
+    /// Move the stop to TP1 once TP2 is hit.
+    pub stop_to_tp1_after_tp2: bool,
+    pub trailing: TrailingConfig,
+    pub time_exit: TimeExitConfig,
+}
+
+impl Default for TradeManagerConfig {
+    fn default() -> Self {
+        Self {
+            take_profit_fractions: vec![0.5, 0.3, 0.2],
+            break_even_after_tp1: true,
+            break_even_include_fees: true,
+            stop_to_tp1_after_tp2: true,
+            trailing: TrailingConfig::default(),
+            time_exit: TimeExitConfig::default(),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use domain::types::lifecycle::{LifecycleEvent, LifecycleStep, Trade, TransitionError};
+use domain::types::symbol::Symbol;
+use uuid::Uuid;
+
+// Finished trades are kept a little longer, since commissions for the closing fill
+// arrive through the user stream after the close itself.
+const FINISHED_RETENTION_MS: i64 = 10 * 60 * 1000;
+
+#[derive(Debug)]
+pub enum LedgerError {
+    UnknownTrade(Uuid),
+    Transition(Uuid, TransitionError),
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::UnknownTrade(id) => write!(f, "Unknown trade {}", id),
+            LedgerError::Transition(id, error) => write!(f, "Trade {}: {}", id, error),
+        }
+    }
+}
+
+/// Every trade the manager has heard of, each driven through the `Trade` state machine.
+#[derive(Debug, Default)]
+pub struct TradeLedger {
+    trades: HashMap<Uuid, Trade>,
+    // Closing orders sent for each trade. Entry orders are kept on the trade itself.
+    exit_orders: HashMap<(Symbol, i64), Uuid>,
+}
+
+impl TradeLedger {
+    pub fn track(&mut self, trade: Trade) {
+        self.trades.entry(trade.intent_id).or_insert(trade);
+    }
+
+    pub fn get(&self, intent_id: &Uuid) -> Option<&Trade> {
+        self.trades.get(intent_id)
+    }
+
+    pub fn apply(
+        &mut self,
+        intent_id: Uuid,
+        event: LifecycleEvent,
+        timestamp_ms: i64,
+    ) -> Result<&LifecycleStep, LedgerError> {
+        let trade = self
+            .trades
+            .get_mut(&intent_id)
+            .ok_or(LedgerError::UnknownTrade(intent_id))?;
+
+        trade
+            .apply(event, timestamp_ms)
+            .map_err(|error| LedgerError::Transition(intent_id, error))
+    }
+
+    /// Records an order sent to close all or part of a trade, so its fills are matched to it.
+    pub fn track_exit_order(&mut self, intent_id: Uuid, symbol: Symbol, order_id: i64) {
+        self.exit_orders.insert((symbol, order_id), intent_id);
+    }
+
+    /// Finds the trade an exchange fill belongs to, by its entry or exit order. Binance order
+    /// IDs are only unique per symbol.
+    pub fn trade_for_fill(&self, symbol: Symbol, order_id: i64) -> Option<Uuid> {
+        self.trades
+            .values()
+            .find(|trade| trade.symbol == symbol && trade.entry_order_id == Some(order_id))
+            .map(|trade| trade.intent_id)
+            .or_else(|| self.exit_orders.get(&(symbol, order_id)).copied())
+    }
+
+    pub fn prune(&mut self, now_ms: i64) {
+        self.trades.retain(|_, trade| {
+            !trade.state().is_terminal()
+                || trade
+                    .last_timestamp_ms()
+                    .is_some_and(|at| now_ms - at < FINISHED_RETENTION_MS)
+        });
+
+        self.exit_orders
+            .retain(|_, intent_id| self.trades.contains_key(intent_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::types::{lifecycle::TradeState, order_side::OrderSide, trade::ExitReason};
+
+    use super::*;
+
+    fn ledger_with_open_trade(order_id: i64) -> (TradeLedger, Uuid) {
+        let mut ledger = TradeLedger::default();
+        let trade = Trade::new(Uuid::new_v4(), Symbol::BTC, OrderSide::Buy, 90.0);
+        let id = trade.intent_id;
+
+        ledger.track(trade);
+        ledger.apply(id, LifecycleEvent::Approve, 1).unwrap();
+        ledger
+            .apply(id, LifecycleEvent::EntrySubmitted { order_id }, 2)
+            .unwrap();
+        ledger
+            .apply(
+                id,
+                LifecycleEvent::EntryFilled {
+                    price: 100.0,
+                    quantity: 1.0,
+                },
+                3,
+            )
+            .unwrap();
+
+        (ledger, id)
+    }
+
+    #[test]
+    fn test_unknown_trade_is_reported() {
+        let mut ledger = TradeLedger::default();
+
+        assert!(matches!(
+            ledger.apply(Uuid::new_v4(), LifecycleEvent::Approve, 1),
+            Err(LedgerError::UnknownTrade(_))
+        ));
+    }
+
+    #[test]
+    fn test_fill_is_matched_to_its_trade_by_order() {
+        let (mut ledger, id) = ledger_with_open_trade(7);
+
+        assert_eq!(ledger.trade_for_fill(Symbol::BTC, 7), Some(id));
+        assert_eq!(ledger.trade_for_fill(Symbol::ETH, 7), None);
+
+        // An order placed outside of Pulsgram, on the same symbol.
+        assert_eq!(ledger.trade_for_fill(Symbol::BTC, 99), None);
+
+        ledger.track_exit_order(id, Symbol::BTC, 99);
+        assert_eq!(ledger.trade_for_fill(Symbol::BTC, 99), Some(id));
+    }
+
+    #[test]
+    fn test_finished_trades_are_pruned_after_retention() {
+        let (mut ledger, id) = ledger_with_open_trade(7);
+
+        ledger
+            .apply(
+                id,
+                LifecycleEvent::Exit {
+                    price: 95.0,
+                    reason: ExitReason::StopLoss,
+                },
+                1_000,
+            )
+            .unwrap();
+
+        ledger.prune(2_000);
+        assert_eq!(ledger.get(&id).unwrap().state(), TradeState::Closed);
+
+        ledger.prune(1_000 + FINISHED_RETENTION_MS);
+        assert!(ledger.get(&id).is_none());
+    }
+}
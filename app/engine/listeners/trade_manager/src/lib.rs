@@ -0,0 +1,551 @@
+pub mod config;
+mod ledger;
+mod position;
+pub mod time_exit;
+pub mod trailing;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use binance::client::BinanceClient;
+use binance::errors::BinanceError;
+use binance::response_types::FuturesOrderResponse;
+use domain::clock::now_ms;
+use domain::types::lifecycle::{LifecycleEvent, Trade};
+use domain::types::signal_update::SignalUpdateKind;
+use domain::types::symbol::Symbol;
+use domain::types::trade::{ExitReason, TradeUpdate, TradeUpdateKind};
+use publisher::types::{ErrorEvent, PulsgramEvent};
+use publisher::{EventBus, handle_recv_error};
+use uuid::Uuid;
+
+use crate::config::TradeManagerConfig;
+use crate::ledger::TradeLedger;
+use crate::position::{LadderStep, ManagedPosition};
+use crate::time_exit::TimeExitAction;
+use crate::trailing::{Trail, TrailingMode, average_true_range};
+
+const STALE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// NOTE: Positions are closed through `close_percentage`/`close_full_position`, which act on the
+// whole symbol position (one-way mode). Two managed trades on the same symbol will interfere.
+pub async fn run(bus: Arc<EventBus>, client: Arc<BinanceClient>, config: TradeManagerConfig) {
+    println!("Trade Manager running...");
+    let mut rx = bus.subscribe();
+
+    let mut positions: HashMap<Uuid, ManagedPosition> = HashMap::new();
+    let mut ledger = TradeLedger::default();
+
+    let mut stale_check = tokio::time::interval(STALE_CHECK_INTERVAL);
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Ok(event) => {
+                    record(&mut ledger, &event, &bus);
+
+                    match event {
+                        PulsgramEvent::TradeOpened(opened) => {
+                            let fee_rate = if config.break_even_include_fees {
+                                taker_fee_rate(&client, opened.symbol, &bus).await
+                            } else {
+                                0.0
+                            };
+
+                            positions
+                                .insert(opened.intent_id, ManagedPosition::new(&opened, fee_rate));
+                        }
+
+                        PulsgramEvent::PriceUpdate(tick) => {
+                            for position in positions
+                                .values_mut()
+                                .filter(|position| position.symbol == tick.symbol)
+                            {
+                                position.observe(tick.price);
+                                manage(position, tick.price, &client, &bus, &config).await;
+
+                                let trailing_due = !position.is_closed()
+                                    && !position.trailing_armed
+                                    && position.targets_hit()
+                                        >= config.trailing.activate_after_target;
+
+                                if trailing_due
+                                    && let Some(mode) = config
+                                        .trailing
+                                        .mode_for(position.source, &position.timeframe)
+                                {
+                                    arm_trailing(position, mode, tick.price, &client, &bus).await;
+                                }
+                            }
+
+                            settle(&mut positions, &mut ledger);
+                        }
+
+                        PulsgramEvent::OrderUpdate(order) => {
+                            for position in positions.values_mut() {
+                                for update in position.trailing_filled(&order).unwrap_or_default() {
+                                    log_update(&update);
+                                    bus.publish(PulsgramEvent::TradeUpdate(update));
+                                }
+                            }
+
+                            settle(&mut positions, &mut ledger);
+                        }
+
+                        // Entries that are not filled yet are cancelled by the executor.
+                        PulsgramEvent::CloseTrade(request) => {
+                            if let Some(position) = positions.get_mut(&request.intent_id) {
+                                close(position, request.reason, &client, &bus).await;
+                            }
+
+                            settle(&mut positions, &mut ledger);
+                        }
+
+                        // Follow-ups on signals whose entry is not filled yet are handled by
+                        // the executor.
+                        PulsgramEvent::SignalUpdate(update) => {
+                            if let Some(position) = positions.get_mut(&update.intent_id) {
+                                follow_provider(position, update.kind, &client, &bus).await;
+                            }
+
+                            settle(&mut positions, &mut ledger);
+                        }
+
+                        _ => {}
+                    }
+                }
+
+                Err(error) => {
+                    if handle_recv_error("TradeManager RecvError", error, &bus) {
+                        break;
+                    }
+                }
+            },
+
+            _ = stale_check.tick(), if !positions.is_empty() => {
+                let now = Instant::now();
+
+                for position in positions.values_mut() {
+                    let stale = config
+                        .time_exit
+                        .limit_for(&position.timeframe)
+                        .is_some_and(|limit| position.is_stale(limit, now));
+
+                    if stale {
+                        exit_stale(position, config.time_exit.action, &client, &bus).await;
+                    }
+                }
+
+                settle(&mut positions, &mut ledger);
+            }
+        }
+    }
+}
+
+/// Hands the closing orders sent to the ledger, then drops the closed positions.
+fn settle(positions: &mut HashMap<Uuid, ManagedPosition>, ledger: &mut TradeLedger) {
+    for position in positions.values_mut() {
+        for order_id in position.exit_orders.drain(..) {
+            ledger.track_exit_order(position.intent_id, position.symbol, order_id);
+        }
+    }
+
+    positions.retain(|_, position| !position.is_closed());
+}
+
+async fn manage(
+    position: &mut ManagedPosition,
+    price: f64,
+    client: &BinanceClient,
+    bus: &EventBus,
+    config: &TradeManagerConfig,
+) {
+    // A single tick can cross several levels (e.g. a gap through TP1 and TP2).
+    while let Some(step) = position.next_step(price, config) {
+        let order = match execute(client, position.symbol, &step).await {
+            Ok(order) => order,
+            Err(error) => {
+                position.failed_attempts += 1;
+
+                // The step is retried on every tick; only report the first failure.
+                if position.failed_attempts == 1 {
+                    bus.publish(PulsgramEvent::Error(ErrorEvent {
+                        source: "TradeManager::Execute",
+                        message_text: format!(
+                            "Failed to execute {:?}\nTrade ID: {}\nSymbol: {}\nError: {}",
+                            step, position.intent_id, position.symbol, error
+                        ),
+                    }));
+                }
+
+                return;
+            }
+        };
+
+        position.failed_attempts = 0;
+        position
+            .exit_orders
+            .extend(order.map(|order| order.order_id));
+
+        for update in position.apply(&step) {
+            log_update(&update);
+            bus.publish(PulsgramEvent::TradeUpdate(update));
+        }
+    }
+
+    if position.is_closed() {
+        cancel_trailing_order(position, client, bus).await;
+    }
+}
+
+async fn exit_stale(
+    position: &mut ManagedPosition,
+    action: TimeExitAction,
+    client: &BinanceClient,
+    bus: &EventBus,
+) {
+    match action {
+        TimeExitAction::Close => close(position, ExitReason::TimeExit, client, bus).await,
+
+        TimeExitAction::Tighten { fraction } => {
+            // The stop is enforced on the price feed, so nothing is sent to the exchange.
+            if let Some(update) = position.tighten(fraction) {
+                log_update(&update);
+                bus.publish(PulsgramEvent::TradeUpdate(update));
+            }
+        }
+    }
+}
+
+async fn close(
+    position: &mut ManagedPosition,
+    reason: ExitReason,
+    client: &BinanceClient,
+    bus: &EventBus,
+) {
+    match client.close_full_position(position.symbol).await {
+        Ok(order) => position
+            .exit_orders
+            .extend(order.map(|order| order.order_id)),
+        Err(error) => {
+            bus.publish(PulsgramEvent::Error(ErrorEvent {
+                source: "TradeManager::Close",
+                message_text: format!(
+                    "Failed to close ({:?})\nTrade ID: {}\nSymbol: {}\nError: {}",
+                    reason, position.intent_id, position.symbol, error
+                ),
+            }));
+            return;
+        }
+    }
+
+    for update in position.close(reason) {
+        log_update(&update);
+        bus.publish(PulsgramEvent::TradeUpdate(update));
+    }
+
+    cancel_trailing_order(position, client, bus).await;
+}
+
+/// Applies the provider's follow-up on the signal. Stops are enforced on the price feed, so only
+/// closes reach the exchange.
+async fn follow_provider(
+    position: &mut ManagedPosition,
+    kind: SignalUpdateKind,
+    client: &BinanceClient,
+    bus: &EventBus,
+) {
+    let update = match kind {
+        SignalUpdateKind::AmendSignal {
+            targets, stop_loss, ..
+        } => position.amend(&targets, stop_loss),
+
+        SignalUpdateKind::MoveStop { to } => position.move_stop(to),
+
+        SignalUpdateKind::ClosePartial { percent } if percent < 100.0 => {
+            match client.close_percentage(position.symbol, percent).await {
+                Ok(order) => position
+                    .exit_orders
+                    .extend(order.map(|order| order.order_id)),
+                Err(error) => {
+                    bus.publish(PulsgramEvent::Error(ErrorEvent {
+                        source: "TradeManager::Provider",
+                        message_text: format!(
+                            "Failed to close {:.1}% on the provider's request\nTrade ID: {}\nSymbol: {}\nError: {}",
+                            percent, position.intent_id, position.symbol, error
+                        ),
+                    }));
+                    return;
+                }
+            }
+
+            Some(position.close_partial(percent))
+        }
+
+        SignalUpdateKind::ClosePartial { .. }
+        | SignalUpdateKind::CloseAll
+        | SignalUpdateKind::Cancel => {
+            close(position, ExitReason::Provider, client, bus).await;
+            return;
+        }
+    };
+
+    if let Some(update) = update {
+        log_update(&update);
+        bus.publish(PulsgramEvent::TradeUpdate(update));
+    }
+}
+
+async fn cancel_trailing_order(
+    position: &mut ManagedPosition,
+    client: &BinanceClient,
+    bus: &EventBus,
+) {
+    if let Some(order_id) = position.trailing_order_id.take()
+        && let Err(error) = client.cancel_order(position.symbol, order_id).await
+    {
+        // Expected when the exchange-side trailing stop is what closed the position.
+        bus.publish(PulsgramEvent::Error(ErrorEvent {
+            source: "TradeManager::Trailing",
+            message_text: format!(
+                "Failed to cancel trailing stop order {}\nTrade ID: {}\nError: {}",
+                order_id, position.intent_id, error
+            ),
+        }));
+    }
+}
+
+async fn arm_trailing(
+    position: &mut ManagedPosition,
+    mode: &TrailingMode,
+    price: f64,
+    client: &BinanceClient,
+    bus: &EventBus,
+) {
+    // Armed even on failure; retrying on every tick would flood the error reporter.
+    position.trailing_armed = true;
+
+    let result = match mode {
+        TrailingMode::Percent { percent } => {
+            position.start_trailing(Trail::Percent(*percent), price);
+            Ok(())
+        }
+
+        TrailingMode::Atr {
+            interval,
+            period,
+            multiplier,
+        } => {
+            // Extra history lets Wilder's smoothing settle.
+            let limit = (period * 3 + 1).min(1500) as u16;
+
+            client
+                .get_klines(position.symbol, interval, limit)
+                .await
+                .and_then(|klines| {
+                    average_true_range(&klines, *period).ok_or_else(|| {
+                        BinanceError::InvalidInput(format!(
+                            "Not enough {} klines for ATR({})",
+                            interval, period
+                        ))
+                    })
+                })
+                .map(|atr| position.start_trailing(Trail::Absolute(atr * multiplier), price))
+        }
+
+        TrailingMode::Native { callback_rate } => client
+            .place_trailing_stop_order(
+                position.symbol,
+                &position.side.opposite(),
+                position.remaining_quantity(),
+                *callback_rate,
+            )
+            .await
+            .map(|order| {
+                position.trailing_order_id = Some(order.order_id);
+                position.exit_orders.push(order.order_id);
+            }),
+    };
+
+    match result {
+        Ok(()) => println!(
+            "[TRAILING] id={} symbol={} {:?}",
+            position.intent_id, position.symbol, mode
+        ),
+
+        Err(error) => bus.publish(PulsgramEvent::Error(ErrorEvent {
+            source: "TradeManager::Trailing",
+            message_text: format!(
+                "Failed to start {:?} trailing\nTrade ID: {}\nSymbol: {}\nError: {}",
+                mode, position.intent_id, position.symbol, error
+            ),
+        })),
+    }
+}
+
+/// Returns the closing order sent, if any.
+async fn execute(
+    client: &BinanceClient,
+    symbol: Symbol,
+    step: &LadderStep,
+) -> Result<Option<FuturesOrderResponse>, BinanceError> {
+    match *step {
+        LadderStep::TakeProfit { is_final: true, .. } | LadderStep::StopLoss { .. } => {
+            client.close_full_position(symbol).await
+        }
+
+        // A level configured with a zero fraction only moves the stop.
+        LadderStep::TakeProfit { close_percent, .. } if close_percent <= 0.0 => Ok(None),
+
+        LadderStep::TakeProfit { close_percent, .. } => {
+            client.close_percentage(symbol, close_percent).await
+        }
+
+        // Trailed stops are enforced here, on the price feed.
+        LadderStep::Trail { .. } => Ok(None),
+    }
+}
+
+/// Applies every trade-related event to the lifecycle ledger, reporting impossible transitions.
+fn record(ledger: &mut TradeLedger, event: &PulsgramEvent, bus: &EventBus) {
+    let now = now_ms();
+
+    let (intent_id, lifecycle_event, timestamp_ms) = match event {
+        PulsgramEvent::TradeIntent(intent) => {
+            ledger.track(Trade::new(
+                intent.intent_id,
+                intent.symbol,
+                intent.side,
+                intent.stop_loss,
+            ));
+            return;
+        }
+
+        PulsgramEvent::TradeApproved(approved) => {
+            ledger.track(Trade::new(
+                approved.intent_id,
+                approved.symbol,
+                approved.side,
+                approved.stop_loss,
+            ));
+            (approved.intent_id, LifecycleEvent::Approve, now)
+        }
+
+        PulsgramEvent::TradeRejected(rejected) if ledger.get(&rejected.intent_id).is_some() => (
+            rejected.intent_id,
+            LifecycleEvent::Cancel {
+                reason: format!("{:?}", rejected.reason),
+            },
+            now,
+        ),
+
+        PulsgramEvent::TradeTransition(transition) => {
+            (transition.intent_id, transition.event.clone(), now)
+        }
+
+        PulsgramEvent::TradeOpened(opened) => (
+            opened.intent_id,
+            LifecycleEvent::EntryFilled {
+                price: opened.entry_price,
+                quantity: opened.quantity,
+            },
+            now,
+        ),
+
+        PulsgramEvent::TradeUpdate(update) => match update.kind {
+            // Levels configured with a zero fraction only move the stop.
+            TradeUpdateKind::TargetHit {
+                price,
+                closed_fraction,
+                ..
+            }
+            | TradeUpdateKind::PartialClose {
+                price,
+                closed_fraction,
+                ..
+            } if closed_fraction > 0.0 => {
+                let quantity = ledger
+                    .get(&update.intent_id)
+                    .map(|trade| trade.quantity * closed_fraction)
+                    .unwrap_or(0.0);
+
+                (
+                    update.intent_id,
+                    LifecycleEvent::PartialExit { price, quantity },
+                    now,
+                )
+            }
+
+            TradeUpdateKind::Closed { price, reason, .. } => (
+                update.intent_id,
+                LifecycleEvent::Exit { price, reason },
+                now,
+            ),
+
+            _ => return,
+        },
+
+        PulsgramEvent::OrderUpdate(order) if order.execution_type == "TRADE" => {
+            // Fills of orders placed outside of Pulsgram do not belong to any trade.
+            let Some(intent_id) = ledger.trade_for_fill(order.symbol, order.order_id) else {
+                println!(
+                    "[LIFECYCLE] symbol={} order_id={} fill matches no trade",
+                    order.symbol, order.order_id
+                );
+                return;
+            };
+
+            (
+                intent_id,
+                LifecycleEvent::Commission {
+                    amount: order.commission,
+                    asset: order.commission_asset.clone(),
+                },
+                order.event_time_ms,
+            )
+        }
+
+        _ => return,
+    };
+
+    match ledger.apply(intent_id, lifecycle_event, timestamp_ms) {
+        // Commissions keep the state, only transitions are logged.
+        Ok(step) if step.from != step.to => println!(
+            "[LIFECYCLE] id={} {:?} -> {:?} r={:.2} fees={:.4} at={}",
+            intent_id, step.from, step.to, step.realized_r, step.fees, step.timestamp_ms
+        ),
+
+        Ok(_) => {}
+
+        Err(error) => bus.publish(PulsgramEvent::Error(ErrorEvent {
+            source: "TradeManager::Lifecycle",
+            message_text: format!("Rejected trade lifecycle event\n{}", error),
+        })),
+    }
+
+    ledger.prune(now);
+}
+
+async fn taker_fee_rate(client: &BinanceClient, symbol: Symbol, bus: &EventBus) -> f64 {
+    let rate = match client.get_trading_fees(symbol).await {
+        Ok(fees) => fees.taker_commission_rate.parse::<f64>().ok(),
+        Err(error) => {
+            bus.publish(PulsgramEvent::Error(ErrorEvent {
+                source: "TradeManager::Fees",
+                message_text: format!("Failed to fetch fees for {}: {}", symbol, error),
+            }));
+            None
+        }
+    };
+
+    // Falls back to a plain break-even stop.
+    rate.unwrap_or(0.0)
+}
+
+fn log_update(update: &TradeUpdate) {
+    println!(
+        "[TRADE_UPDATE] id={} symbol={} {:?}",
+        update.intent_id, update.symbol, update.kind
+    );
+}
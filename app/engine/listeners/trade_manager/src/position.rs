@@ -0,0 +1,802 @@
+use domain::types::{
+    lifecycle::is_nothing_left,
+    market::OrderUpdate,
+    order_side::OrderSide,
+    signal_update::StopLevel,
+    symbol::Symbol,
+    trade::{ExitReason, TradeOpened, TradeUpdate, TradeUpdateKind},
+};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::config::TradeManagerConfig;
+use crate::trailing::Trail;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LadderStep {
+    TakeProfit {
+        /// 1-based target level (TP1, TP2, ...).
+        level: usize,
+        price: f64,
+        /// Share of the *remaining* position to close, as expected by `close_percentage`.
+        close_percent: f64,
+        /// Share of the *original* position closed by this step.
+        closed_fraction: f64,
+        new_stop: Option<f64>,
+        is_final: bool,
+    },
+    StopLoss {
+        price: f64,
+    },
+    /// Moves the stop behind the best price seen; nothing is sent to the exchange.
+    Trail {
+        new_stop: f64,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ManagedPosition {
+    pub intent_id: Uuid,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub entry_price: f64,
+    pub quantity: f64,
+    pub targets: Vec<f64>,
+    pub timeframe: String,
+    pub source: Option<i64>,
+    pub stop: f64,
+    pub failed_attempts: u32,
+    /// Set once trailing was set up (or failed to), so it is only attempted once.
+    pub trailing_armed: bool,
+    /// Exchange-side `TRAILING_STOP_MARKET` order, for the native trailing mode.
+    pub trailing_order_id: Option<i64>,
+    /// Closing orders sent since the ledger last took them, so their fills are matched to the
+    /// trade.
+    pub exit_orders: Vec<i64>,
+    trail: Option<Trail>,
+    best_price: f64,
+    last_price: f64,
+    // Entry, last target hit or last time-based tightening.
+    progressed_at: Instant,
+    break_even: f64,
+    next_target: usize,
+    remaining_fraction: f64,
+    realized_pnl: f64,
+}
+
+impl ManagedPosition {
+    /// `fee_rate` is the taker commission rate; pass 0.0 for a plain break-even stop.
+    pub fn new(opened: &TradeOpened, fee_rate: f64) -> Self {
+        // Entry and exit are both charged, so break-even has to cover two fees.
+        let fee_offset = opened.entry_price * fee_rate * 2.0;
+
+        let break_even = if opened.side.is_long() {
+            opened.entry_price + fee_offset
+        } else {
+            opened.entry_price - fee_offset
+        };
+
+        Self {
+            intent_id: opened.intent_id,
+            symbol: opened.symbol,
+            side: opened.side,
+            entry_price: opened.entry_price,
+            quantity: opened.quantity,
+            targets: opened.targets.clone(),
+            timeframe: opened.timeframe.clone(),
+            source: opened.source,
+            stop: opened.stop_loss,
+            failed_attempts: 0,
+            trailing_armed: false,
+            trailing_order_id: None,
+            exit_orders: Vec::new(),
+            trail: None,
+            best_price: opened.entry_price,
+            last_price: opened.entry_price,
+            progressed_at: Instant::now(),
+            break_even,
+            next_target: 0,
+            remaining_fraction: 1.0,
+            realized_pnl: 0.0,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        is_nothing_left(self.remaining_fraction)
+    }
+
+    pub fn targets_hit(&self) -> usize {
+        self.next_target
+    }
+
+    pub fn remaining_quantity(&self) -> f64 {
+        self.quantity * self.remaining_fraction
+    }
+
+    /// Starts trailing the stop from `price`.
+    pub fn start_trailing(&mut self, trail: Trail, price: f64) {
+        self.trail = Some(trail);
+        self.best_price = price;
+    }
+
+    /// Records the latest price, so a trailing stop can follow it.
+    pub fn observe(&mut self, price: f64) {
+        self.last_price = price;
+
+        if self.trail.is_some() && self.is_reached(price, self.best_price) {
+            self.best_price = price;
+        }
+    }
+
+    /// Returns the next action required at `price`, without changing any state.
+    /// State is only advanced through `apply`, once the exchange accepted the order.
+    pub fn next_step(&self, price: f64, config: &TradeManagerConfig) -> Option<LadderStep> {
+        if self.is_closed() {
+            return None;
+        }
+
+        if self.is_stop_hit(price) {
+            return Some(LadderStep::StopLoss { price });
+        }
+
+        self.take_profit_step(price, config)
+            .or_else(|| self.trail_step())
+    }
+
+    fn trail_step(&self) -> Option<LadderStep> {
+        let new_stop = self.trail?.stop_from(self.best_price, self.side);
+
+        self.improves_stop(new_stop)
+            .then_some(LadderStep::Trail { new_stop })
+    }
+
+    fn take_profit_step(&self, price: f64, config: &TradeManagerConfig) -> Option<LadderStep> {
+        // An edit can leave fewer targets than were already hit; the last one then takes the rest.
+        let target = match self.targets.get(self.next_target) {
+            Some(target) => *target,
+            None => *self.targets.last()?,
+        };
+
+        if !self.is_reached(price, target) {
+            return None;
+        }
+
+        let is_last_target = self.next_target + 1 >= self.targets.len();
+
+        let closed_fraction = if is_last_target {
+            self.remaining_fraction
+        } else {
+            config
+                .take_profit_fractions
+                .get(self.next_target)
+                .copied()
+                .unwrap_or(0.0)
+                .clamp(0.0, self.remaining_fraction)
+        };
+
+        let new_stop = self
+            .stop_after_target(self.next_target, config)
+            .filter(|stop| self.improves_stop(*stop));
+
+        Some(LadderStep::TakeProfit {
+            level: self.next_target + 1,
+            price,
+            close_percent: closed_fraction / self.remaining_fraction * 100.0,
+            closed_fraction,
+            new_stop,
+            is_final: is_last_target || is_nothing_left(self.remaining_fraction - closed_fraction),
+        })
+    }
+
+    /// Advances the position past `step` and returns the lifecycle updates to publish.
+    pub fn apply(&mut self, step: &LadderStep) -> Vec<TradeUpdate> {
+        let mut kinds = Vec::new();
+
+        match *step {
+            LadderStep::TakeProfit {
+                level,
+                price,
+                closed_fraction,
+                new_stop,
+                is_final,
+                ..
+            } => {
+                let pnl = self.pnl(price, closed_fraction);
+                self.realized_pnl += pnl;
+                self.remaining_fraction -= closed_fraction;
+                self.next_target += 1;
+                self.progressed_at = Instant::now();
+
+                kinds.push(TradeUpdateKind::TargetHit {
+                    level,
+                    price,
+                    closed_fraction,
+                    pnl,
+                });
+
+                if is_final {
+                    self.remaining_fraction = 0.0;
+
+                    kinds.push(TradeUpdateKind::Closed {
+                        price,
+                        pnl: self.realized_pnl,
+                        reason: ExitReason::FinalTarget,
+                    });
+                } else if let Some(to) = new_stop {
+                    kinds.push(TradeUpdateKind::StopMoved {
+                        from: self.stop,
+                        to,
+                    });
+                    self.stop = to;
+                }
+            }
+
+            LadderStep::StopLoss { price } => {
+                self.realized_pnl += self.pnl(price, self.remaining_fraction);
+                self.remaining_fraction = 0.0;
+
+                kinds.push(TradeUpdateKind::Closed {
+                    price,
+                    pnl: self.realized_pnl,
+                    reason: ExitReason::StopLoss,
+                });
+            }
+
+            LadderStep::Trail { new_stop } => {
+                kinds.push(TradeUpdateKind::StopMoved {
+                    from: self.stop,
+                    to: new_stop,
+                });
+                self.stop = new_stop;
+            }
+        }
+
+        kinds
+            .into_iter()
+            .map(|kind| TradeUpdate {
+                intent_id: self.intent_id,
+                symbol: self.symbol,
+                kind,
+            })
+            .collect()
+    }
+
+    /// Closes the rest of the position outside of the ladder, at the last observed price.
+    pub fn close(&mut self, reason: ExitReason) -> Vec<TradeUpdate> {
+        self.close_at(self.last_price, reason)
+    }
+
+    /// Closes the position once its exchange-side trailing stop has filled. Returns `None` for
+    /// any other order.
+    pub fn trailing_filled(&mut self, order: &OrderUpdate) -> Option<Vec<TradeUpdate>> {
+        if self.trailing_order_id != Some(order.order_id) || order.status != "FILLED" {
+            return None;
+        }
+
+        self.trailing_order_id = None;
+        Some(self.close_at(order.last_filled_price, ExitReason::StopLoss))
+    }
+
+    fn close_at(&mut self, price: f64, reason: ExitReason) -> Vec<TradeUpdate> {
+        self.realized_pnl += self.pnl(price, self.remaining_fraction);
+        self.remaining_fraction = 0.0;
+
+        vec![TradeUpdate {
+            intent_id: self.intent_id,
+            symbol: self.symbol,
+            kind: TradeUpdateKind::Closed {
+                price,
+                pnl: self.realized_pnl,
+                reason,
+            },
+        }]
+    }
+
+    /// Closes `percent` of the remaining position outside of the ladder, at the last observed
+    /// price.
+    pub fn close_partial(&mut self, percent: f64) -> TradeUpdate {
+        let price = self.last_price;
+        let closed_fraction = self.remaining_fraction * (percent / 100.0).clamp(0.0, 1.0);
+
+        let pnl = self.pnl(price, closed_fraction);
+        self.realized_pnl += pnl;
+        self.remaining_fraction -= closed_fraction;
+
+        TradeUpdate {
+            intent_id: self.intent_id,
+            symbol: self.symbol,
+            kind: TradeUpdateKind::PartialClose {
+                price,
+                closed_fraction,
+                pnl,
+            },
+        }
+    }
+
+    /// Moves the stop where the provider asked. Unlike the ladder, the provider may loosen it.
+    pub fn move_stop(&mut self, to: StopLevel) -> Option<TradeUpdate> {
+        let to = match to {
+            StopLevel::Entry => self.break_even,
+            StopLevel::Price(price) => price,
+        };
+
+        if to == self.stop {
+            return None;
+        }
+
+        let from = self.stop;
+        self.stop = to;
+
+        Some(TradeUpdate {
+            intent_id: self.intent_id,
+            symbol: self.symbol,
+            kind: TradeUpdateKind::StopMoved { from, to },
+        })
+    }
+
+    /// Replaces the levels with the ones of an edited signal. The ladder keeps its progress: with
+    /// one target hit, the next one taken is the second of the new list. When the new list is not
+    /// longer than the targets already hit, its last target closes the rest.
+    pub fn amend(&mut self, targets: &[f64], stop_loss: f64) -> Option<TradeUpdate> {
+        self.targets = targets.to_vec();
+        self.move_stop(StopLevel::Price(stop_loss))
+    }
+
+    /// True when nothing happened to the trade for `limit`.
+    pub fn is_stale(&self, limit: Duration, now: Instant) -> bool {
+        now.saturating_duration_since(self.progressed_at) >= limit
+    }
+
+    /// Moves the stop `fraction` of the way towards the last observed price and restarts the
+    /// stale clock. Returns None when that would not tighten the stop.
+    pub fn tighten(&mut self, fraction: f64) -> Option<TradeUpdate> {
+        self.progressed_at = Instant::now();
+
+        let new_stop = self.stop + (self.last_price - self.stop) * fraction.clamp(0.0, 1.0);
+
+        if !self.improves_stop(new_stop) {
+            return None;
+        }
+
+        let from = self.stop;
+        self.stop = new_stop;
+
+        Some(TradeUpdate {
+            intent_id: self.intent_id,
+            symbol: self.symbol,
+            kind: TradeUpdateKind::StopMoved { from, to: new_stop },
+        })
+    }
+
+    fn stop_after_target(&self, target_index: usize, config: &TradeManagerConfig) -> Option<f64> {
+        match target_index {
+            0 if config.break_even_after_tp1 => Some(self.break_even),
+            1 if config.stop_to_tp1_after_tp2 => self.targets.first().copied(),
+            _ => None,
+        }
+    }
+
+    fn pnl(&self, exit_price: f64, fraction: f64) -> f64 {
+        let direction = if self.side.is_long() { 1.0 } else { -1.0 };
+        (exit_price - self.entry_price) * self.quantity * fraction * direction
+    }
+
+    fn is_stop_hit(&self, price: f64) -> bool {
+        if self.side.is_long() {
+            price <= self.stop
+        } else {
+            price >= self.stop
+        }
+    }
+
+    fn is_reached(&self, price: f64, target: f64) -> bool {
+        if self.side.is_long() {
+            price >= target
+        } else {
+            price <= target
+        }
+    }
+
+    // A stop may only be tightened, never loosened.
+    fn improves_stop(&self, stop: f64) -> bool {
+        if self.side.is_long() {
+            stop > self.stop
+        } else {
+            stop < self.stop
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opened(side: OrderSide, entry: f64, targets: &[f64], stop_loss: f64) -> TradeOpened {
+        TradeOpened {
+            intent_id: Uuid::new_v4(),
+            symbol: Symbol::BTC,
+            side,
+            entry_price: entry,
+            quantity: 2.0,
+            targets: targets.to_vec(),
+            timeframe: "1h".to_string(),
+            stop_loss,
+            source: None,
+        }
+    }
+
+    fn step_and_apply(position: &mut ManagedPosition, price: f64) -> Vec<TradeUpdate> {
+        let config = TradeManagerConfig::default();
+        let step = position.next_step(price, &config).expect("expected a step");
+        position.apply(&step)
+    }
+
+    #[test]
+    fn test_nothing_happens_between_stop_and_tp1() {
+        let position = ManagedPosition::new(&opened(OrderSide::Buy, 100.0, &[110.0], 90.0), 0.0);
+
+        assert_eq!(
+            position.next_step(105.0, &TradeManagerConfig::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tp1_closes_half_and_moves_stop_to_break_even() {
+        let mut position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[110.0, 120.0, 130.0], 90.0),
+            0.0,
+        );
+
+        let step = position
+            .next_step(110.0, &TradeManagerConfig::default())
+            .unwrap();
+
+        assert_eq!(
+            step,
+            LadderStep::TakeProfit {
+                level: 1,
+                price: 110.0,
+                close_percent: 50.0,
+                closed_fraction: 0.5,
+                new_stop: Some(100.0),
+                is_final: false,
+            }
+        );
+
+        let updates = position.apply(&step);
+
+        assert_eq!(updates.len(), 2);
+        assert!(matches!(
+            updates[0].kind,
+            TradeUpdateKind::TargetHit { level: 1, pnl, .. } if pnl == 10.0
+        ));
+        assert!(matches!(
+            updates[1].kind,
+            TradeUpdateKind::StopMoved {
+                from: 90.0,
+                to: 100.0
+            }
+        ));
+        assert_eq!(position.stop, 100.0);
+    }
+
+    #[test]
+    fn test_break_even_includes_round_trip_fees() {
+        let position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[110.0, 120.0], 90.0),
+            0.0005,
+        );
+
+        let Some(LadderStep::TakeProfit { new_stop, .. }) =
+            position.next_step(110.0, &TradeManagerConfig::default())
+        else {
+            panic!("expected take profit");
+        };
+
+        assert!((new_stop.unwrap() - 100.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tp2_uses_remaining_share_and_moves_stop_to_tp1() {
+        let mut position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[110.0, 120.0, 130.0], 90.0),
+            0.0,
+        );
+
+        step_and_apply(&mut position, 110.0);
+
+        let Some(LadderStep::TakeProfit {
+            close_percent,
+            closed_fraction,
+            new_stop,
+            ..
+        }) = position.next_step(120.0, &TradeManagerConfig::default())
+        else {
+            panic!("expected take profit");
+        };
+
+        // 0.3 of the original size is 60% of the remaining half.
+        assert!((close_percent - 60.0).abs() < 1e-9);
+        assert_eq!(closed_fraction, 0.3);
+        assert_eq!(new_stop, Some(110.0));
+    }
+
+    #[test]
+    fn test_last_target_closes_remaining_position() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Buy, 100.0, &[110.0, 120.0], 90.0), 0.0);
+
+        step_and_apply(&mut position, 110.0);
+        let updates = step_and_apply(&mut position, 120.0);
+
+        assert!(position.is_closed());
+        assert!(matches!(
+            updates.last().unwrap().kind,
+            TradeUpdateKind::Closed {
+                reason: ExitReason::FinalTarget,
+                pnl,
+                ..
+            } if pnl == 30.0
+        ));
+    }
+
+    #[test]
+    fn test_stop_after_tp1_exits_at_break_even() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Buy, 100.0, &[110.0, 120.0], 90.0), 0.0);
+
+        step_and_apply(&mut position, 110.0);
+        let updates = step_and_apply(&mut position, 99.5);
+
+        assert!(position.is_closed());
+        assert!(matches!(
+            updates[0].kind,
+            TradeUpdateKind::Closed {
+                reason: ExitReason::StopLoss,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_short_ladder_mirrors_long() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Sell, 100.0, &[90.0, 80.0], 110.0), 0.0);
+
+        assert_eq!(
+            position.next_step(95.0, &TradeManagerConfig::default()),
+            None
+        );
+
+        step_and_apply(&mut position, 89.0);
+        assert_eq!(position.stop, 100.0);
+
+        let updates = step_and_apply(&mut position, 101.0);
+        assert!(matches!(
+            updates[0].kind,
+            TradeUpdateKind::Closed {
+                reason: ExitReason::StopLoss,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_trailing_stop_follows_best_price_only() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Buy, 100.0, &[150.0], 90.0), 0.0);
+        position.start_trailing(Trail::Percent(5.0), 100.0);
+
+        position.observe(120.0);
+        step_and_apply(&mut position, 120.0);
+        assert_eq!(position.stop, 114.0);
+
+        // A pullback leaves the stop where it was.
+        position.observe(116.0);
+        assert_eq!(
+            position.next_step(116.0, &TradeManagerConfig::default()),
+            None
+        );
+
+        position.observe(113.0);
+        let updates = step_and_apply(&mut position, 113.0);
+        assert!(matches!(
+            updates[0].kind,
+            TradeUpdateKind::Closed {
+                reason: ExitReason::StopLoss,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_short_trailing_stop_moves_down() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Sell, 100.0, &[50.0], 110.0), 0.0);
+        position.start_trailing(Trail::Absolute(4.0), 100.0);
+
+        position.observe(90.0);
+        step_and_apply(&mut position, 90.0);
+
+        assert_eq!(position.stop, 94.0);
+    }
+
+    #[test]
+    fn test_close_uses_last_observed_price() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Buy, 100.0, &[110.0, 120.0], 90.0), 0.0);
+
+        step_and_apply(&mut position, 110.0);
+        position.observe(105.0);
+
+        let updates = position.close(ExitReason::Conflict);
+
+        assert!(position.is_closed());
+        // 10 on the first half, 5 on the second.
+        assert!(matches!(
+            updates[0].kind,
+            TradeUpdateKind::Closed {
+                reason: ExitReason::Conflict,
+                pnl,
+                price: 105.0,
+            } if pnl == 15.0
+        ));
+    }
+
+    #[test]
+    fn test_target_hit_restarts_stale_clock() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Buy, 100.0, &[110.0, 120.0], 90.0), 0.0);
+        let limit = Duration::from_secs(3600);
+        let opened_at = position.progressed_at;
+
+        assert!(!position.is_stale(limit, opened_at + Duration::from_secs(3599)));
+        assert!(position.is_stale(limit, opened_at + limit));
+
+        step_and_apply(&mut position, 110.0);
+
+        assert!(!position.is_stale(limit, position.progressed_at + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_tighten_moves_stop_towards_price() {
+        let mut position =
+            ManagedPosition::new(&opened(OrderSide::Sell, 100.0, &[90.0], 110.0), 0.0);
+        position.observe(102.0);
+
+        let update = position.tighten(0.5).unwrap();
+
+        assert!(matches!(
+            update.kind,
+            TradeUpdateKind::StopMoved {
+                from: 110.0,
+                to: 106.0
+            }
+        ));
+
+        // Tightening by nothing is not a move.
+        assert!(position.tighten(0.0).is_none());
+    }
+
+    #[test]
+    fn test_stop_is_never_loosened() {
+        // TP1 sits below entry, so TP1 as a stop would be worse than break-even.
+        let mut position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[99.0, 120.0, 130.0], 90.0),
+            0.0,
+        );
+
+        step_and_apply(&mut position, 99.0);
+        step_and_apply(&mut position, 120.0);
+
+        assert_eq!(position.stop, 100.0);
+    }
+
+    #[test]
+    fn test_provider_partial_close_is_share_of_remaining() {
+        let mut position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[110.0, 120.0, 130.0], 90.0),
+            0.0,
+        );
+
+        step_and_apply(&mut position, 110.0);
+        position.observe(105.0);
+
+        let update = position.close_partial(50.0);
+
+        assert!(matches!(
+            update.kind,
+            TradeUpdateKind::PartialClose { price: 105.0, closed_fraction, pnl }
+                if closed_fraction == 0.25 && pnl == 2.5
+        ));
+        assert_eq!(position.remaining_quantity(), 0.5);
+    }
+
+    #[test]
+    fn test_provider_may_loosen_the_stop() {
+        let mut position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[110.0, 120.0], 90.0),
+            0.0005,
+        );
+
+        let update = position.move_stop(StopLevel::Entry).unwrap();
+        assert!(matches!(
+            update.kind,
+            TradeUpdateKind::StopMoved { from: 90.0, .. }
+        ));
+        assert!((position.stop - 100.1).abs() < 1e-9);
+
+        assert!(position.move_stop(StopLevel::Price(85.0)).is_some());
+        assert_eq!(position.stop, 85.0);
+        assert!(position.move_stop(StopLevel::Price(85.0)).is_none());
+    }
+
+    #[test]
+    fn test_amended_ladder_shorter_than_targets_hit_closes_at_its_last_target() {
+        let mut position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[110.0, 120.0, 130.0], 90.0),
+            0.0,
+        );
+
+        step_and_apply(&mut position, 110.0);
+        step_and_apply(&mut position, 120.0);
+
+        position.amend(&[115.0], 110.0);
+
+        let config = TradeManagerConfig::default();
+        assert_eq!(position.next_step(112.0, &config), None);
+
+        let updates = step_and_apply(&mut position, 116.0);
+
+        assert!(position.is_closed());
+        assert!(matches!(
+            updates.last().unwrap().kind,
+            TradeUpdateKind::Closed {
+                reason: ExitReason::FinalTarget,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_filled_trailing_stop_closes_the_position_at_its_fill() {
+        let mut position = ManagedPosition::new(
+            &opened(OrderSide::Buy, 100.0, &[110.0, 120.0, 130.0], 90.0),
+            0.0,
+        );
+        step_and_apply(&mut position, 110.0);
+        position.trailing_order_id = Some(42);
+
+        let mut order = OrderUpdate {
+            symbol: Symbol::BTC,
+            order_id: 7,
+            side: OrderSide::Sell,
+            execution_type: "TRADE".to_string(),
+            status: "FILLED".to_string(),
+            last_filled_quantity: 1.0,
+            last_filled_price: 115.0,
+            commission: 0.0,
+            commission_asset: "USDT".to_string(),
+            realized_profit: 0.0,
+            reduce_only: true,
+            event_time_ms: 0,
+        };
+        assert!(position.trailing_filled(&order).is_none());
+
+        order.order_id = 42;
+        let updates = position.trailing_filled(&order).unwrap();
+
+        assert!(position.is_closed());
+        assert_eq!(position.trailing_order_id, None);
+        assert_eq!(updates.len(), 1);
+        assert!(matches!(
+            updates[0].kind,
+            TradeUpdateKind::Closed {
+                price: 115.0,
+                reason: ExitReason::StopLoss,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use domain::types::timeframe::Timeframe;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TimeExitAction {
+    /// Close the rest of the position at market.
+    Close,
+    /// Move the stop `fraction` of the way from where it is towards the current price.
+    /// The ladder keeps running, and the stop is tightened again if the trade stays stale.
+    Tighten { fraction: f64 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TimeExitConfig {
+    /// A trade that reaches neither its next target nor its stop within this many candles
+    /// of its signal timeframe, counted from entry or from the last target hit, is stale.
+    /// Disabled when unset.
+    pub after_candles: Option<u32>,
+    pub action: TimeExitAction,
+}
+
+impl Default for TimeExitConfig {
+    fn default() -> Self {
+        Self {
+            after_candles: None,
+            action: TimeExitAction::Close,
+        }
+    }
+}
+
+impl TimeExitConfig {
+    /// How long a trade on `timeframe` may stay open. None when disabled or the timeframe is invalid.
+    pub fn limit_for(&self, timeframe: &str) -> Option<Duration> {
+        let candles = self.after_candles?;
+        let timeframe = timeframe.parse::<Timeframe>().ok()?;
+
+        Some(timeframe.candles(candles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_is_a_multiple_of_the_timeframe() {
+        let config = TimeExitConfig {
+            after_candles: Some(6),
+            ..TimeExitConfig::default()
+        };
+
+        assert_eq!(config.limit_for("4h"), Some(Duration::from_secs(24 * 3600)));
+        assert_eq!(config.limit_for("soon"), None);
+        assert_eq!(TimeExitConfig::default().limit_for("4h"), None);
+    }
+}
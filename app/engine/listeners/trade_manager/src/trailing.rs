@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use binance::response_types::Kline;
+use domain::types::order_side::OrderSide;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum TrailingMode {
+    /// Stop follows the best price at a fixed percentage distance.
+    Percent { percent: f64 },
+    /// Stop follows the best price at `multiplier` x ATR, measured on `interval` klines
+    /// when trailing is armed.
+    Atr {
+        interval: String,
+        period: usize,
+        multiplier: f64,
+    },
+    /// Delegated to Binance as a `TRAILING_STOP_MARKET` order. `callback_rate` is in percent.
+    Native { callback_rate: f64 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TrailingConfig {
+    /// Used when neither the source nor the timeframe has a mode of its own.
+    /// Trailing is disabled when nothing matches.
+    pub default: Option<TrailingMode>,
+    /// Keyed by source peer ID. Takes precedence over `by_timeframe`.
+    pub by_source: HashMap<String, TrailingMode>,
+    /// Keyed by signal timeframe (15m, 1h, 4h, ...).
+    pub by_timeframe: HashMap<String, TrailingMode>,
+    /// Number of targets that must be hit before trailing starts. 0 trails from entry.
+    pub activate_after_target: usize,
+}
+
+impl Default for TrailingConfig {
+    fn default() -> Self {
+        Self {
+            default: None,
+            by_source: HashMap::new(),
+            by_timeframe: HashMap::new(),
+            activate_after_target: 1,
+        }
+    }
+}
+
+impl TrailingConfig {
+    pub fn mode_for(&self, source: Option<i64>, timeframe: &str) -> Option<&TrailingMode> {
+        source
+            .and_then(|source| self.by_source.get(&source.to_string()))
+            .or_else(|| self.by_timeframe.get(timeframe))
+            .or(self.default.as_ref())
+    }
+}
+
+/// Distance kept between the best price seen and the stop, for trails run by the manager.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trail {
+    Percent(f64),
+    Absolute(f64),
+}
+
+impl Trail {
+    pub fn stop_from(&self, best_price: f64, side: OrderSide) -> f64 {
+        let distance = match *self {
+            Trail::Percent(percent) => best_price * percent / 100.0,
+            Trail::Absolute(distance) => distance,
+        };
+
+        if side.is_long() {
+            best_price - distance
+        } else {
+            best_price + distance
+        }
+    }
+}
+
+/// Wilder's ATR over the last `period` true ranges. Needs `period + 1` klines.
+pub fn average_true_range(klines: &[Kline], period: usize) -> Option<f64> {
+    if period == 0 || klines.len() < period + 1 {
+        return None;
+    }
+
+    let candles = klines
+        .iter()
+        .map(|kline| {
+            Some((
+                kline.high.parse::<f64>().ok()?,
+                kline.low.parse::<f64>().ok()?,
+                kline.close.parse::<f64>().ok()?,
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let true_ranges: Vec<f64> = candles
+        .windows(2)
+        .map(|pair| {
+            let (_, _, prev_close) = pair[0];
+            let (high, low, _) = pair[1];
+
+            (high - low)
+                .max((high - prev_close).abs())
+                .max((low - prev_close).abs())
+        })
+        .collect();
+
+    let (seed, rest) = true_ranges.split_at(period);
+    let mut atr = seed.iter().sum::<f64>() / period as f64;
+
+    for true_range in rest {
+        atr = (atr * (period as f64 - 1.0) + true_range) / period as f64;
+    }
+
+    Some(atr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(high: f64, low: f64, close: f64) -> Kline {
+        Kline {
+            open_time: 0,
+            open: close.to_string(),
+            high: high.to_string(),
+            low: low.to_string(),
+            close: close.to_string(),
+            volume: "0".to_string(),
+            close_time: 0,
+            quote_volume: "0".to_string(),
+            trades: 0,
+            taker_buy_base_volume: "0".to_string(),
+            taker_buy_quote_volume: "0".to_string(),
+            _ignore: "0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mode_prefers_source_then_timeframe_then_default() {
+        let percent = TrailingMode::Percent { percent: 1.0 };
+        let native = TrailingMode::Native { callback_rate: 0.5 };
+        let default = TrailingMode::Percent { percent: 3.0 };
+
+        let config = TrailingConfig {
+            default: Some(default.clone()),
+            by_source: HashMap::from([("-100".to_string(), native.clone())]),
+            by_timeframe: HashMap::from([("1h".to_string(), percent.clone())]),
+            activate_after_target: 1,
+        };
+
+        assert_eq!(config.mode_for(Some(-100), "1h"), Some(&native));
+        assert_eq!(config.mode_for(Some(-200), "1h"), Some(&percent));
+        assert_eq!(config.mode_for(None, "4h"), Some(&default));
+    }
+
+    #[test]
+    fn test_trail_stop_is_behind_best_price() {
+        assert_eq!(Trail::Percent(2.0).stop_from(100.0, OrderSide::Buy), 98.0);
+        assert_eq!(
+            Trail::Absolute(5.0).stop_from(100.0, OrderSide::Sell),
+            105.0
+        );
+    }
+
+    #[test]
+    fn test_average_true_range_uses_previous_close() {
+        // Gap up: the true range of the second candle is high - previous close.
+        let klines = vec![
+            kline(11.0, 9.0, 10.0),
+            kline(14.0, 13.0, 13.5),
+            kline(14.0, 12.0, 13.0),
+        ];
+
+        // TRs: 4.0, 2.0
+        assert_eq!(average_true_range(&klines, 2), Some(3.0));
+        assert_eq!(average_true_range(&klines, 3), None);
+    }
+}
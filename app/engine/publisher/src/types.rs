@@ -1,5 +1,14 @@
-use domain::types::trade::{TradeApproved, TradeRejected};
+use domain::types::execution::{AccountExecution, ExecutionQuality, ExecutionReport};
+use domain::types::lifecycle::TradeTransition;
+use domain::types::market::{OrderUpdate, PriceTick};
+use domain::types::parse_failure::SignalParseFailure;
+use domain::types::provider::Provider;
+use domain::types::signal_update::SignalUpdate;
+use domain::types::trade::{CloseTrade, TradeApproved, TradeOpened, TradeRejected, TradeUpdate};
+use domain::types::trade_intent::TradeIntent;
 use telegram_types::Message;
+use twitter::entities::Entity;
+use twitter::regex::MessageType;
 
 #[derive(Debug, Clone)]
 pub enum EventTag {
@@ -44,6 +53,24 @@ pub struct TgEvent {
     // (for each subscriber)
 }
 
+/// Messages deleted from a chat the main account is in.
+#[derive(Debug, Clone)]
+pub struct TgDeletion {
+    /// Only known for channels and supergroups; private chats and small groups share one
+    /// sequence of message IDs per account.
+    pub channel_id: Option<i64>,
+    pub message_ids: Vec<i32>,
+}
+
+/// A relay channel message, classified by the twitter parser.
+#[derive(Debug, Clone)]
+pub struct KolActivity {
+    pub message: Box<Message>,
+    pub kind: MessageType,
+    /// Found in `kind.body()`; positions are relative to it.
+    pub entities: Vec<Entity>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ErrorEvent {
     pub source: &'static str,
@@ -57,10 +84,47 @@ pub struct TradeEvent {
     pub stop_loss: f64,
 }
 
+/// Operator commands for the risk manager's kill switch, sent through the API.
+#[derive(Debug, Clone)]
+pub enum KillSwitchCommand {
+    /// Enforce the configured loss limits.
+    Arm,
+    /// Stop enforcing limits and allow entries, even if the switch has tripped.
+    Disarm,
+    /// Halt new entries right away, optionally closing everything.
+    Trip { flatten: bool },
+    /// Allow new entries again after a trip.
+    Reset,
+}
+
 #[derive(Debug, Clone)]
 pub enum PulsgramEvent {
     Telegram(TgEvent),
+    /// A message edited after it was posted.
+    TelegramEdited(TgEvent),
+    TelegramDeleted(TgDeletion),
     Error(ErrorEvent),
+    TradeIntent(TradeIntent),
     TradeApproved(TradeApproved),
     TradeRejected(TradeRejected),
+    TradeOpened(TradeOpened),
+    TradeUpdate(TradeUpdate),
+    PriceUpdate(PriceTick),
+    TradeTransition(TradeTransition),
+    OrderUpdate(OrderUpdate),
+    CloseTrade(CloseTrade),
+    KillSwitch(KillSwitchCommand),
+    ExecutionReport(ExecutionReport),
+    /// Running slippage and latency of the source and symbol of the last `ExecutionReport`.
+    ExecutionQuality(ExecutionQuality),
+    AccountExecution(AccountExecution),
+    SignalUpdate(SignalUpdate),
+    /// A message from a signal source that looked like a signal but was not turned into one.
+    SignalParseFailed(SignalParseFailure),
+    /// A captured failure sent back through the signal parsers, e.g. after a template fix.
+    SignalReplay(SignalParseFailure),
+    /// Every provider with fresh stats, published on each refresh.
+    Providers(Vec<Provider>),
+    /// A tweet, retweet, quote, reply, follow or profile update from the KOL relay.
+    KolActivity(KolActivity),
 }
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use grammers_client::Client;
+use grammers_client::message::Message;
+use grammers_session::types::PeerRef;
+use serde::Deserialize;
+
+use crate::errors::TelegramError;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CatchUpConfig {
+    /// Where the last processed message of each source is kept between runs.
+    pub state_path: String,
+    /// Most messages fetched per source after a restart; older ones are skipped.
+    pub max_messages: usize,
+    pub sources: Vec<CatchUpSource>,
+}
+
+impl Default for CatchUpConfig {
+    fn default() -> Self {
+        Self {
+            state_path: "catch_up.json".into(),
+            max_messages: 100,
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// A chat whose messages posted while the engine was down are fetched on startup.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatchUpSource {
+    pub chat_id: i64,
+    /// Overrides `max_messages` for this chat.
+    pub max_messages: Option<usize>,
+}
+
+/// The last message processed from each source, saved as JSON.
+#[derive(Debug)]
+pub struct Cursors {
+    path: PathBuf,
+    last: HashMap<i64, i32>,
+}
+
+impl Cursors {
+    /// Starts empty when the file does not exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, TelegramError> {
+        let path = path.into();
+
+        let last = match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw).map_err(|e| {
+                TelegramError::Other(format!("Invalid catch-up state in {}: {e}", path.display()))
+            })?,
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, last })
+    }
+
+    pub fn last(&self, chat_id: i64) -> Option<i32> {
+        self.last.get(&chat_id).copied()
+    }
+
+    /// False when the message is not newer than the last one processed.
+    pub fn advance(&mut self, chat_id: i64, message_id: i32) -> bool {
+        match self.last.get(&chat_id) {
+            Some(last) if *last >= message_id => false,
+            _ => {
+                self.last.insert(chat_id, message_id);
+                true
+            }
+        }
+    }
+
+    /// Written next to the file and renamed over it, so a crash never leaves half a file.
+    pub async fn save(&self) -> Result<(), TelegramError> {
+        let raw = serde_json::to_string(&self.last)
+            .map_err(|e| TelegramError::Other(format!("Catch-up state: {e}")))?;
+
+        let temp = self.path.with_extension("tmp");
+
+        tokio::fs::write(&temp, raw).await?;
+        tokio::fs::rename(&temp, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+/// Sources to catch up on, resolved against the listening account's dialogs.
+pub struct CatchUp {
+    /// Peer and message limit of each source, by bare chat ID.
+    sources: HashMap<i64, (PeerRef, usize)>,
+    cursors: Cursors,
+}
+
+impl CatchUp {
+    pub fn new(
+        config: &CatchUpConfig,
+        peers: &HashMap<i64, PeerRef>,
+    ) -> Result<Self, TelegramError> {
+        let mut sources = HashMap::with_capacity(config.sources.len());
+
+        for source in &config.sources {
+            let peer = peers.get(&source.chat_id).copied().ok_or_else(|| {
+                TelegramError::Other(format!(
+                    "Catch-up source {} is not in the dialogs",
+                    source.chat_id
+                ))
+            })?;
+
+            sources.insert(
+                source.chat_id,
+                (peer, source.max_messages.unwrap_or(config.max_messages)),
+            );
+        }
+
+        Ok(Self {
+            sources,
+            cursors: Cursors::load(&config.state_path)?,
+        })
+    }
+
+    /// Messages posted in each source since the last processed one, oldest first.
+    ///
+    /// A source without a saved cursor only records where it starts, so the first run does not
+    /// replay its history.
+    pub async fn missed(&mut self, client: &Client) -> Result<Vec<Message>, TelegramError> {
+        let mut missed = Vec::new();
+
+        for (chat_id, (peer, max_messages)) in &self.sources {
+            let last = self.cursors.last(*chat_id);
+
+            let mut messages = client.iter_messages(*peer).limit(match last {
+                Some(_) => *max_messages,
+                None => 1,
+            });
+
+            let mut from_source = Vec::new();
+
+            while let Some(message) = messages.next().await? {
+                match last {
+                    Some(last) if message.id() <= last => break,
+                    Some(_) => from_source.push(message),
+                    None => {
+                        self.cursors.advance(*chat_id, message.id());
+                    }
+                }
+            }
+
+            if from_source.len() == *max_messages {
+                println!(
+                    "Catch-up of {} stopped at {} messages; older ones are skipped.",
+                    chat_id, max_messages
+                );
+            }
+
+            from_source.reverse();
+            missed.extend(from_source);
+        }
+
+        self.cursors.save().await?;
+
+        Ok(missed)
+    }
+
+    /// Whether the message should go through the pipeline. Messages from sources are only
+    /// passed on once, whether they came from catch-up or as a live update.
+    pub async fn process(&mut self, message: &Message) -> bool {
+        let chat_id = message.peer_id().bare_id();
+
+        if !self.sources.contains_key(&chat_id) {
+            return true;
+        }
+
+        if !self.cursors.advance(chat_id, message.id()) {
+            return false;
+        }
+
+        // The message is still processed; at worst it is fetched again after a restart.
+        if let Err(e) = self.cursors.save().await {
+            eprintln!("Failed to save catch-up state: {e}");
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("pulsgram_{}_{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_cursors_only_move_forward() {
+        let mut cursors = Cursors::load(temp_path("forward")).unwrap();
+
+        assert_eq!(cursors.last(1), None);
+        assert!(cursors.advance(1, 10));
+        assert!(!cursors.advance(1, 10));
+        assert!(!cursors.advance(1, 9));
+        assert!(cursors.advance(1, 11));
+        assert_eq!(cursors.last(1), Some(11));
+    }
+
+    #[tokio::test]
+    async fn test_cursors_survive_a_restart() {
+        let path = temp_path("restart");
+
+        let mut cursors = Cursors::load(&path).unwrap();
+        cursors.advance(1234567890, 42);
+        cursors.advance(7, 3);
+        cursors.save().await.unwrap();
+
+        let reloaded = Cursors::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(reloaded.last(1234567890), Some(42));
+        assert_eq!(reloaded.last(7), Some(3));
+    }
+}
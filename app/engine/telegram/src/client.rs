@@ -1,11 +1,12 @@
-use std::{
-    io::{self},
-    sync::Arc,
-};
+use std::sync::Arc;
 
-use crate::{config::load_tg_client_config, errors::TelegramError};
-use grammers_client::{InvocationError, SignInError, client::UpdatesConfiguration};
-use grammers_client::{peer::Peer, update::Update};
+use crate::{
+    catch_up::CatchUp,
+    config::{AccountSession, load_api_id},
+    errors::TelegramError,
+};
+use grammers_client::{InvocationError, client::UpdatesConfiguration};
+use grammers_client::{message::Message, peer::Peer, update::Update};
 use grammers_mtsender::SenderPool;
 use grammers_session::{storages::SqliteSession, updates::UpdatesLike};
 use grammers_tl_types::enums::{InputNotifyPeer, InputPeer};
@@ -17,51 +18,56 @@ pub struct ConnectClientReturnType {
     pub updates_receiver: UnboundedReceiver<UpdatesLike>,
 }
 
-async fn create_sender_pool(session_path: &str, api_id: i32) -> Result<SenderPool, TelegramError> {
-    Ok(SenderPool::new(
-        Arc::new(
-            SqliteSession::open(session_path)
-                .await
-                .map_err(|e| TelegramError::Other(e.to_string()))?,
-        ),
-        api_id,
-    ))
+/// A client whose session may not be logged in yet.
+pub(crate) struct Connection {
+    pub client: Client,
+    pub updates_receiver: UnboundedReceiver<UpdatesLike>,
+    /// Kept to move the session to another DC during QR login.
+    pub session: Arc<SqliteSession>,
 }
 
-pub async fn connect_client(
+pub(crate) async fn open_session(
     session_path: &str,
-    api_id_var: &str,
-    api_hash_var: &str,
-    phone_number_var: &str,
-    password_var: &str,
-) -> Result<ConnectClientReturnType, TelegramError> {
-    let config = load_tg_client_config(api_id_var, api_hash_var, phone_number_var, password_var)?;
+    api_id: i32,
+) -> Result<Connection, TelegramError> {
+    let session = Arc::new(
+        SqliteSession::open(session_path)
+            .await
+            .map_err(|e| TelegramError::Other(e.to_string()))?,
+    );
 
-    let sender_pool = create_sender_pool(session_path, config.api_id).await?;
+    let sender_pool = SenderPool::new(Arc::clone(&session), api_id);
 
     let client = Client::new(sender_pool.handle);
 
     tokio::spawn(sender_pool.runner.run());
 
+    Ok(Connection {
+        client,
+        updates_receiver: sender_pool.updates,
+        session,
+    })
+}
+
+/// Connects with a session created by the `login` subcommand.
+///
+/// Never prompts: an expired or missing session fails with `TelegramError::Unauthorized`, so
+/// the service does not hang waiting for a code when started unattended.
+pub async fn connect_client(
+    account: &AccountSession,
+) -> Result<ConnectClientReturnType, TelegramError> {
+    let api_id = load_api_id(account.api_id_var)?;
+
+    let Connection {
+        client,
+        updates_receiver,
+        ..
+    } = open_session(account.session_path, api_id).await?;
+
     if !client.is_authorized().await? {
-        let token = client
-            .request_login_code(&config.phone_number, config.api_hash.as_str())
-            .await?;
-
-        println!("Enter the OTP code: ");
-        let mut code = String::new();
-        io::stdin().read_line(&mut code)?;
-        let code = code.trim();
-
-        match client.sign_in(&token, code).await {
-            Ok(_) => println!("Logged in successfully!"),
-            Err(SignInError::PasswordRequired(password_token)) => {
-                client
-                    .check_password(password_token, &config.password)
-                    .await?;
-            }
-            Err(e) => return Err(e.into()),
-        }
+        return Err(TelegramError::Unauthorized {
+            session_path: account.session_path.to_string(),
+        });
     }
 
     println!(
@@ -70,7 +76,7 @@ pub async fn connect_client(
     );
     Ok(ConnectClientReturnType {
         client,
-        updates_receiver: sender_pool.updates,
+        updates_receiver,
     })
 }
 
@@ -79,6 +85,7 @@ pub async fn handle_updates(
     updates_receiver: UnboundedReceiver<UpdatesLike>,
     event_bus: Arc<publisher::EventBus>,
     dispatcher_user_id: i64,
+    mut catch_up: CatchUp,
 ) {
     let mut updates = client
         .stream_updates(
@@ -92,6 +99,29 @@ pub async fn handle_updates(
 
     println!("Updates handler spawned.");
 
+    // Live updates wait in the stream while the missed messages are fetched and published.
+    match catch_up.missed(&client).await {
+        Ok(missed) => {
+            println!("Caught up on {} missed messages.", missed.len());
+
+            for message in missed {
+                if !is_ignored(&message, dispatcher_user_id) && catch_up.process(&message).await {
+                    event_bus.publish(publisher::types::PulsgramEvent::Telegram(
+                        publisher::types::TgEvent {
+                            message: Box::new(message),
+                        },
+                    ));
+                }
+            }
+        }
+        Err(e) => event_bus.publish(publisher::types::PulsgramEvent::Error(
+            publisher::types::ErrorEvent {
+                message_text: format!("Catch-up failed: {}", e),
+                source: "UpdateHandler::CatchUp",
+            },
+        )),
+    }
+
     loop {
         let update_result = updates.next().await;
         let update = match update_result {
@@ -114,24 +144,33 @@ pub async fn handle_updates(
         };
 
         match update {
-            Update::NewMessage(message) if !message.outgoing() => {
-                let peer_id = message.peer_id().bare_id();
-
-                if let Some(sender) = message.sender()
-                    && sender.id().bare_id() == dispatcher_user_id
-                {
-                    continue;
-                }
-
-                // Ignore Pulsgram Errors channel
-                if peer_id == 3228445189 {
-                    //TODO
+            Update::NewMessage(message) if !is_ignored(&message, dispatcher_user_id) => {
+                // Already published by the catch-up.
+                if !catch_up.process(&message).await {
                     continue;
                 }
 
                 event_bus.publish(publisher::types::PulsgramEvent::Telegram(
                     publisher::types::TgEvent {
-                        message: Box::new(message),
+                        message: Box::new((*message).clone()),
+                    },
+                ));
+            }
+
+            // Providers edit signals after posting them.
+            Update::MessageEdited(message) if !is_ignored(&message, dispatcher_user_id) => {
+                event_bus.publish(publisher::types::PulsgramEvent::TelegramEdited(
+                    publisher::types::TgEvent {
+                        message: Box::new((*message).clone()),
+                    },
+                ));
+            }
+
+            Update::MessageDeleted(deletion) => {
+                event_bus.publish(publisher::types::PulsgramEvent::TelegramDeleted(
+                    publisher::types::TgDeletion {
+                        channel_id: deletion.channel_id(),
+                        message_ids: deletion.into_messages(),
                     },
                 ));
             }
@@ -140,6 +179,22 @@ pub async fn handle_updates(
     }
 }
 
+fn is_ignored(message: &Message, dispatcher_user_id: i64) -> bool {
+    if message.outgoing() {
+        return true;
+    }
+
+    if let Some(sender) = message.sender()
+        && sender.id().bare_id() == dispatcher_user_id
+    {
+        return true;
+    }
+
+    // Ignore Pulsgram Errors channel
+    //TODO
+    message.peer_id().bare_id() == 3228445189
+}
+
 //TODO: Test this v0.9.0 update.
 pub async fn toggle_mute_peer(
     client: Arc<Client>,
@@ -2,6 +2,16 @@ use std::env;
 
 use crate::errors::TelegramError;
 
+/// Where an account's session is stored, and the environment variables holding its credentials.
+#[derive(Debug, Clone, Copy)]
+pub struct AccountSession {
+    pub session_path: &'static str,
+    pub api_id_var: &'static str,
+    pub api_hash_var: &'static str,
+    pub phone_number_var: &'static str,
+    pub password_var: &'static str,
+}
+
 #[derive(Debug)]
 pub struct ConfigData {
     pub api_id: i32,
@@ -16,17 +26,7 @@ pub fn load_tg_client_config(
     phone_number: &str,
     password: &str,
 ) -> Result<ConfigData, TelegramError> {
-    let api_id_str = env::var(api_id).map_err(|e| TelegramError::EnvVar {
-        name: api_id.to_string(),
-        source: e,
-    })?;
-
-    let api_id = api_id_str
-        .parse::<i32>()
-        .map_err(|e| TelegramError::ParseInt {
-            name: api_id.to_string(),
-            source: e,
-        })?;
+    let api_id = load_api_id(api_id)?;
 
     let api_hash = env::var(api_hash).map_err(|e| TelegramError::EnvVar {
         name: api_hash.to_string(),
@@ -50,3 +50,17 @@ pub fn load_tg_client_config(
         password,
     })
 }
+
+pub fn load_api_id(api_id: &str) -> Result<i32, TelegramError> {
+    let api_id_str = env::var(api_id).map_err(|e| TelegramError::EnvVar {
+        name: api_id.to_string(),
+        source: e,
+    })?;
+
+    api_id_str
+        .parse::<i32>()
+        .map_err(|e| TelegramError::ParseInt {
+            name: api_id.to_string(),
+            source: e,
+        })
+}
@@ -0,0 +1,177 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use grammers_session::types::{PeerKind, PeerRef};
+use publisher::types::TgDeletion;
+
+/// A message in a source chat, keyed the way Telegram reports deletions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourceMessage {
+    /// `None` outside channels and supergroups, where message IDs are unique per account.
+    pub channel_id: Option<i64>,
+    pub message_id: i32,
+}
+
+impl SourceMessage {
+    pub fn of(message: &grammers_client::message::Message) -> Self {
+        let peer_id = message.peer_id();
+
+        Self {
+            channel_id: (peer_id.kind() == PeerKind::Channel).then(|| peer_id.bare_id()),
+            message_id: message.id(),
+        }
+    }
+
+    pub fn deleted(deletion: &TgDeletion) -> impl Iterator<Item = Self> + '_ {
+        deletion.message_ids.iter().map(|message_id| Self {
+            channel_id: deletion.channel_id,
+            message_id: *message_id,
+        })
+    }
+}
+
+/// A message we sent because of a source message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SentCopy {
+    pub peer: PeerRef,
+    pub message_id: i32,
+}
+
+#[derive(Default)]
+struct Entries {
+    copies: HashMap<SourceMessage, Vec<SentCopy>>,
+    /// Oldest first, for eviction.
+    order: VecDeque<SourceMessage>,
+}
+
+/// The copies sent for the most recent source messages.
+///
+/// Edits and deletions of older messages are not mirrored.
+pub struct CopyLog {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl CopyLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub fn record(&self, source: SourceMessage, copy: SentCopy) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.lock();
+
+        if let Some(copies) = entries.copies.get_mut(&source) {
+            copies.push(copy);
+            return;
+        }
+
+        if entries.order.len() >= self.capacity
+            && let Some(oldest) = entries.order.pop_front()
+        {
+            entries.copies.remove(&oldest);
+        }
+
+        entries.copies.insert(source, vec![copy]);
+        entries.order.push_back(source);
+    }
+
+    pub fn copies(&self, source: &SourceMessage) -> Vec<SentCopy> {
+        self.lock().copies.get(source).cloned().unwrap_or_default()
+    }
+
+    /// Removes and returns the copies, e.g. once the source message is gone.
+    pub fn forget(&self, source: &SourceMessage) -> Vec<SentCopy> {
+        let mut entries = self.lock();
+
+        let Some(copies) = entries.copies.remove(source) else {
+            return Vec::new();
+        };
+
+        entries.order.retain(|kept| kept != source);
+        copies
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grammers_session::types::{PeerAuth, PeerId};
+
+    fn source(message_id: i32) -> SourceMessage {
+        SourceMessage {
+            channel_id: Some(1),
+            message_id,
+        }
+    }
+
+    fn copy(message_id: i32) -> SentCopy {
+        SentCopy {
+            peer: PeerRef {
+                id: PeerId::channel_unchecked(9),
+                auth: PeerAuth::default(),
+            },
+            message_id,
+        }
+    }
+
+    #[test]
+    fn test_copies_accumulate_per_source() {
+        let log = CopyLog::new(10);
+
+        log.record(source(1), copy(100));
+        log.record(source(1), copy(101));
+        log.record(source(2), copy(102));
+
+        assert_eq!(log.copies(&source(1)), vec![copy(100), copy(101)]);
+        assert_eq!(log.forget(&source(1)), vec![copy(100), copy(101)]);
+        assert!(log.copies(&source(1)).is_empty());
+        assert_eq!(log.copies(&source(2)), vec![copy(102)]);
+    }
+
+    #[test]
+    fn test_oldest_sources_are_evicted() {
+        let log = CopyLog::new(2);
+
+        log.record(source(1), copy(100));
+        log.record(source(2), copy(101));
+        log.record(source(3), copy(102));
+
+        assert!(log.copies(&source(1)).is_empty());
+        assert_eq!(log.copies(&source(3)), vec![copy(102)]);
+    }
+
+    #[test]
+    fn test_deletions_outside_channels_have_no_chat() {
+        let deletion = TgDeletion {
+            channel_id: None,
+            message_ids: vec![5, 6],
+        };
+
+        assert_eq!(
+            SourceMessage::deleted(&deletion).collect::<Vec<_>>(),
+            vec![
+                SourceMessage {
+                    channel_id: None,
+                    message_id: 5
+                },
+                SourceMessage {
+                    channel_id: None,
+                    message_id: 6
+                },
+            ]
+        );
+    }
+}
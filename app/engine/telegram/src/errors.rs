@@ -7,6 +7,10 @@ pub enum TelegramError {
     SignIn(Box<grammers_client::SignInError>),
     StdIO(std::io::Error),
     Other(String),
+    /// The session has no logged-in account, or Telegram revoked it.
+    Unauthorized {
+        session_path: String,
+    },
     EnvVar {
         name: String,
         source: std::env::VarError,
@@ -25,6 +29,11 @@ impl Display for TelegramError {
             TelegramError::StdIO(msg) => write!(f, "std::io error: {}", msg),
             TelegramError::SignIn(msg) => write!(f, "Telegram::SignIn error: {}", msg),
             TelegramError::Other(msg) => write!(f, "Other error: {}", msg),
+            TelegramError::Unauthorized { session_path } => write!(
+                f,
+                "Session {} is not logged in. Run the `login` subcommand to sign in.",
+                session_path
+            ),
             TelegramError::EnvVar { name, source } => {
                 write!(f, "Environment variable '{}' error: {}", name, source)
             }
@@ -51,6 +60,7 @@ impl Error for TelegramError {
             TelegramError::SignIn(err) => Some(err),
             TelegramError::StdIO(err) => Some(err),
             TelegramError::Other(_) => None,
+            TelegramError::Unauthorized { .. } => None,
             TelegramError::EnvVar { source, .. } => Some(source),
             TelegramError::ParseInt { source, .. } => Some(source),
         }
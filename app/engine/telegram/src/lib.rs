@@ -5,12 +5,16 @@ use grammers_client::{
 
 use crate::errors::TelegramError;
 
+pub mod catch_up;
 pub mod client;
 pub mod config;
+pub mod copies;
 pub mod dialogs;
 pub mod errors;
+pub mod login;
 pub mod media;
 pub mod msg_reply;
+pub mod outbox;
 
 pub async fn get_me(client: &Client) -> Result<User, TelegramError> {
     Ok(client.get_me().await?)
@@ -0,0 +1,226 @@
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use grammers_client::client::PasswordToken;
+use grammers_client::peer::User;
+use grammers_client::{Client, InvocationError, SignInError};
+use grammers_session::Session;
+use grammers_session::storages::SqliteSession;
+use grammers_session::updates::UpdatesLike;
+use grammers_tl_types as tl;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::timeout;
+
+use crate::client::{Connection, open_session};
+use crate::config::{AccountSession, ConfigData, load_tg_client_config};
+use crate::errors::TelegramError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginMethod {
+    /// A code sent to the account's Telegram apps, typed in here.
+    Code,
+    /// A link to open as a QR code and scan from a logged-in phone.
+    Qr,
+}
+
+/// Signs the account in if its session is not logged in, creating the session file if needed.
+///
+/// Interactive; meant for the `login` subcommand, not for the service.
+pub async fn login(account: &AccountSession, method: LoginMethod) -> Result<User, TelegramError> {
+    let config = load_tg_client_config(
+        account.api_id_var,
+        account.api_hash_var,
+        account.phone_number_var,
+        account.password_var,
+    )?;
+
+    let Connection {
+        client,
+        mut updates_receiver,
+        session,
+    } = open_session(account.session_path, config.api_id).await?;
+
+    if client.is_authorized().await? {
+        let me = client.get_me().await?;
+        println!(
+            "{} is already logged in as {}.",
+            account.session_path,
+            me.full_name()
+        );
+        return Ok(me);
+    }
+
+    println!(
+        "Logging in {} ({}).",
+        account.session_path, config.phone_number
+    );
+
+    match method {
+        LoginMethod::Code => code_login(&client, &config).await?,
+        LoginMethod::Qr => qr_login(&client, &session, &mut updates_receiver, &config).await?,
+    }
+
+    let me = client.get_me().await?;
+    println!("Logged in as {}.", me.full_name());
+
+    Ok(me)
+}
+
+async fn code_login(client: &Client, config: &ConfigData) -> Result<(), TelegramError> {
+    let token = client
+        .request_login_code(&config.phone_number, config.api_hash.as_str())
+        .await?;
+
+    println!("Enter the OTP code: ");
+    let mut code = String::new();
+    io::stdin().read_line(&mut code)?;
+    let code = code.trim();
+
+    match client.sign_in(&token, code).await {
+        Ok(_) => Ok(()),
+        Err(SignInError::PasswordRequired(password_token)) => {
+            client
+                .check_password(password_token, &config.password)
+                .await?;
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Shows a login link until it is scanned, replacing it whenever it expires.
+async fn qr_login(
+    client: &Client,
+    session: &SqliteSession,
+    updates: &mut UnboundedReceiver<UpdatesLike>,
+    config: &ConfigData,
+) -> Result<(), TelegramError> {
+    let export = tl::functions::auth::ExportLoginToken {
+        api_id: config.api_id,
+        api_hash: config.api_hash.clone(),
+        except_ids: Vec::new(),
+    };
+
+    loop {
+        let login_token = match client.invoke(&export).await {
+            Ok(login_token) => login_token,
+            Err(error) if is_password_needed(&error) => {
+                return password_login(client, config).await;
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        match login_token {
+            tl::enums::auth::LoginToken::Token(token) => {
+                println!(
+                    "\nOpen this link as a QR code and scan it in Telegram under Settings > \
+                     Devices > Link Desktop Device, e.g. with `qrencode -t ansiutf8 '<link>'`:\n\n{}\n",
+                    login_url(&token.token)
+                );
+
+                wait_for_scan(updates, token.expires).await;
+            }
+
+            // The account lives on another DC; the scan is confirmed there.
+            tl::enums::auth::LoginToken::MigrateTo(migrate) => {
+                session.set_home_dc_id(migrate.dc_id).await;
+
+                let import = tl::functions::auth::ImportLoginToken {
+                    token: migrate.token,
+                };
+
+                return match client.invoke(&import).await {
+                    Ok(tl::enums::auth::LoginToken::Success(_)) => Ok(()),
+                    Ok(_) => Err(TelegramError::Other(
+                        "QR login was not confirmed after switching DC".into(),
+                    )),
+                    Err(error) if is_password_needed(&error) => {
+                        password_login(client, config).await
+                    }
+                    Err(error) => Err(error.into()),
+                };
+            }
+
+            tl::enums::auth::LoginToken::Success(_) => return Ok(()),
+        }
+    }
+}
+
+/// Returns once Telegram reports the scan, or once the link expires.
+async fn wait_for_scan(updates: &mut UnboundedReceiver<UpdatesLike>, expires: i32) {
+    loop {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+
+        let remaining = Duration::from_secs((expires as i64 - now).max(1) as u64);
+
+        match timeout(remaining, updates.recv()).await {
+            Ok(Some(update)) if is_login_token_update(&update) => return,
+            Ok(Some(_)) => continue,
+            // Expired, or the connection is gone; exporting again tells which.
+            Ok(None) | Err(_) => return,
+        }
+    }
+}
+
+fn is_login_token_update(update: &UpdatesLike) -> bool {
+    match update {
+        UpdatesLike::Updates(tl::enums::Updates::UpdateShort(short)) => {
+            matches!(short.update, tl::enums::Update::LoginToken)
+        }
+        UpdatesLike::Updates(tl::enums::Updates::Updates(updates)) => updates
+            .updates
+            .iter()
+            .any(|update| matches!(update, tl::enums::Update::LoginToken)),
+        _ => false,
+    }
+}
+
+fn is_password_needed(error: &InvocationError) -> bool {
+    matches!(error, InvocationError::Rpc(rpc) if rpc.name == "SESSION_PASSWORD_NEEDED")
+}
+
+async fn password_login(client: &Client, config: &ConfigData) -> Result<(), TelegramError> {
+    let tl::enums::account::Password::Password(password) = client
+        .invoke(&tl::functions::account::GetPassword {})
+        .await?;
+
+    client
+        .check_password(PasswordToken::new(password), &config.password)
+        .await?;
+
+    Ok(())
+}
+
+fn login_url(token: &[u8]) -> String {
+    format!("tg://login?token={}", URL_SAFE_NO_PAD.encode(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_url_is_url_safe_base64() {
+        assert_eq!(login_url(&[0xfb, 0xff, 0x01]), "tg://login?token=-_8B");
+    }
+
+    #[test]
+    fn test_only_login_token_updates_end_the_wait() {
+        let short = |update| {
+            UpdatesLike::Updates(tl::enums::Updates::UpdateShort(tl::types::UpdateShort {
+                update,
+                date: 0,
+            }))
+        };
+
+        assert!(is_login_token_update(&short(tl::enums::Update::LoginToken)));
+        assert!(!is_login_token_update(&short(tl::enums::Update::Config)));
+        assert!(!is_login_token_update(&UpdatesLike::Updates(
+            tl::enums::Updates::TooLong
+        )));
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use grammers_client::message::{InputMessage, Message};
+use grammers_client::{Client, InvocationError};
+
+use crate::copies::{CopyLog, SentCopy, SourceMessage};
+use grammers_session::types::PeerRef;
+use publisher::EventBus;
+use publisher::types::{ErrorEvent, PulsgramEvent};
+use serde::Deserialize;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{Instant, sleep_until};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OutboxConfig {
+    /// Sends per second across all chats.
+    pub messages_per_sec: u32,
+    /// Minimum time between two sends to the same chat.
+    pub per_chat_interval_ms: u64,
+    /// Attempts after the first one, for flood waits and connection errors.
+    pub max_retries: u32,
+    /// Longer flood waits fail the message instead of holding the chat's queue.
+    pub max_flood_wait_secs: u32,
+    /// Source messages whose copies are remembered, so edits and deletions can follow them.
+    pub remembered_sources: usize,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            messages_per_sec: 20,
+            per_chat_interval_ms: 1_000,
+            max_retries: 3,
+            max_flood_wait_secs: 10 * 60,
+            remembered_sources: 10_000,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum OutboxError {
+    /// The last attempt failed, or the error is not worth retrying.
+    Send {
+        attempts: u32,
+        error: InvocationError,
+    },
+    FloodWaitTooLong {
+        secs: u32,
+    },
+    /// The chat's queue stopped before the message was sent.
+    Closed,
+}
+
+impl Display for OutboxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutboxError::Send { attempts, error } => {
+                write!(f, "{} (after {} attempts)", error, attempts)
+            }
+            OutboxError::FloodWaitTooLong { secs } => {
+                write!(f, "Telegram asked to wait {}s before sending", secs)
+            }
+            OutboxError::Closed => write!(f, "Outbox closed before sending"),
+        }
+    }
+}
+
+impl Error for OutboxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            OutboxError::Send { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves once the message was sent, edited or deleted, or once its retries ran out.
+pub struct Delivery<T = Message> {
+    rx: oneshot::Receiver<Result<T, OutboxError>>,
+}
+
+impl<T: Send + 'static> Delivery<T> {
+    /// Publishes an error if the message cannot be delivered, without holding up the caller.
+    pub fn report_failure(self, bus: Arc<EventBus>, source: &'static str, context: String) {
+        tokio::spawn(async move {
+            if let Err(error) = self.await {
+                bus.publish(PulsgramEvent::Error(ErrorEvent {
+                    message_text: format!("{}\nError: {}", context, error),
+                    source,
+                }));
+            }
+        });
+    }
+}
+
+impl<T> Future for Delivery<T> {
+    type Output = Result<T, OutboxError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx)
+            .poll(cx)
+            .map(|result| result.unwrap_or(Err(OutboxError::Closed)))
+    }
+}
+
+enum Outgoing {
+    Send {
+        message: InputMessage,
+        /// Recorded in the copy log once sent.
+        source: Option<SourceMessage>,
+        reply: oneshot::Sender<Result<Message, OutboxError>>,
+    },
+    Edit {
+        message_id: i32,
+        message: InputMessage,
+        reply: oneshot::Sender<Result<(), OutboxError>>,
+    },
+    Delete {
+        message_ids: Vec<i32>,
+        reply: oneshot::Sender<Result<(), OutboxError>>,
+    },
+}
+
+struct Inner {
+    client: Arc<Client>,
+    config: OutboxConfig,
+    queues: Mutex<HashMap<i64, mpsc::UnboundedSender<Outgoing>>>,
+    /// Earliest time the next send may start, whatever the chat.
+    next_global: Mutex<Instant>,
+    copies: CopyLog,
+}
+
+/// Outgoing messages, sent one at a time per chat and paced globally.
+///
+/// Each chat has its own queue, so a flood wait in one chat does not hold up the others beyond
+/// the global pause Telegram asks for.
+#[derive(Clone)]
+pub struct Outbox {
+    inner: Arc<Inner>,
+}
+
+impl Outbox {
+    pub fn new(client: Arc<Client>, config: OutboxConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                client,
+                queues: Mutex::new(HashMap::new()),
+                next_global: Mutex::new(Instant::now()),
+                copies: CopyLog::new(config.remembered_sources),
+                config,
+            }),
+        }
+    }
+
+    /// Queues the message behind the ones already waiting for the same chat.
+    pub fn send<M: Into<InputMessage>>(&self, peer: PeerRef, message: M) -> Delivery {
+        let (reply, rx) = oneshot::channel();
+
+        self.enqueue(
+            peer,
+            Outgoing::Send {
+                message: message.into(),
+                source: None,
+                reply,
+            },
+        );
+
+        Delivery { rx }
+    }
+
+    /// Like `send`, remembering the sent message as a copy of `source`.
+    pub fn send_copy<M: Into<InputMessage>>(
+        &self,
+        source: SourceMessage,
+        peer: PeerRef,
+        message: M,
+    ) -> Delivery {
+        let (reply, rx) = oneshot::channel();
+
+        self.enqueue(
+            peer,
+            Outgoing::Send {
+                message: message.into(),
+                source: Some(source),
+                reply,
+            },
+        );
+
+        Delivery { rx }
+    }
+
+    /// Edits a message sent earlier; an edit that changes nothing counts as done.
+    pub fn edit<M: Into<InputMessage>>(
+        &self,
+        peer: PeerRef,
+        message_id: i32,
+        message: M,
+    ) -> Delivery<()> {
+        let (reply, rx) = oneshot::channel();
+
+        self.enqueue(
+            peer,
+            Outgoing::Edit {
+                message_id,
+                message: message.into(),
+                reply,
+            },
+        );
+
+        Delivery { rx }
+    }
+
+    pub fn delete(&self, peer: PeerRef, message_ids: Vec<i32>) -> Delivery<()> {
+        let (reply, rx) = oneshot::channel();
+
+        self.enqueue(peer, Outgoing::Delete { message_ids, reply });
+
+        Delivery { rx }
+    }
+
+    /// The copies sent so far for `source`, in the order they were sent.
+    pub fn copies(&self, source: &SourceMessage) -> Vec<SentCopy> {
+        self.inner.copies.copies(source)
+    }
+
+    /// Like `copies`, no longer remembering them afterwards.
+    pub fn forget_copies(&self, source: &SourceMessage) -> Vec<SentCopy> {
+        self.inner.copies.forget(source)
+    }
+
+    fn enqueue(&self, peer: PeerRef, outgoing: Outgoing) {
+        let mut queues = self
+            .inner
+            .queues
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let queue = queues.entry(peer.id.bare_id()).or_insert_with(|| {
+            let (tx, rx) = mpsc::unbounded_channel();
+            tokio::spawn(run_queue(Arc::clone(&self.inner), peer, rx));
+            tx
+        });
+
+        // The queue task never stops while a sender exists, so this cannot fail.
+        let _ = queue.send(outgoing);
+    }
+}
+
+async fn run_queue(inner: Arc<Inner>, peer: PeerRef, mut rx: mpsc::UnboundedReceiver<Outgoing>) {
+    let mut next_send = Instant::now();
+
+    while let Some(outgoing) = rx.recv().await {
+        match outgoing {
+            Outgoing::Send {
+                message,
+                source,
+                reply,
+            } => {
+                let result = inner
+                    .deliver(&mut next_send, || {
+                        inner.client.send_message(peer, message.clone())
+                    })
+                    .await;
+
+                if let (Some(source), Ok(sent)) = (source, &result) {
+                    inner.copies.record(
+                        source,
+                        SentCopy {
+                            peer,
+                            message_id: sent.id(),
+                        },
+                    );
+                }
+
+                let _ = reply.send(result);
+            }
+
+            Outgoing::Edit {
+                message_id,
+                message,
+                reply,
+            } => {
+                let result = inner
+                    .deliver(&mut next_send, || async {
+                        match inner
+                            .client
+                            .edit_message(peer, message_id, message.clone())
+                            .await
+                        {
+                            Err(InvocationError::Rpc(rpc))
+                                if rpc.name == "MESSAGE_NOT_MODIFIED" =>
+                            {
+                                Ok(())
+                            }
+                            result => result,
+                        }
+                    })
+                    .await;
+
+                let _ = reply.send(result);
+            }
+
+            Outgoing::Delete { message_ids, reply } => {
+                let result = inner
+                    .deliver(&mut next_send, || async {
+                        inner
+                            .client
+                            .delete_messages(peer, &message_ids)
+                            .await
+                            .map(|_| ())
+                    })
+                    .await;
+
+                let _ = reply.send(result);
+            }
+        }
+    }
+}
+
+impl Inner {
+    async fn deliver<T, F, Fut>(
+        &self,
+        next_send: &mut Instant,
+        mut attempt: F,
+    ) -> Result<T, OutboxError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, InvocationError>>,
+    {
+        let mut attempts = 0;
+
+        loop {
+            sleep_until(*next_send).await;
+            self.wait_global_slot().await;
+
+            attempts += 1;
+            *next_send = Instant::now() + Duration::from_millis(self.config.per_chat_interval_ms);
+
+            let error = match attempt().await {
+                Ok(sent) => return Ok(sent),
+                Err(error) => error,
+            };
+
+            let wait = match retry_after(&error, attempts) {
+                Retry::FloodWait(secs) if secs > self.config.max_flood_wait_secs => {
+                    return Err(OutboxError::FloodWaitTooLong { secs });
+                }
+                Retry::FloodWait(secs) => {
+                    let wait = Duration::from_secs(secs as u64);
+                    // Flood waits apply to the whole account, not only this chat.
+                    self.pause_all(wait);
+                    wait
+                }
+                Retry::After(wait) => wait,
+                Retry::Never => return Err(OutboxError::Send { attempts, error }),
+            };
+
+            if attempts > self.config.max_retries {
+                return Err(OutboxError::Send { attempts, error });
+            }
+
+            *next_send = (*next_send).max(Instant::now() + wait);
+        }
+    }
+
+    async fn wait_global_slot(&self) {
+        let interval = Duration::from_secs(1) / self.config.messages_per_sec.max(1);
+
+        let slot = {
+            let mut next_global = self
+                .next_global
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            let slot = (*next_global).max(Instant::now());
+            *next_global = slot + interval;
+            slot
+        };
+
+        sleep_until(slot).await;
+    }
+
+    fn pause_all(&self, wait: Duration) {
+        let mut next_global = self
+            .next_global
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        *next_global = (*next_global).max(Instant::now() + wait);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Retry {
+    /// Telegram asked to wait this many seconds.
+    FloodWait(u32),
+    After(Duration),
+    Never,
+}
+
+fn retry_after(error: &InvocationError, attempts: u32) -> Retry {
+    match error {
+        InvocationError::Rpc(rpc) => match (rpc.name.as_str(), rpc.value) {
+            ("FLOOD_WAIT" | "FLOOD_PREMIUM_WAIT" | "SLOWMODE_WAIT", Some(secs)) => {
+                Retry::FloodWait(secs)
+            }
+            // Telegram-side hiccups.
+            _ if rpc.code >= 500 => Retry::After(Duration::from_secs(attempts as u64)),
+            _ => Retry::Never,
+        },
+        InvocationError::Io(_) | InvocationError::Transport(_) | InvocationError::Dropped => {
+            Retry::After(Duration::from_secs(attempts as u64))
+        }
+        _ => Retry::Never,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grammers_client::sender::RpcError;
+
+    fn rpc(code: i32, name: &str, value: Option<u32>) -> InvocationError {
+        InvocationError::Rpc(RpcError {
+            code,
+            name: name.into(),
+            value,
+            caused_by: None,
+        })
+    }
+
+    #[test]
+    fn test_flood_waits_carry_their_duration() {
+        assert_eq!(
+            retry_after(&rpc(420, "FLOOD_WAIT", Some(31)), 1),
+            Retry::FloodWait(31)
+        );
+        assert_eq!(
+            retry_after(&rpc(420, "SLOWMODE_WAIT", Some(10)), 1),
+            Retry::FloodWait(10)
+        );
+    }
+
+    #[test]
+    fn test_server_errors_back_off() {
+        assert_eq!(
+            retry_after(&rpc(500, "INTERNAL", None), 2),
+            Retry::After(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_bad_requests_are_not_retried() {
+        assert_eq!(
+            retry_after(&rpc(400, "CHAT_WRITE_FORBIDDEN", None), 1),
+            Retry::Never
+        );
+        assert_eq!(
+            retry_after(&rpc(400, "MESSAGE_TOO_LONG", None), 1),
+            Retry::Never
+        );
+    }
+}
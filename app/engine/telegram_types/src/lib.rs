@@ -1,8 +1,9 @@
 pub use grammers_client::Client;
+pub use grammers_client::media::Media;
 pub use grammers_client::media::Photo;
 pub use grammers_client::media::Uploaded;
 pub use grammers_client::message::InputMessage;
+pub use grammers_client::message::Message;
 pub use grammers_client::peer::Peer;
-pub use grammers_client::update::Message;
 pub use grammers_session::types::PeerRef;
 pub use grammers_session::updates::UpdatesLike;
@@ -0,0 +1,200 @@
+use domain::types::symbol::Symbol;
+use regex::Regex;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntityKind {
+    /// `$GHOST`, stored upper-cased without the dollar sign.
+    Cashtag(String),
+    SolanaAddress(String),
+    EvmAddress(String),
+    Url(String),
+    /// A listed perp, named bare (`BTC`, `SOLUSDT`) or as a cashtag (`$sol`).
+    Symbol(Symbol),
+}
+
+/// Something worth alerting on, found in a post. `start..end` is the byte range in the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub kind: EntityKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+struct EntityRegexes {
+    cashtag: Regex,
+    evm_address: Regex,
+    solana_address: Regex,
+    url: Regex,
+    word: Regex,
+}
+
+static ENTITY_REGEXES: OnceLock<EntityRegexes> = OnceLock::new();
+fn regexes() -> &'static EntityRegexes {
+    ENTITY_REGEXES.get_or_init(|| EntityRegexes {
+        cashtag: Regex::new(r"\$([A-Za-z][A-Za-z0-9_]{0,19})\b").expect("Invalid cashtag pattern"),
+        evm_address: Regex::new(r"\b0x[0-9a-fA-F]{40}\b").expect("Invalid EVM address pattern"),
+        solana_address: Regex::new(r"\b[1-9A-HJ-NP-Za-km-z]{32,44}\b")
+            .expect("Invalid Solana address pattern"),
+        url: Regex::new(r"https?://[^\s<>()]+").expect("Invalid URL pattern"),
+        word: Regex::new(r"\b[A-Z]{2,10}\b").expect("Invalid word pattern"),
+    })
+}
+
+/// Every entity in `text`, ordered by position.
+///
+/// Entities may overlap: a listed cashtag is also a `Symbol`, and an address inside a URL is
+/// reported on its own too.
+pub fn extract_entities(text: &str) -> Vec<Entity> {
+    let regexes = regexes();
+    let mut entities = Vec::new();
+
+    for found in regexes.cashtag.captures_iter(text) {
+        let (Some(whole), Some(ticker)) = (found.get(0), found.get(1)) else {
+            continue;
+        };
+
+        entities.push(Entity {
+            kind: EntityKind::Cashtag(ticker.as_str().to_ascii_uppercase()),
+            start: whole.start(),
+            end: whole.end(),
+        });
+
+        if let Ok(symbol) = ticker.as_str().parse::<Symbol>() {
+            entities.push(Entity {
+                kind: EntityKind::Symbol(symbol),
+                start: whole.start(),
+                end: whole.end(),
+            });
+        }
+    }
+
+    for found in regexes.evm_address.find_iter(text) {
+        entities.push(Entity {
+            kind: EntityKind::EvmAddress(found.as_str().to_string()),
+            start: found.start(),
+            end: found.end(),
+        });
+    }
+
+    // Long words are valid base58 too; real addresses practically always contain a digit.
+    for found in regexes
+        .solana_address
+        .find_iter(text)
+        .filter(|found| found.as_str().bytes().any(|byte| byte.is_ascii_digit()))
+    {
+        entities.push(Entity {
+            kind: EntityKind::SolanaAddress(found.as_str().to_string()),
+            start: found.start(),
+            end: found.end(),
+        });
+    }
+
+    for found in regexes.url.find_iter(text) {
+        let url = found
+            .as_str()
+            .trim_end_matches(['.', ',', '!', '?', ';', ':', '"', '\'']);
+
+        entities.push(Entity {
+            kind: EntityKind::Url(url.to_string()),
+            start: found.start(),
+            end: found.start() + url.len(),
+        });
+    }
+
+    // Bare tickers must be upper-case, otherwise "sol" or "ada" in prose would count.
+    for found in regexes.word.find_iter(text) {
+        let preceded_by_dollar = text[..found.start()].ends_with('$');
+
+        if preceded_by_dollar {
+            continue;
+        }
+
+        if let Ok(symbol) = found.as_str().parse::<Symbol>() {
+            entities.push(Entity {
+                kind: EntityKind::Symbol(symbol),
+                start: found.start(),
+                end: found.end(),
+            });
+        }
+    }
+
+    entities.sort_by_key(|entity| (entity.start, entity.end));
+    entities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<EntityKind> {
+        extract_entities(text)
+            .into_iter()
+            .map(|entity| entity.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_cashtags_are_upper_cased_with_positions() {
+        let text = "$GHOST reclaimed 8M, next is $buttcoin";
+        let entities = extract_entities(text);
+
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[0].kind, EntityKind::Cashtag("GHOST".into()));
+        assert_eq!(&text[entities[0].start..entities[0].end], "$GHOST");
+        assert_eq!(entities[1].kind, EntityKind::Cashtag("BUTTCOIN".into()));
+        assert_eq!(&text[entities[1].start..entities[1].end], "$buttcoin");
+    }
+
+    #[test]
+    fn test_dollar_amounts_are_not_cashtags() {
+        assert!(kinds("raised $100 and $2.5M").is_empty());
+    }
+
+    #[test]
+    fn test_listed_symbols_bare_and_as_cashtags() {
+        assert_eq!(
+            kinds("BTC looks weak, $sol strong, ETHUSDT flat, ada meh"),
+            vec![
+                EntityKind::Symbol(Symbol::BTC),
+                EntityKind::Cashtag("SOL".into()),
+                EntityKind::Symbol(Symbol::SOL),
+                EntityKind::Symbol(Symbol::ETH),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_contract_addresses() {
+        let evm = "0x6982508145454Ce325dDbE47a25d4ec3d2311933";
+        let solana = "7GCihgDB8fe6KNjn2MYtkzZcRjQy3t9GHdC8uHYmW2hr";
+
+        assert_eq!(
+            kinds(&format!("ca: {solana} / eth: {evm}")),
+            vec![
+                EntityKind::SolanaAddress(solana.into()),
+                EntityKind::EvmAddress(evm.into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_words_are_not_solana_addresses() {
+        assert!(kinds("Supercalifragilisticexpialidociousness").is_empty());
+    }
+
+    #[test]
+    fn test_urls_drop_trailing_punctuation() {
+        let text = "chart here: https://dexscreener.com/solana/abc. wow";
+        let entities = extract_entities(text);
+
+        assert_eq!(
+            entities[0].kind,
+            EntityKind::Url("https://dexscreener.com/solana/abc".into())
+        );
+        assert_eq!(
+            &text[entities[0].start..entities[0].end],
+            "https://dexscreener.com/solana/abc"
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub mod entities;
 mod local_tests;
+pub mod profile;
 pub mod regex;
@@ -345,7 +345,7 @@ automating things
                 assert_eq!(follower_link, Some("https://x.com/blknoiz06".to_string()));
                 assert_eq!(followee, "rohunvora");
                 assert_eq!(followee_link, Some("https://x.com/rohunvora".to_string()));
-                assert!(profile_info.len() > 0);
+                assert!(!profile_info.is_empty());
             }
             _ => panic!("Expected Follow, got {:?}", result),
         }
@@ -436,7 +436,7 @@ macdonal im dont love it
                     followee_link,
                     Some("https://x.com/chiweethedog".to_string())
                 );
-                assert!(profile_info.len() > 0);
+                assert!(!profile_info.is_empty());
             }
             _ => panic!("Expected Follow, got {:?}", result),
         }
@@ -0,0 +1,51 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// The counts line of a relayed profile, e.g. `753 Following | 719 Followers`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileCounts {
+    pub following: u64,
+    pub followers: u64,
+}
+
+static COUNTS_REGEX: OnceLock<Regex> = OnceLock::new();
+fn counts_regex() -> &'static Regex {
+    COUNTS_REGEX.get_or_init(|| {
+        Regex::new(r"([\d,]+) Following \| ([\d,]+) Followers")
+            .expect("Invalid COUNTS_REGEX pattern")
+    })
+}
+
+pub fn profile_counts(profile_info: &str) -> Option<ProfileCounts> {
+    let caps = counts_regex().captures(profile_info)?;
+
+    let count = |index: usize| caps[index].replace(',', "").parse::<u64>().ok();
+
+    Some(ProfileCounts {
+        following: count(1)?,
+        followers: count(2)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_with_thousands_separators() {
+        let profile = " rohun (rohunvora)\n2,406 Following | 10,927 Followers\nautomating things";
+
+        assert_eq!(
+            profile_counts(profile),
+            Some(ProfileCounts {
+                following: 2_406,
+                followers: 10_927
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_counts() {
+        assert_eq!(profile_counts("📍 null"), None);
+    }
+}
@@ -1,7 +1,7 @@
 use regex::Regex;
 use std::sync::OnceLock;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MessageType {
     Tweet {
         user: String,
@@ -43,6 +43,21 @@ pub enum MessageType {
     Unknown,
 }
 
+impl MessageType {
+    /// The post itself, without the relay header. Empty for `Unknown`.
+    pub fn body(&self) -> &str {
+        match self {
+            MessageType::Tweet { text, .. }
+            | MessageType::Retweet { text, .. }
+            | MessageType::Reply { text, .. }
+            | MessageType::Quote { text, .. } => text,
+            MessageType::Follow { profile_info, .. } => profile_info,
+            MessageType::ProfileUpdate { update_info, .. } => update_info,
+            MessageType::Unknown => "",
+        }
+    }
+}
+
 static TWEET_REGEX: OnceLock<Regex> = OnceLock::new();
 pub fn tweet_regex() -> &'static Regex {
     TWEET_REGEX.get_or_init(|| {
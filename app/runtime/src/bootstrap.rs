@@ -1,43 +1,42 @@
 use std::sync::Arc;
 
 use crate::{
-    config::Config,
+    config::{Config, DISPATCHER_ACCOUNT, MAIN_ACCOUNT, required_env_string},
     error::AppError,
+    settings::Settings,
     types::{AppRuntime, TelegramRuntime, WorkersConfig},
     utils::{create_reqwest_client, get_build_version},
 };
 use api::start_api_server;
 use binance::filters::extract_supported_filters;
+use binance::{client::BinanceClient, network::Network};
+use domain::types::provider::Provider;
 use domain::types::symbol::Symbol;
+use forwarder::config::ForwarderConfig;
+use forwarder::router::Router;
+use mirror::Follower;
+use perp_signals::config::{PerpSignalsConfig, SignalSource};
+use perp_signals::parsers::ParserRegistry;
+use providers::client::PersistenceClient;
+use telegram::catch_up::{CatchUp, CatchUpConfig};
+use telegram::outbox::Outbox;
 use telegram::{
     client::{ConnectClientReturnType, connect_client, handle_updates},
     dialogs::{build_peers_map_from_dialogs, load_dialogs, normalize_dialogs_into_data},
 };
+use trade_executor::account::{Account, AccountConfig};
 
 pub async fn bootstrap() -> Result<AppRuntime, AppError> {
     let build_version = get_build_version();
     println!("Build Version: {}", build_version);
 
-    let config = Config::from_env(true)?;
+    let config = Config::from_env()?;
+    let mut settings = Settings::load()?;
 
-    let telegram = init_telegram(&config).await?;
+    let telegram = init_telegram(&config, &settings.forwarder, &settings.catch_up).await?;
 
     let reqwest_client = create_reqwest_client()?;
 
-    let mut binance_client = binance::client::BinanceClient::new(
-        reqwest_client.clone(),
-        binance::constants::TESTNET_FUTURES,
-        &config.binance_api_key,
-        &config.binance_api_secret,
-    );
-
-    // TODO: Move exchangeInfo fetch + filter extraction into BinanceClient::build()
-    // This is exchange-specific initialization logic and does not belong in bootstrap.
-    let exchange_info = binance_client
-        .get_exchange_info()
-        .await
-        .map_err(AppError::from)?;
-
     // TODO: Move SUPPORTED symbols into domain layer (domain::symbols::SUPPORTED_SYMBOLS)
     // Bootstrap should not define trading universe.
     let supported_symbols = vec![
@@ -51,8 +50,90 @@ pub async fn bootstrap() -> Result<AppRuntime, AppError> {
         Symbol::ASTER,
     ];
 
-    let filters = extract_supported_filters(&exchange_info, &supported_symbols)?;
-    binance_client.set_symbol_filters(filters);
+    let account_configs: Vec<AccountConfig> = if settings.accounts.is_empty() {
+        vec![AccountConfig::testnet()]
+    } else {
+        // Disabled accounts are not connected, so their keys need not be set.
+        settings
+            .accounts
+            .iter()
+            .filter(|account| account.enabled)
+            .cloned()
+            .collect()
+    };
+
+    if account_configs.is_empty() {
+        return Err(AppError::Other(
+            "Every [[accounts]] entry is disabled, enable at least one".to_string(),
+        ));
+    }
+
+    let mut accounts = Vec::with_capacity(account_configs.len());
+
+    for config in account_configs {
+        let client = connect_binance(
+            &reqwest_client,
+            config.network,
+            &config.api_key_env,
+            &config.api_secret_env,
+            &supported_symbols,
+        )
+        .await?;
+
+        accounts.push(Account {
+            config,
+            client: Arc::new(client),
+        });
+    }
+
+    let mut followers = Vec::with_capacity(settings.mirror.followers.len());
+
+    for config in settings.mirror.followers.clone() {
+        let client = connect_binance(
+            &reqwest_client,
+            config.network,
+            &config.api_key_env,
+            &config.api_secret_env,
+            &supported_symbols,
+        )
+        .await?;
+
+        followers.push(Follower {
+            config,
+            client: Arc::new(client),
+        });
+    }
+
+    let primary = &accounts[0];
+    let binance_client = Arc::clone(&primary.client);
+    let binance_stream_url = primary.config.network.stream_url();
+
+    println!(
+        "Binance accounts: {} (primary: {})",
+        accounts
+            .iter()
+            .map(|account| account.name())
+            .collect::<Vec<_>>()
+            .join(", "),
+        primary.name()
+    );
+
+    let persistence = settings
+        .providers
+        .url
+        .as_deref()
+        .map(|url| PersistenceClient::new(reqwest_client.clone(), url));
+
+    if let Some(persistence) = &persistence {
+        match persistence.providers(settings.providers.stats_days).await {
+            Ok(providers) => add_provider_sources(&mut settings.perp_signals, &providers),
+            Err(e) => eprintln!("Providers unavailable, reading [perp_signals] sources only: {e}"),
+        }
+    }
+
+    let signal_parsers = ParserRegistry::from_config(&settings.perp_signals)
+        .map_err(|e| AppError::Other(format!("Invalid signal parsers: {e}")))?
+        .or_default_source(config.lcs_user_id);
 
     let bus = Arc::new(publisher::new_event_bus());
 
@@ -62,6 +143,9 @@ pub async fn bootstrap() -> Result<AppRuntime, AppError> {
         client_dispatcher: telegram.dispatcher.clone(),
         reqwest_client: reqwest_client.clone(),
         bus: bus.clone(),
+        signal_parsers: Arc::new(signal_parsers),
+        persistence,
+        api_token: config.api_token.clone(),
     };
 
     let shared_state = Arc::new(state);
@@ -72,9 +156,10 @@ pub async fn bootstrap() -> Result<AppRuntime, AppError> {
         state: shared_state,
         bus,
         client: telegram.client,
-        client_dispatcher: telegram.dispatcher,
+        outbox: Outbox::new(telegram.dispatcher, settings.outbox.clone()),
         dispatcher_id: telegram.dispatcher_id,
         updates_receiver: telegram.updates_receiver,
+        catch_up: telegram.catch_up,
         workers: WorkersConfig {
             errors_peer: telegram.workers.errors_peer,
 
@@ -89,38 +174,77 @@ pub async fn bootstrap() -> Result<AppRuntime, AppError> {
 
             perp_kols_usernames: telegram.workers.perp_kols_usernames,
 
+            kol_convergence: telegram.workers.kol_convergence,
+
+            forwarder: telegram.workers.forwarder,
+
             rs_user_id: config.rs_user_id,
-            lcs_user_id: config.lcs_user_id,
         },
-        binance_client: Arc::new(binance_client),
+        binance_client,
+        binance_stream_url,
+        accounts,
+        followers,
         listen_key,
+        settings,
     })
 }
 
-pub async fn init_telegram(config: &Config) -> Result<TelegramRuntime, AppError> {
+/// Registered providers are read like configured sources. The config wins for a chat in both.
+fn add_provider_sources(config: &mut PerpSignalsConfig, providers: &[Provider]) {
+    for provider in providers.iter().filter(|provider| provider.enabled) {
+        if config
+            .sources
+            .iter()
+            .any(|source| source.chat_id == provider.chat_id)
+        {
+            continue;
+        }
+
+        config.sources.push(SignalSource {
+            chat_id: provider.chat_id,
+            parser: provider.parser.clone(),
+        });
+    }
+}
+
+async fn connect_binance(
+    reqwest_client: &reqwest::Client,
+    network: Network,
+    api_key_env: &str,
+    api_secret_env: &str,
+    supported_symbols: &Vec<Symbol>,
+) -> Result<BinanceClient, AppError> {
+    let mut client = BinanceClient::new(
+        reqwest_client.clone(),
+        network.base_url(),
+        &required_env_string(api_key_env)?,
+        &required_env_string(api_secret_env)?,
+    );
+
+    // TODO: Move exchangeInfo fetch + filter extraction into BinanceClient::build()
+    // This is exchange-specific initialization logic and does not belong in bootstrap.
+    let exchange_info = client.get_exchange_info().await.map_err(AppError::from)?;
+
+    let filters = extract_supported_filters(&exchange_info, supported_symbols)?;
+    client.set_symbol_filters(filters);
+
+    Ok(client)
+}
+
+pub async fn init_telegram(
+    config: &Config,
+    forwarder: &ForwarderConfig,
+    catch_up: &CatchUpConfig,
+) -> Result<TelegramRuntime, AppError> {
     let ConnectClientReturnType {
         client,
         updates_receiver,
-    } = connect_client(
-        "pulsgram.session",
-        "API_ID",
-        "API_HASH",
-        "PHONE_NUMBER",
-        "PASSWORD",
-    )
-    .await?;
+    } = connect_client(&MAIN_ACCOUNT).await?;
 
     let ConnectClientReturnType {
         client: client_dispatcher,
         updates_receiver: _,
-    } = connect_client(
-        "dispatcher.pulsgram.session",
-        "API_ID",
-        "API_HASH",
-        "PHONE_NUMBER_DISPATCHER",
-        "PASSWORD_DISPATCHER",
-    )
-    .await?;
+    } = connect_client(&DISPATCHER_ACCOUNT).await?;
 
     let dispatcher_me = telegram::get_me(&client_dispatcher).await?;
     let dispatcher_id = dispatcher_me.id().bare_id();
@@ -128,6 +252,8 @@ pub async fn init_telegram(config: &Config) -> Result<TelegramRuntime, AppError>
     let dialogs = load_dialogs(&client).await?;
     let dialogs_data = normalize_dialogs_into_data(&dialogs);
 
+    let catch_up = CatchUp::new(catch_up, &build_peers_map_from_dialogs(&dialogs).await)?;
+
     if !cfg!(feature = "production") {
         dbg!(&dialogs_data);
     }
@@ -141,6 +267,10 @@ pub async fn init_telegram(config: &Config) -> Result<TelegramRuntime, AppError>
 
     let mut peers_map_dispatcher = build_peers_map_from_dialogs(&dialogs_from_dispatcher).await;
 
+    // Resolved before the workers take their peers out of the map, so rules may share them.
+    let forwarder = Router::new(forwarder, &peers_map_dispatcher)
+        .map_err(|e| AppError::Other(format!("Invalid forwarder rules: {e}")))?;
+
     let workers = WorkersConfig {
         errors_peer: peers_map_dispatcher
             .remove(&config.errors_peer_id)
@@ -172,14 +302,25 @@ pub async fn init_telegram(config: &Config) -> Result<TelegramRuntime, AppError>
 
         perp_kols_usernames: config.perp_kols_usernames.clone(),
 
+        kol_convergence: config
+            .kol_convergence_chat_id
+            .map(|chat_id| {
+                peers_map_dispatcher
+                    .remove(&chat_id)
+                    .ok_or(AppError::NotFound("kol_convergence_chat_id"))
+            })
+            .transpose()?,
+
+        forwarder,
+
         rs_user_id: config.rs_user_id,
-        lcs_user_id: config.lcs_user_id,
     };
 
     Ok(TelegramRuntime {
         client: Arc::new(client),
         dispatcher: Arc::new(client_dispatcher),
         updates_receiver,
+        catch_up,
         dispatcher_id,
         workers,
         dialogs_data,
@@ -187,17 +328,11 @@ pub async fn init_telegram(config: &Config) -> Result<TelegramRuntime, AppError>
 }
 
 pub async fn run(runtime: AppRuntime) -> Result<(), AppError> {
-    tokio::spawn(handle_updates(
-        Arc::clone(&runtime.client),
-        runtime.updates_receiver,
-        Arc::clone(&runtime.bus),
-        runtime.dispatcher_id,
-    ));
-
     // tokio::spawn(perp_signals::run(
     //     Arc::clone(&runtime.bus),
-    //     Arc::clone(&runtime.client_dispatcher),
-    //     runtime.workers.lcs_user_id,
+    //     runtime.outbox.clone(),
+    //     Arc::clone(&runtime.state.signal_parsers),
+    //     runtime.settings.perp_signals.validation.clone(),
     //     if cfg!(feature = "production") {
     //         runtime.workers.perp_signals_prod
     //     } else {
@@ -205,26 +340,66 @@ pub async fn run(runtime: AppRuntime) -> Result<(), AppError> {
     //     },
     // ));
 
+    tokio::spawn(risk_manager::run(
+        Arc::clone(&runtime.bus),
+        runtime
+            .accounts
+            .iter()
+            .map(|account| (account.name().to_string(), Arc::clone(&account.client)))
+            .collect(),
+        runtime.settings.risk_manager,
+    ));
+
+    if let Some(persistence) = &runtime.state.persistence {
+        tokio::spawn(providers::run(
+            Arc::clone(&runtime.bus),
+            persistence.clone(),
+            runtime.settings.providers.clone(),
+        ));
+    }
+
     tokio::spawn(errors_reporter::run(
-        Arc::clone(&runtime.client_dispatcher),
+        runtime.outbox.clone(),
         runtime.workers.errors_peer,
         Arc::clone(&runtime.bus),
     ));
 
-    tokio::spawn(kol_follows::run(
+    tokio::spawn(kol_feed::run(
         Arc::clone(&runtime.bus),
         runtime.workers.rs_user_id,
+    ));
+
+    if !runtime.workers.forwarder.is_empty() {
+        tokio::spawn(forwarder::run(
+            runtime.outbox.clone(),
+            runtime.workers.forwarder,
+            Arc::clone(&runtime.bus),
+        ));
+    }
+
+    if let Some(destination) = runtime.workers.kol_convergence {
+        tokio::spawn(kol_convergence::run(
+            Arc::clone(&runtime.bus),
+            runtime.outbox.clone(),
+            destination,
+            runtime.settings.kol_convergence.clone(),
+        ));
+    }
+
+    tokio::spawn(kol_follows::run(
+        Arc::clone(&runtime.bus),
         if cfg!(feature = "production") {
             runtime.workers.kol_follows_prod
         } else {
             runtime.workers.kol_follows_test
         },
-        Arc::clone(&runtime.client_dispatcher),
+        runtime.outbox.clone(),
+        runtime.state.persistence.clone(),
     ));
 
     tokio::spawn(perp_kols::run(
         Arc::clone(&runtime.bus),
-        Arc::clone(&runtime.client_dispatcher),
+        runtime.outbox.clone(),
         runtime.workers.rs_user_id,
         if cfg!(feature = "production") {
             runtime.workers.perp_kols_prod
@@ -235,7 +410,36 @@ pub async fn run(runtime: AppRuntime) -> Result<(), AppError> {
     ));
 
     #[cfg(not(feature = "production"))]
-    tokio::spawn(market_data::run(Symbol::default_trading_set()));
+    tokio::spawn(market_data::run(
+        Arc::clone(&runtime.bus),
+        Symbol::default_trading_set(),
+    ));
+
+    // Driven by market_data price ticks, so it follows the same feature gate.
+    #[cfg(not(feature = "production"))]
+    tokio::spawn(trade_manager::run(
+        runtime.bus.clone(),
+        runtime.binance_client.clone(),
+        runtime.settings.trade_manager,
+    ));
+
+    // Follows the trade manager's updates, so it shares its feature gate.
+    #[cfg(not(feature = "production"))]
+    if !runtime.followers.is_empty() {
+        tokio::spawn(mirror::run(
+            runtime.bus.clone(),
+            runtime.followers,
+            runtime.settings.mirror,
+        ));
+    }
+
+    // Order fills and commissions for the trade lifecycle, on the primary account.
+    #[cfg(not(feature = "production"))]
+    tokio::spawn(market_data::user_stream::run(
+        Arc::clone(&runtime.bus),
+        runtime.binance_stream_url,
+        runtime.listen_key.clone(),
+    ));
 
     #[cfg(not(feature = "production"))]
     tokio::spawn(listen_key_keepalive::run(
@@ -245,9 +449,19 @@ pub async fn run(runtime: AppRuntime) -> Result<(), AppError> {
 
     // tokio::spawn(trade_executor::run(
     //     runtime.bus.clone(),
-    //     runtime.binance_client.clone(),
+    //     runtime.accounts,
+    //     runtime.settings.trade_executor,
     // ));
 
+    // Last, so the workers are listening before missed messages are replayed.
+    tokio::spawn(handle_updates(
+        Arc::clone(&runtime.client),
+        runtime.updates_receiver,
+        Arc::clone(&runtime.bus),
+        runtime.dispatcher_id,
+        runtime.catch_up,
+    ));
+
     let address = if cfg!(feature = "production") {
         "0.0.0.0"
     } else {
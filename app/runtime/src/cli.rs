@@ -0,0 +1,71 @@
+use dotenv::dotenv;
+use telegram::config::AccountSession;
+use telegram::login::{LoginMethod, login};
+
+use crate::config::{DISPATCHER_ACCOUNT, MAIN_ACCOUNT};
+use crate::error::AppError;
+
+const USAGE: &str = "\
+Usage:
+  pulsgram                  Run the engine. Both Telegram sessions must be logged in.
+  pulsgram login [options]  Log in the sessions that are not, then exit.
+
+Login options:
+  --qr           Scan a QR code from a logged-in phone instead of typing a code.
+  --main         Only the main account (pulsgram.session).
+  --dispatcher   Only the dispatcher account (dispatcher.pulsgram.session).";
+
+pub enum Command {
+    Run,
+    Login {
+        method: LoginMethod,
+        accounts: Vec<AccountSession>,
+    },
+}
+
+impl Command {
+    /// Reads the arguments after the program name.
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, AppError> {
+        let Some(command) = args.next() else {
+            return Ok(Command::Run);
+        };
+
+        if command != "login" {
+            return Err(AppError::Other(format!(
+                "Unknown command `{command}`\n\n{USAGE}"
+            )));
+        }
+
+        let mut method = LoginMethod::Code;
+        let mut accounts = Vec::new();
+
+        for arg in args {
+            match arg.as_str() {
+                "--qr" => method = LoginMethod::Qr,
+                "--main" => accounts.push(MAIN_ACCOUNT),
+                "--dispatcher" => accounts.push(DISPATCHER_ACCOUNT),
+                _ => {
+                    return Err(AppError::Other(format!(
+                        "Unknown login option `{arg}`\n\n{USAGE}"
+                    )));
+                }
+            }
+        }
+
+        if accounts.is_empty() {
+            accounts = vec![MAIN_ACCOUNT, DISPATCHER_ACCOUNT];
+        }
+
+        Ok(Command::Login { method, accounts })
+    }
+}
+
+pub async fn run_login(method: LoginMethod, accounts: &[AccountSession]) -> Result<(), AppError> {
+    dotenv().ok();
+
+    for account in accounts {
+        login(account, method).await?;
+    }
+
+    Ok(())
+}
@@ -1,8 +1,27 @@
 use dotenv::dotenv;
 use std::env;
+use telegram::config::AccountSession;
 
 use crate::error::AppError;
 
+/// The account reading source chats.
+pub const MAIN_ACCOUNT: AccountSession = AccountSession {
+    session_path: "pulsgram.session",
+    api_id_var: "API_ID",
+    api_hash_var: "API_HASH",
+    phone_number_var: "PHONE_NUMBER",
+    password_var: "PASSWORD",
+};
+
+/// The account every outgoing message is sent from.
+pub const DISPATCHER_ACCOUNT: AccountSession = AccountSession {
+    session_path: "dispatcher.pulsgram.session",
+    api_id_var: "API_ID",
+    api_hash_var: "API_HASH",
+    phone_number_var: "PHONE_NUMBER_DISPATCHER",
+    password_var: "PASSWORD_DISPATCHER",
+};
+
 #[derive(Debug)]
 pub struct Config {
     pub kol_follows_chat_id: i64,
@@ -13,13 +32,15 @@ pub struct Config {
     pub perp_kols_chat_id: i64,
     pub perp_kols_test_chat_id: i64,
     pub perp_kols_usernames: Vec<String>,
+    /// Where multi-KOL convergence alerts go; the detector is off without it.
+    pub kol_convergence_chat_id: Option<i64>,
     pub lcs_user_id: i64,
     pub rs_user_id: i64,
-    pub binance_api_key: String,
-    pub binance_api_secret: String,
+    /// Required by the API routes that trade or change risk limits.
+    pub api_token: Option<String>,
 }
 
-fn required_env_string(key: &str) -> Result<String, AppError> {
+pub(crate) fn required_env_string(key: &str) -> Result<String, AppError> {
     Ok(env::var(key)?)
 }
 
@@ -29,16 +50,28 @@ fn required_env_i64(key: &str) -> Result<i64, AppError> {
         .map_err(|e| AppError::Other(format!("Invalid value for {key}: {e}")))
 }
 
+fn optional_env_string(key: &str) -> Result<Option<String>, AppError> {
+    match env::var(key) {
+        Ok(val) if val.is_empty() => Ok(None),
+        Ok(val) => Ok(Some(val)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn optional_env_i64(key: &str) -> Result<Option<i64>, AppError> {
+    match env::var(key) {
+        Ok(_) => required_env_i64(key).map(Some),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
 impl Config {
-    pub fn from_env(use_binance_testnet: bool) -> Result<Self, AppError> {
+    /// Binance credentials are read per account, see `AccountConfig`.
+    pub fn from_env() -> Result<Self, AppError> {
         dotenv().ok();
 
-        let (api_key_var, api_secret_var) = if use_binance_testnet {
-            ("BINANCE_API_KEY_TEST", "BINANCE_API_SECRET_TEST")
-        } else {
-            ("BINANCE_API_KEY", "BINANCE_API_SECRET")
-        };
-
         Ok(Self {
             kol_follows_chat_id: required_env_i64("KOL_FOLLOWS_CHAT_ID")?,
             errors_peer_id: required_env_i64("ERRORS_PEER_ID")?,
@@ -51,10 +84,10 @@ impl Config {
                 .split(',')
                 .map(|s| s.to_string())
                 .collect(),
+            kol_convergence_chat_id: optional_env_i64("KOL_CONVERGENCE_CHAT_ID")?,
             lcs_user_id: required_env_i64("LCS_USER_ID")?,
             rs_user_id: required_env_i64("RS_USER_ID")?,
-            binance_api_key: required_env_string(api_key_var)?,
-            binance_api_secret: required_env_string(api_secret_var)?,
+            api_token: optional_env_string("API_TOKEN")?,
         })
     }
 }
@@ -1,13 +1,34 @@
+use std::process::ExitCode;
+
+use crate::cli::Command;
 use crate::error::AppError;
 
 mod bootstrap;
+mod cli;
 mod config;
 mod error;
+mod settings;
 mod types;
 mod utils;
 
 #[tokio::main]
-async fn main() -> Result<(), AppError> {
-    let runtime = bootstrap::bootstrap().await?;
-    bootstrap::run(runtime).await
+async fn main() -> ExitCode {
+    // Printed with `Display`, so usage and login hints read as written.
+    match start().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn start() -> Result<(), AppError> {
+    match Command::parse(std::env::args().skip(1))? {
+        Command::Run => {
+            let runtime = bootstrap::bootstrap().await?;
+            bootstrap::run(runtime).await
+        }
+        Command::Login { method, accounts } => cli::run_login(method, &accounts).await,
+    }
 }
@@ -0,0 +1,60 @@
+use std::{env, fs, io::ErrorKind};
+
+use forwarder::config::ForwarderConfig;
+use kol_convergence::config::KolConvergenceConfig;
+use mirror::config::MirrorConfig;
+use perp_signals::config::PerpSignalsConfig;
+use providers::config::ProvidersConfig;
+use risk_manager::config::RiskManagerConfig;
+use serde::Deserialize;
+use telegram::catch_up::CatchUpConfig;
+use telegram::outbox::OutboxConfig;
+use trade_executor::account::AccountConfig;
+use trade_executor::config::TradeExecutorConfig;
+use trade_manager::config::TradeManagerConfig;
+
+use crate::error::AppError;
+
+const DEFAULT_SETTINGS_PATH: &str = "pulsgram.toml";
+
+/// Structured, non-secret configuration loaded from a TOML file.
+/// Secrets and chat IDs stay in the environment (see `Config`).
+///
+/// The path is taken from `PULSGRAM_SETTINGS` and defaults to `pulsgram.toml`.
+/// A missing file is not an error; every section falls back to its defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub trade_manager: TradeManagerConfig,
+    #[allow(dead_code)] // Read by trade_executor, which is currently not spawned.
+    pub trade_executor: TradeExecutorConfig,
+    pub risk_manager: RiskManagerConfig,
+    /// Binance accounts trades are executed on; the first one is the primary account.
+    /// Falls back to `AccountConfig::testnet` when empty.
+    pub accounts: Vec<AccountConfig>,
+    pub mirror: MirrorConfig,
+    pub perp_signals: PerpSignalsConfig,
+    pub providers: ProvidersConfig,
+    pub kol_convergence: KolConvergenceConfig,
+    pub forwarder: ForwarderConfig,
+    pub outbox: OutboxConfig,
+    pub catch_up: CatchUpConfig,
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, AppError> {
+        let path = env::var("PULSGRAM_SETTINGS").unwrap_or_else(|_| DEFAULT_SETTINGS_PATH.into());
+
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                println!("Settings file {} not found, using defaults.", path);
+                return Ok(Self::default());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        toml::from_str(&raw)
+            .map_err(|e| AppError::Other(format!("Invalid settings in {path}: {e}")))
+    }
+}
@@ -2,15 +2,24 @@ use std::sync::Arc;
 
 use app_state::AppState;
 use binance::client::BinanceClient;
+use forwarder::router::Router;
+use mirror::Follower;
 use publisher::EventBus;
+use telegram::catch_up::CatchUp;
 use telegram::dialogs::DialogData;
+use telegram::outbox::Outbox;
 use telegram_types::{Client, PeerRef, UpdatesLike};
 use tokio::sync::mpsc::UnboundedReceiver;
+use trade_executor::account::Account;
+
+use crate::settings::Settings;
 
 pub struct TelegramRuntime {
     pub client: Arc<Client>,
     pub dispatcher: Arc<Client>,
     pub updates_receiver: UnboundedReceiver<UpdatesLike>,
+    /// Sources whose missed messages are replayed before live updates.
+    pub catch_up: CatchUp,
     pub dispatcher_id: i64,
     pub workers: WorkersConfig,
     pub dialogs_data: dashmap::DashMap<i64, DialogData>,
@@ -30,18 +39,30 @@ pub struct WorkersConfig {
 
     pub perp_kols_usernames: Vec<String>,
 
+    pub kol_convergence: Option<PeerRef>,
+
+    pub forwarder: Router,
+
     pub rs_user_id: i64,
-    pub lcs_user_id: i64,
 }
 
 pub struct AppRuntime {
     pub state: Arc<AppState>,
     pub bus: Arc<EventBus>,
     pub client: Arc<Client>,
-    pub client_dispatcher: Arc<Client>,
+    /// Sends through the dispatcher account.
+    pub outbox: Outbox,
     pub updates_receiver: UnboundedReceiver<UpdatesLike>,
+    pub catch_up: CatchUp,
     pub dispatcher_id: i64,
     pub workers: WorkersConfig,
+    /// Client of the primary account, used for position management and the user stream.
     pub binance_client: Arc<BinanceClient>,
+    /// User data stream of the primary account.
+    pub binance_stream_url: &'static str,
+    #[allow(dead_code)] // Read by trade_executor, which is currently not spawned.
+    pub accounts: Vec<Account>,
+    pub followers: Vec<Follower>,
     pub listen_key: String,
+    pub settings: Settings,
 }
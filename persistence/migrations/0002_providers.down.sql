@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS provider_daily_stats;
+DROP TABLE IF EXISTS providers;
\ No newline at end of file
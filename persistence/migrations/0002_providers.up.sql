@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS providers (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name             TEXT NOT NULL UNIQUE,
+    chat_id          BIGINT NOT NULL UNIQUE,
+    parser           TEXT NOT NULL,
+    enabled          BOOLEAN NOT NULL DEFAULT TRUE,
+    weight           DOUBLE PRECISION NOT NULL DEFAULT 1.0 CHECK (weight >= 0),
+    risk_multiplier  DOUBLE PRECISION NOT NULL DEFAULT 1.0 CHECK (risk_multiplier >= 0)
+);
+
+-- One row per provider and UTC day; rolling stats sum the last N rows.
+CREATE TABLE IF NOT EXISTS provider_daily_stats (
+    provider_id      UUID NOT NULL REFERENCES providers (id) ON DELETE CASCADE,
+    day              DATE NOT NULL,
+    signals          INTEGER NOT NULL DEFAULT 0,
+    parse_failures   INTEGER NOT NULL DEFAULT 0,
+    wins             INTEGER NOT NULL DEFAULT 0,
+    losses           INTEGER NOT NULL DEFAULT 0,
+    pnl              DOUBLE PRECISION NOT NULL DEFAULT 0,
+    PRIMARY KEY (provider_id, day)
+);
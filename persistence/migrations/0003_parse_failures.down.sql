@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_parse_failures_open;
+DROP TABLE IF EXISTS parse_failures;
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Messages from signal sources that looked like a signal but could not be parsed.
+CREATE TABLE IF NOT EXISTS parse_failures (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    chat_id        BIGINT NOT NULL,
+    message_id     INTEGER NOT NULL,
+    text           TEXT NOT NULL,
+    reason         TEXT NOT NULL,
+    posted_at_ms   BIGINT NOT NULL,
+    resolved       BOOLEAN NOT NULL DEFAULT FALSE,
+    UNIQUE (chat_id, message_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_parse_failures_open ON parse_failures (posted_at_ms) WHERE NOT resolved;
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_kol_follows_followed_at;
+DROP INDEX IF EXISTS idx_kol_follows_followee;
+DROP TABLE IF EXISTS kol_follows;
\ No newline at end of file
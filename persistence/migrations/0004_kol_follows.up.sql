@@ -0,0 +1,11 @@
+-- Follow graph of the tracked KOLs: one edge per KOL and followed account, handles lower-cased.
+CREATE TABLE IF NOT EXISTS kol_follows (
+    follower         TEXT NOT NULL,
+    followee         TEXT NOT NULL,
+    followed_at_ms   BIGINT NOT NULL,
+    profile_info     TEXT NOT NULL,
+    PRIMARY KEY (follower, followee)
+);
+
+CREATE INDEX IF NOT EXISTS idx_kol_follows_followee ON kol_follows (followee);
+CREATE INDEX IF NOT EXISTS idx_kol_follows_followed_at ON kol_follows (followed_at_ms);
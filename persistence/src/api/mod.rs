@@ -1,6 +1,7 @@
 mod routes;
 
-use axum::Router;
+use axum::{Extension, Router};
+use std::sync::Arc;
 use tokio::net::TcpListener;
 
 use crate::{
@@ -9,6 +10,7 @@ use crate::{
         middlewares::{request_id_middleware, request_logger, timeout_middleware},
         routes,
     },
+    repositories::{Repositories, error::PersistenceError},
     utils::shutdown_signal,
 };
 use axum::{
@@ -18,12 +20,17 @@ use axum::{
 use sqlx::Error as SqlxError;
 use std::error::Error;
 
-pub async fn start_api_server(address: &str, port: i32) -> Result<(), Box<dyn Error>> {
+pub async fn start_api_server(
+    address: &str,
+    port: i32,
+    repos: Arc<Repositories>,
+) -> Result<(), Box<dyn Error>> {
     let listener = TcpListener::bind(format!("{}:{}", address, port)).await?;
 
     //TODO: Test timeout midware & other ones
     let router = Router::new()
         .nest("/persistance", routes())
+        .layer(Extension(repos))
         .layer(build_cors_layer())
         .layer(axum::middleware::from_fn(timeout_middleware))
         .layer(axum::middleware::from_fn(request_logger))
@@ -56,3 +63,17 @@ pub fn map_db_error(err: SqlxError) -> Response {
         _ => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
     }
 }
+
+impl IntoResponse for PersistenceError {
+    fn into_response(self) -> Response {
+        match self {
+            PersistenceError::NotFound => {
+                (StatusCode::NOT_FOUND, "Resource not found").into_response()
+            }
+            PersistenceError::Conflict => (StatusCode::CONFLICT, "Duplicate entry").into_response(),
+            PersistenceError::DatabaseError => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+            }
+        }
+    }
+}
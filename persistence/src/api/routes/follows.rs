@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+};
+use serde::Deserialize;
+
+use crate::repositories::{
+    Repositories,
+    error::PersistenceError,
+    follow::{FollowTarget, NewFollow, TopTarget},
+};
+
+const DEFAULT_TOP_DAYS: i64 = 7;
+const DEFAULT_TOP_LIMIT: i64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct TopQuery {
+    days: Option<i64>,
+    limit: Option<i64>,
+}
+
+/// Stores the edge and returns the followed account with every tracked KOL following it.
+pub async fn record_follow(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Json(follow): Json<NewFollow>,
+) -> Result<Json<FollowTarget>, PersistenceError> {
+    repos.follow.record(&follow).await?;
+
+    Ok(Json(repos.follow.target(&follow.followee).await?))
+}
+
+pub async fn get_target(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Path(followee): Path<String>,
+) -> Result<Json<FollowTarget>, PersistenceError> {
+    let target = repos.follow.target(&followee).await?;
+
+    if target.followers.is_empty() {
+        return Err(PersistenceError::NotFound);
+    }
+
+    Ok(Json(target))
+}
+
+pub async fn get_top(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Query(query): Query<TopQuery>,
+) -> Result<Json<Vec<TopTarget>>, PersistenceError> {
+    let days = query.days.unwrap_or(DEFAULT_TOP_DAYS).clamp(1, 365);
+    let limit = query.limit.unwrap_or(DEFAULT_TOP_LIMIT).clamp(1, 1000);
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as i64);
+
+    Ok(Json(
+        repos
+            .follow
+            .top(now_ms - days * 24 * 60 * 60 * 1000, limit)
+            .await?,
+    ))
+}
@@ -1,15 +1,45 @@
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 
+use crate::api::routes::follows::{get_target, get_top, record_follow};
+use crate::api::routes::parse_failures::{
+    capture_failure, get_failure, get_open_failures, resolve_failure,
+};
 use crate::api::routes::ping::ping;
+use crate::api::routes::providers::{
+    delete_provider, get_provider, get_providers, get_stats, record_activity, upsert_provider,
+};
 
 pub mod cors;
+mod follows;
 pub mod middlewares;
+mod parse_failures;
 mod ping;
+mod providers;
 
 pub fn routes() -> Router {
     Router::new().merge(_routes())
 }
 
 fn _routes() -> Router {
-    Router::new().route("/ping", get(ping))
+    Router::new()
+        .route("/ping", get(ping))
+        .route("/providers", get(get_providers).put(upsert_provider))
+        .route("/providers/stats", get(get_stats))
+        .route(
+            "/providers/{chat_id}",
+            get(get_provider).delete(delete_provider),
+        )
+        .route("/providers/{chat_id}/activity", post(record_activity))
+        .route(
+            "/parse-failures",
+            get(get_open_failures).post(capture_failure),
+        )
+        .route("/parse-failures/{id}", get(get_failure))
+        .route("/parse-failures/{id}/resolve", post(resolve_failure))
+        .route("/follows", post(record_follow))
+        .route("/follows/top", get(get_top))
+        .route("/follows/{followee}", get(get_target))
 }
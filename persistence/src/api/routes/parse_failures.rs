@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::repositories::{
+    Repositories,
+    error::PersistenceError,
+    parse_failure::{NewParseFailure, ParseFailure},
+};
+
+const DEFAULT_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct OpenQuery {
+    limit: Option<i64>,
+}
+
+pub async fn get_open_failures(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Query(query): Query<OpenQuery>,
+) -> Result<Json<Vec<ParseFailure>>, PersistenceError> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, 1000);
+
+    Ok(Json(repos.parse_failure.get_open(limit).await?))
+}
+
+pub async fn get_failure(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ParseFailure>, PersistenceError> {
+    repos
+        .parse_failure
+        .get_by_id(id)
+        .await?
+        .map(Json)
+        .ok_or(PersistenceError::NotFound)
+}
+
+pub async fn capture_failure(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Json(failure): Json<NewParseFailure>,
+) -> Result<Json<ParseFailure>, PersistenceError> {
+    Ok(Json(repos.parse_failure.capture(&failure).await?))
+}
+
+pub async fn resolve_failure(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, PersistenceError> {
+    match repos.parse_failure.resolve(id).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(PersistenceError::NotFound),
+    }
+}
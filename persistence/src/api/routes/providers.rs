@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+use serde::Deserialize;
+
+use crate::repositories::{
+    Repositories,
+    error::PersistenceError,
+    provider::{NewProvider, Provider, ProviderActivity, ProviderStats},
+};
+
+const DEFAULT_STATS_DAYS: i32 = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct StatsQuery {
+    days: Option<i32>,
+}
+
+pub async fn get_providers(
+    Extension(repos): Extension<Arc<Repositories>>,
+) -> Result<Json<Vec<Provider>>, PersistenceError> {
+    Ok(Json(repos.provider.get_all().await?))
+}
+
+pub async fn get_provider(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Path(chat_id): Path<i64>,
+) -> Result<Json<Provider>, PersistenceError> {
+    repos
+        .provider
+        .get_by_chat_id(chat_id)
+        .await?
+        .map(Json)
+        .ok_or(PersistenceError::NotFound)
+}
+
+pub async fn upsert_provider(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Json(provider): Json<NewProvider>,
+) -> Result<Json<Provider>, PersistenceError> {
+    Ok(Json(repos.provider.upsert(&provider).await?))
+}
+
+pub async fn delete_provider(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Path(chat_id): Path<i64>,
+) -> Result<StatusCode, PersistenceError> {
+    match repos.provider.delete(chat_id).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(PersistenceError::NotFound),
+    }
+}
+
+pub async fn record_activity(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Path(chat_id): Path<i64>,
+    Json(activity): Json<ProviderActivity>,
+) -> Result<StatusCode, PersistenceError> {
+    match repos.provider.record(chat_id, activity).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(PersistenceError::NotFound),
+    }
+}
+
+/// Rolling stats of every provider over the last `days` days, 30 by default.
+pub async fn get_stats(
+    Extension(repos): Extension<Arc<Repositories>>,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<Vec<ProviderStats>>, PersistenceError> {
+    let days = query.days.unwrap_or(DEFAULT_STATS_DAYS).max(1);
+
+    Ok(Json(repos.provider.stats(days).await?))
+}
@@ -48,6 +48,8 @@ pub async fn run(pool: &PgPool) -> Result<(), sqlx::Error> {
 #[derive(Serialize, Deserialize)]
 struct Dump {
     chats: Vec<crate::repositories::chat::Chat>,
+    #[serde(default)]
+    providers: Vec<crate::repositories::provider::Provider>,
 }
 
 #[allow(dead_code)]
@@ -55,6 +57,7 @@ struct Dump {
 pub async fn dump_all(repos: &Repositories) -> Result<(), PersistenceError> {
     let dump = Dump {
         chats: repos.chat.get_all().await?,
+        providers: repos.provider.get_all().await?,
     };
 
     let json = serde_json::to_string_pretty(&dump).map_err(|_| PersistenceError::DatabaseError)?;
@@ -76,6 +79,10 @@ pub async fn restore_all(repos: &Repositories) -> Result<(), PersistenceError> {
         repos.chat.create(&chat.name, &chat.chat_id).await?;
     }
 
-    println!("Restored chats");
+    for provider in dump.providers {
+        repos.provider.upsert(&provider.into()).await?;
+    }
+
+    println!("Restored chats and providers");
     Ok(())
 }
@@ -3,6 +3,8 @@ mod db;
 mod repositories;
 mod utils;
 
+use std::sync::Arc;
+
 use crate::{
     db::{connect, health_check, run},
     repositories::Repositories,
@@ -16,7 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let pool = connect(&database_url).await?;
 
-    let _repos = Repositories::new(pool.clone());
+    let repos = Arc::new(Repositories::new(pool.clone()));
 
     println!("Connected to database!");
 
@@ -24,7 +26,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     run(&pool).await?;
 
-    api::start_api_server("127.0.0.1", 8180).await?;
+    api::start_api_server("127.0.0.1", 8180, repos).await?;
 
     println!("Server stopped accepting new connections.");
 
@@ -0,0 +1,183 @@
+pub mod queries;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::repositories::error::PersistenceError;
+
+/// A tracked KOL following an account, as relayed by the follow tracker.
+#[derive(Debug, Deserialize)]
+pub struct NewFollow {
+    pub follower: String,
+    pub followee: String,
+    pub followed_at_ms: i64,
+    /// The followed account's profile as shown in the relay message.
+    pub profile_info: String,
+}
+
+#[derive(Debug, sqlx::FromRow, Serialize, PartialEq)]
+pub struct KolFollower {
+    pub follower: String,
+    pub followed_at_ms: i64,
+}
+
+/// Which tracked KOLs follow an account, earliest first.
+#[derive(Debug, Serialize)]
+pub struct FollowTarget {
+    pub followee: String,
+    pub first_followed_at_ms: Option<i64>,
+    pub followers: Vec<KolFollower>,
+}
+
+#[derive(Debug, sqlx::FromRow, Serialize)]
+pub struct TopTarget {
+    pub followee: String,
+    pub followers: i64,
+    pub first_followed_at_ms: i64,
+}
+
+pub struct FollowRepository {
+    pool: PgPool,
+}
+
+impl FollowRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, follow: &NewFollow) -> Result<(), PersistenceError> {
+        sqlx::query(queries::UPSERT)
+            .bind(&follow.follower)
+            .bind(&follow.followee)
+            .bind(follow.followed_at_ms)
+            .bind(&follow.profile_info)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn followers(&self, followee: &str) -> Result<Vec<KolFollower>, PersistenceError> {
+        Ok(sqlx::query_as::<_, KolFollower>(queries::SELECT_FOLLOWERS)
+            .bind(followee)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// None when no tracked KOL follows the account.
+    pub async fn first_followed_at(&self, followee: &str) -> Result<Option<i64>, PersistenceError> {
+        Ok(
+            sqlx::query_scalar::<_, Option<i64>>(queries::SELECT_FIRST_FOLLOWED_AT)
+                .bind(followee)
+                .fetch_one(&self.pool)
+                .await?,
+        )
+    }
+
+    pub async fn target(&self, followee: &str) -> Result<FollowTarget, PersistenceError> {
+        Ok(FollowTarget {
+            followee: followee.to_lowercase(),
+            first_followed_at_ms: self.first_followed_at(followee).await?,
+            followers: self.followers(followee).await?,
+        })
+    }
+
+    /// Accounts followed by the most tracked KOLs since `since_ms`.
+    pub async fn top(&self, since_ms: i64, limit: i64) -> Result<Vec<TopTarget>, PersistenceError> {
+        Ok(sqlx::query_as::<_, TopTarget>(queries::SELECT_TOP)
+            .bind(since_ms)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    fn follow(follower: &str, followee: &str, followed_at_ms: i64) -> NewFollow {
+        NewFollow {
+            follower: follower.into(),
+            followee: followee.into(),
+            followed_at_ms,
+            profile_info: "753 Following | 719 Followers".into(),
+        }
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn followers_are_listed_earliest_first(pool: PgPool) {
+        let repo = FollowRepository::new(pool);
+
+        repo.record(&follow("toly", "yashaxbt", 2_000))
+            .await
+            .unwrap();
+        repo.record(&follow("pmarca", "YashaXBT", 1_000))
+            .await
+            .unwrap();
+
+        let target = repo.target("yashaxbt").await.unwrap();
+
+        assert_eq!(target.first_followed_at_ms, Some(1_000));
+        assert_eq!(
+            target
+                .followers
+                .iter()
+                .map(|follower| follower.follower.as_str())
+                .collect::<Vec<_>>(),
+            vec!["pmarca", "toly"]
+        );
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn following_again_keeps_the_first_time(pool: PgPool) {
+        let repo = FollowRepository::new(pool);
+
+        repo.record(&follow("toly", "yashaxbt", 1_000))
+            .await
+            .unwrap();
+        repo.record(&follow("toly", "yashaxbt", 5_000))
+            .await
+            .unwrap();
+
+        let followers = repo.followers("yashaxbt").await.unwrap();
+
+        assert_eq!(
+            followers,
+            vec![KolFollower {
+                follower: "toly".into(),
+                followed_at_ms: 1_000
+            }]
+        );
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn unknown_account_has_no_first_follow(pool: PgPool) {
+        let repo = FollowRepository::new(pool);
+
+        assert_eq!(repo.first_followed_at("nobody").await.unwrap(), None);
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn top_counts_follows_since(pool: PgPool) {
+        let repo = FollowRepository::new(pool);
+
+        repo.record(&follow("toly", "old", 100)).await.unwrap();
+        repo.record(&follow("toly", "new", 2_000)).await.unwrap();
+        repo.record(&follow("pmarca", "new", 3_000)).await.unwrap();
+        repo.record(&follow("pmarca", "other", 3_000))
+            .await
+            .unwrap();
+
+        let top = repo.top(1_000, 10).await.unwrap();
+
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].followee, "new");
+        assert_eq!(top[0].followers, 2);
+    }
+}
@@ -0,0 +1,25 @@
+// WHY: A KOL that unfollows and follows again keeps its first follow time;
+// the profile is refreshed since it reflects the account as of the latest follow.
+pub const UPSERT: &str =
+    "INSERT INTO kol_follows (follower, followee, followed_at_ms, profile_info)
+         VALUES (LOWER($1), LOWER($2), $3, $4)
+         ON CONFLICT (follower, followee) DO UPDATE SET
+             followed_at_ms = LEAST(kol_follows.followed_at_ms, EXCLUDED.followed_at_ms),
+             profile_info = EXCLUDED.profile_info";
+
+pub const SELECT_FOLLOWERS: &str = "SELECT follower, followed_at_ms
+         FROM kol_follows
+         WHERE followee = LOWER($1)
+         ORDER BY followed_at_ms";
+
+pub const SELECT_FIRST_FOLLOWED_AT: &str = "SELECT MIN(followed_at_ms)
+         FROM kol_follows
+         WHERE followee = LOWER($1)";
+
+pub const SELECT_TOP: &str =
+    "SELECT followee, COUNT(*) AS followers, MIN(followed_at_ms) AS first_followed_at_ms
+         FROM kol_follows
+         WHERE followed_at_ms >= $1
+         GROUP BY followee
+         ORDER BY followers DESC, first_followed_at_ms DESC
+         LIMIT $2";
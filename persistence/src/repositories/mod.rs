@@ -1,16 +1,25 @@
 pub mod chat;
 pub mod error;
+pub mod follow;
+pub mod parse_failure;
+pub mod provider;
 
 use sqlx::PgPool;
 
 pub struct Repositories {
     pub chat: chat::ChatRepository,
+    pub provider: provider::ProviderRepository,
+    pub parse_failure: parse_failure::ParseFailureRepository,
+    pub follow: follow::FollowRepository,
 }
 
 impl Repositories {
     pub fn new(pool: PgPool) -> Self {
         Self {
             chat: chat::ChatRepository::new(pool.clone()),
+            provider: provider::ProviderRepository::new(pool.clone()),
+            parse_failure: parse_failure::ParseFailureRepository::new(pool.clone()),
+            follow: follow::FollowRepository::new(pool.clone()),
         }
     }
 }
@@ -0,0 +1,142 @@
+pub mod queries;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::repositories::error::PersistenceError;
+
+/// A message from a signal source that looked like a signal but could not be parsed.
+#[derive(Debug, sqlx::FromRow, Serialize, Deserialize)]
+pub struct ParseFailure {
+    pub id: Uuid,
+    pub chat_id: i64,
+    pub message_id: i32,
+    pub text: String,
+    pub reason: String,
+    pub posted_at_ms: i64,
+    /// Set once the message was replayed successfully.
+    pub resolved: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewParseFailure {
+    pub chat_id: i64,
+    pub message_id: i32,
+    pub text: String,
+    pub reason: String,
+    pub posted_at_ms: i64,
+}
+
+pub struct ParseFailureRepository {
+    pool: PgPool,
+}
+
+impl ParseFailureRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Unresolved failures, newest first.
+    pub async fn get_open(&self, limit: i64) -> Result<Vec<ParseFailure>, PersistenceError> {
+        Ok(sqlx::query_as::<_, ParseFailure>(queries::SELECT_OPEN)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    pub async fn get_by_id(&self, id: Uuid) -> Result<Option<ParseFailure>, PersistenceError> {
+        Ok(sqlx::query_as::<_, ParseFailure>(queries::SELECT_BY_ID)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    pub async fn capture(
+        &self,
+        failure: &NewParseFailure,
+    ) -> Result<ParseFailure, PersistenceError> {
+        Ok(sqlx::query_as::<_, ParseFailure>(queries::UPSERT)
+            .bind(failure.chat_id)
+            .bind(failure.message_id)
+            .bind(&failure.text)
+            .bind(&failure.reason)
+            .bind(failure.posted_at_ms)
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    pub async fn resolve(&self, id: Uuid) -> Result<bool, PersistenceError> {
+        let result = sqlx::query(queries::RESOLVE)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    fn failure(message_id: i32, reason: &str) -> NewParseFailure {
+        NewParseFailure {
+            chat_id: 1,
+            message_id,
+            text: "ETH SHORT entry 3100".into(),
+            reason: reason.into(),
+            posted_at_ms: 1_000 + message_id as i64,
+        }
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn capture_and_get_open(pool: PgPool) {
+        let repo = ParseFailureRepository::new(pool);
+
+        repo.capture(&failure(1, "missing stop loss"))
+            .await
+            .unwrap();
+        repo.capture(&failure(2, "missing targets")).await.unwrap();
+
+        let open = repo.get_open(10).await.unwrap();
+
+        assert_eq!(open.len(), 2);
+        assert_eq!(open[0].message_id, 2);
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn resolved_failures_are_not_open(pool: PgPool) {
+        let repo = ParseFailureRepository::new(pool);
+
+        let captured = repo
+            .capture(&failure(1, "missing stop loss"))
+            .await
+            .unwrap();
+
+        assert!(repo.resolve(captured.id).await.unwrap());
+        assert!(repo.get_open(10).await.unwrap().is_empty());
+        assert!(repo.get_by_id(captured.id).await.unwrap().unwrap().resolved);
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn failing_again_reopens_the_same_entry(pool: PgPool) {
+        let repo = ParseFailureRepository::new(pool);
+
+        let first = repo
+            .capture(&failure(1, "missing stop loss"))
+            .await
+            .unwrap();
+        repo.resolve(first.id).await.unwrap();
+
+        let again = repo.capture(&failure(1, "missing targets")).await.unwrap();
+
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.reason, "missing targets");
+        assert!(!again.resolved);
+    }
+}
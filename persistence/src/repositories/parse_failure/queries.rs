@@ -0,0 +1,22 @@
+pub const SELECT_OPEN: &str = "SELECT id, chat_id, message_id, text, reason, posted_at_ms, resolved
+         FROM parse_failures
+         WHERE NOT resolved
+         ORDER BY posted_at_ms DESC
+         LIMIT $1";
+
+pub const SELECT_BY_ID: &str =
+    "SELECT id, chat_id, message_id, text, reason, posted_at_ms, resolved
+         FROM parse_failures
+         WHERE id = $1";
+
+// WHY: A message that fails again, e.g. after an edit, reopens its existing entry.
+pub const UPSERT: &str =
+    "INSERT INTO parse_failures (chat_id, message_id, text, reason, posted_at_ms)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (chat_id, message_id) DO UPDATE SET
+             text = EXCLUDED.text,
+             reason = EXCLUDED.reason,
+             resolved = FALSE
+         RETURNING id, chat_id, message_id, text, reason, posted_at_ms, resolved";
+
+pub const RESOLVE: &str = "UPDATE parse_failures SET resolved = TRUE WHERE id = $1";
@@ -0,0 +1,326 @@
+pub mod queries;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::repositories::error::PersistenceError;
+
+/// A signal channel or KOL whose posts are traded.
+#[derive(Debug, sqlx::FromRow, Serialize, Deserialize)]
+pub struct Provider {
+    pub id: Uuid,
+    pub name: String,
+    pub chat_id: i64,
+    pub parser: String,
+    pub enabled: bool,
+    pub weight: f64,
+    pub risk_multiplier: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewProvider {
+    pub name: String,
+    pub chat_id: i64,
+    pub parser: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_one")]
+    pub weight: f64,
+    #[serde(default = "default_one")]
+    pub risk_multiplier: f64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_one() -> f64 {
+    1.0
+}
+
+impl From<Provider> for NewProvider {
+    fn from(provider: Provider) -> Self {
+        Self {
+            name: provider.name,
+            chat_id: provider.chat_id,
+            parser: provider.parser,
+            enabled: provider.enabled,
+            weight: provider.weight,
+            risk_multiplier: provider.risk_multiplier,
+        }
+    }
+}
+
+/// Something a provider did, counted in today's stats.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProviderActivity {
+    Signal,
+    ParseFailure,
+    /// A trade from one of the provider's signals closed with this realised PnL, in USDT.
+    Closed {
+        pnl: f64,
+    },
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ProviderTotals {
+    pub chat_id: i64,
+    pub signals: i64,
+    pub parse_failures: i64,
+    pub wins: i64,
+    pub losses: i64,
+    pub pnl: f64,
+}
+
+/// Rolling stats over the last `days` days.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ProviderStats {
+    pub chat_id: i64,
+    pub days: i32,
+    pub signals_per_day: f64,
+    /// Share of signal-looking messages that could not be parsed.
+    pub parse_failure_rate: f64,
+    pub closed_trades: i64,
+    pub win_rate: f64,
+    /// Average realised PnL per closed trade, in USDT.
+    pub expectancy: f64,
+}
+
+impl ProviderStats {
+    pub fn new(totals: &ProviderTotals, days: i32) -> Self {
+        let attempts = totals.signals + totals.parse_failures;
+        let closed_trades = totals.wins + totals.losses;
+
+        let ratio = |part: f64, whole: i64| {
+            if whole > 0 { part / whole as f64 } else { 0.0 }
+        };
+
+        Self {
+            chat_id: totals.chat_id,
+            days,
+            signals_per_day: ratio(totals.signals as f64, days.max(1) as i64),
+            parse_failure_rate: ratio(totals.parse_failures as f64, attempts),
+            closed_trades,
+            win_rate: ratio(totals.wins as f64, closed_trades),
+            expectancy: ratio(totals.pnl, closed_trades),
+        }
+    }
+}
+
+pub struct ProviderRepository {
+    pool: PgPool,
+}
+
+impl ProviderRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<Provider>, PersistenceError> {
+        Ok(sqlx::query_as::<_, Provider>(queries::SELECT_ALL)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    pub async fn get_by_chat_id(&self, chat_id: i64) -> Result<Option<Provider>, PersistenceError> {
+        Ok(sqlx::query_as::<_, Provider>(queries::SELECT_BY_CHAT_ID)
+            .bind(chat_id)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Creates the provider, or replaces the settings of the one with the same chat.
+    pub async fn upsert(&self, provider: &NewProvider) -> Result<Provider, PersistenceError> {
+        Ok(sqlx::query_as::<_, Provider>(queries::UPSERT)
+            .bind(&provider.name)
+            .bind(provider.chat_id)
+            .bind(&provider.parser)
+            .bind(provider.enabled)
+            .bind(provider.weight)
+            .bind(provider.risk_multiplier)
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    pub async fn delete(&self, chat_id: i64) -> Result<bool, PersistenceError> {
+        let result = sqlx::query(queries::DELETE)
+            .bind(chat_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns false when no provider has this chat.
+    pub async fn record(
+        &self,
+        chat_id: i64,
+        activity: ProviderActivity,
+    ) -> Result<bool, PersistenceError> {
+        let (signals, parse_failures, wins, losses, pnl) = match activity {
+            ProviderActivity::Signal => (1, 0, 0, 0, 0.0),
+            ProviderActivity::ParseFailure => (0, 1, 0, 0, 0.0),
+            ProviderActivity::Closed { pnl } if pnl > 0.0 => (0, 0, 1, 0, pnl),
+            ProviderActivity::Closed { pnl } => (0, 0, 0, 1, pnl),
+        };
+
+        let result = sqlx::query(queries::RECORD_ACTIVITY)
+            .bind(chat_id)
+            .bind(signals)
+            .bind(parse_failures)
+            .bind(wins)
+            .bind(losses)
+            .bind(pnl)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn stats(&self, days: i32) -> Result<Vec<ProviderStats>, PersistenceError> {
+        let totals = sqlx::query_as::<_, ProviderTotals>(queries::SELECT_TOTALS)
+            .bind(days)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(totals
+            .iter()
+            .map(|totals| ProviderStats::new(totals, days))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    fn new_provider(name: &str, chat_id: i64) -> NewProvider {
+        NewProvider {
+            name: name.into(),
+            chat_id,
+            parser: "labeled".into(),
+            enabled: true,
+            weight: 1.0,
+            risk_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn stats_from_totals() {
+        let totals = ProviderTotals {
+            chat_id: 1,
+            signals: 30,
+            parse_failures: 10,
+            wins: 6,
+            losses: 4,
+            pnl: 20.0,
+        };
+
+        let stats = ProviderStats::new(&totals, 30);
+
+        assert_eq!(stats.signals_per_day, 1.0);
+        assert_eq!(stats.parse_failure_rate, 0.25);
+        assert_eq!(stats.closed_trades, 10);
+        assert_eq!(stats.win_rate, 0.6);
+        assert_eq!(stats.expectancy, 2.0);
+    }
+
+    #[test]
+    fn stats_without_activity_are_zero() {
+        let totals = ProviderTotals {
+            chat_id: 1,
+            signals: 0,
+            parse_failures: 0,
+            wins: 0,
+            losses: 0,
+            pnl: 0.0,
+        };
+
+        let stats = ProviderStats::new(&totals, 0);
+
+        assert_eq!(stats.signals_per_day, 0.0);
+        assert_eq!(stats.win_rate, 0.0);
+        assert_eq!(stats.expectancy, 0.0);
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn upsert_replaces_settings(pool: PgPool) {
+        let repo = ProviderRepository::new(pool);
+
+        let created = repo.upsert(&new_provider("Whales", 1)).await.unwrap();
+
+        let mut update = new_provider("Whales", 1);
+        update.enabled = false;
+        update.risk_multiplier = 0.5;
+
+        let updated = repo.upsert(&update).await.unwrap();
+
+        assert_eq!(updated.id, created.id);
+        assert!(!updated.enabled);
+        assert_eq!(updated.risk_multiplier, 0.5);
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn duplicate_name_on_another_chat_fails(pool: PgPool) {
+        let repo = ProviderRepository::new(pool);
+
+        repo.upsert(&new_provider("Whales", 1)).await.unwrap();
+
+        let result = repo.upsert(&new_provider("Whales", 2)).await;
+
+        assert!(matches!(result, Err(PersistenceError::Conflict)));
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn record_adds_up_todays_activity(pool: PgPool) {
+        let repo = ProviderRepository::new(pool);
+
+        repo.upsert(&new_provider("Whales", 1)).await.unwrap();
+
+        for activity in [
+            ProviderActivity::Signal,
+            ProviderActivity::Signal,
+            ProviderActivity::ParseFailure,
+            ProviderActivity::Closed { pnl: 12.0 },
+            ProviderActivity::Closed { pnl: -4.0 },
+        ] {
+            assert!(repo.record(1, activity).await.unwrap());
+        }
+
+        let stats = repo.stats(1).await.unwrap();
+
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].signals_per_day, 2.0);
+        assert_eq!(stats[0].win_rate, 0.5);
+        assert_eq!(stats[0].expectancy, 4.0);
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn record_for_unknown_chat_returns_false(pool: PgPool) {
+        let repo = ProviderRepository::new(pool);
+
+        let recorded = repo.record(42, ProviderActivity::Signal).await.unwrap();
+
+        assert!(!recorded);
+    }
+
+    #[sqlx::test]
+    #[ignore]
+    async fn delete_removes_stats(pool: PgPool) {
+        let repo = ProviderRepository::new(pool);
+
+        repo.upsert(&new_provider("Whales", 1)).await.unwrap();
+        repo.record(1, ProviderActivity::Signal).await.unwrap();
+
+        assert!(repo.delete(1).await.unwrap());
+        assert!(repo.get_by_chat_id(1).await.unwrap().is_none());
+        assert!(repo.stats(30).await.unwrap().is_empty());
+    }
+}
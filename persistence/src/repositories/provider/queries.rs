@@ -0,0 +1,47 @@
+pub const SELECT_ALL: &str = "SELECT id, name, chat_id, parser, enabled, weight, risk_multiplier
+         FROM providers
+         ORDER BY name";
+
+pub const SELECT_BY_CHAT_ID: &str =
+    "SELECT id, name, chat_id, parser, enabled, weight, risk_multiplier
+         FROM providers
+         WHERE chat_id = $1";
+
+pub const UPSERT: &str =
+    "INSERT INTO providers (name, chat_id, parser, enabled, weight, risk_multiplier)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (chat_id) DO UPDATE SET
+             name = EXCLUDED.name,
+             parser = EXCLUDED.parser,
+             enabled = EXCLUDED.enabled,
+             weight = EXCLUDED.weight,
+             risk_multiplier = EXCLUDED.risk_multiplier
+         RETURNING id, name, chat_id, parser, enabled, weight, risk_multiplier";
+
+pub const DELETE: &str = "DELETE FROM providers WHERE chat_id = $1";
+
+// WHY: Selecting from providers makes an unknown chat insert nothing instead of failing.
+pub const RECORD_ACTIVITY: &str = "INSERT INTO provider_daily_stats
+             (provider_id, day, signals, parse_failures, wins, losses, pnl)
+         SELECT id, (NOW() AT TIME ZONE 'UTC')::DATE, $2, $3, $4, $5, $6
+         FROM providers
+         WHERE chat_id = $1
+         ON CONFLICT (provider_id, day) DO UPDATE SET
+             signals = provider_daily_stats.signals + EXCLUDED.signals,
+             parse_failures = provider_daily_stats.parse_failures + EXCLUDED.parse_failures,
+             wins = provider_daily_stats.wins + EXCLUDED.wins,
+             losses = provider_daily_stats.losses + EXCLUDED.losses,
+             pnl = provider_daily_stats.pnl + EXCLUDED.pnl";
+
+/// Totals over the last `$1` UTC days, today included. Providers without activity get zeros.
+pub const SELECT_TOTALS: &str = "SELECT p.chat_id,
+             COALESCE(SUM(s.signals), 0)::BIGINT AS signals,
+             COALESCE(SUM(s.parse_failures), 0)::BIGINT AS parse_failures,
+             COALESCE(SUM(s.wins), 0)::BIGINT AS wins,
+             COALESCE(SUM(s.losses), 0)::BIGINT AS losses,
+             COALESCE(SUM(s.pnl), 0)::DOUBLE PRECISION AS pnl
+         FROM providers p
+         LEFT JOIN provider_daily_stats s
+             ON s.provider_id = p.id
+             AND s.day > (NOW() AT TIME ZONE 'UTC')::DATE - $1::INTEGER
+         GROUP BY p.chat_id";